@@ -0,0 +1,124 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		cred::BasicAuthCredential,
+		remotes::{clone, CloneOptions},
+	},
+	AsyncGitNotification, ProgressPercent, RemoteProgress,
+};
+use crossbeam_channel::unbounded;
+use std::{
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	thread,
+};
+
+enum JobState {
+	Request {
+		url: String,
+		path: PathBuf,
+		options: CloneOptions,
+		basic_credential: Option<BasicAuthCredential>,
+	},
+	Response(Result<PathBuf>),
+}
+
+///
+#[derive(Clone)]
+pub struct AsyncCloneJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncCloneJob {
+	///
+	pub fn new(
+		url: String,
+		path: PathBuf,
+		options: CloneOptions,
+		basic_credential: Option<BasicAuthCredential>,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				url,
+				path,
+				options,
+				basic_credential,
+			}))),
+		}
+	}
+
+	/// path of the new repository once the clone succeeded
+	pub fn result(&self) -> Option<Result<PathBuf>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncCloneJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ProgressPercent;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request {
+					url,
+					path,
+					options,
+					basic_credential,
+				} => {
+					let (sender, receiver) = unbounded();
+
+					let result = thread::scope(|s| {
+						// the channel closes once `clone` returned
+						s.spawn(|| {
+							for progress in receiver {
+								let progress =
+									RemoteProgress::from(progress)
+										.progress;
+								if params
+									.set_progress(progress)
+									.unwrap_or_default()
+								{
+									params
+										.send(AsyncGitNotification::Clone)
+										.ok();
+								}
+							}
+						});
+
+						clone(
+							&url,
+							&path,
+							&options,
+							basic_credential,
+							Some(sender),
+						)
+					});
+
+					JobState::Response(result.map(|()| path))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Clone)
+	}
+}
@@ -0,0 +1,78 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{describe_commit, CommitId, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(CommitId),
+	Response(Result<Option<String>>),
+}
+
+/// describes a commit relative to the closest tag
+#[derive(Clone)]
+pub struct AsyncDescribeJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+	commit: CommitId,
+}
+
+///
+impl AsyncDescribeJob {
+	///
+	pub fn new(repo: RepoPath, commit: CommitId) -> Self {
+		Self {
+			repo,
+			commit,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				commit,
+			)))),
+		}
+	}
+
+	/// the described commit
+	pub const fn commit(&self) -> CommitId {
+		self.commit
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<Option<String>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncDescribeJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(commit) => JobState::Response(
+					describe_commit(&self.repo, commit),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Describe)
+	}
+}
@@ -3,7 +3,7 @@
 use crate::{
 	asyncjob::{AsyncJob, RunParams},
 	error::Result,
-	sync::remotes::fetch_all,
+	sync::remotes::{fetch_all, FetchSettings},
 	sync::{cred::BasicAuthCredential, RepoPath},
 	AsyncGitNotification, ProgressPercent,
 };
@@ -11,7 +11,7 @@ use crate::{
 use std::sync::{Arc, Mutex};
 
 enum JobState {
-	Request(Option<BasicAuthCredential>),
+	Request(FetchSettings, Option<BasicAuthCredential>),
 	Response(Result<()>),
 }
 
@@ -27,15 +27,31 @@ impl AsyncFetchJob {
 	///
 	pub fn new(
 		repo: RepoPath,
+		settings: FetchSettings,
 		basic_credential: Option<BasicAuthCredential>,
 	) -> Self {
 		Self {
 			repo,
 			state: Arc::new(Mutex::new(Some(JobState::Request(
+				settings,
 				basic_credential,
 			)))),
 		}
 	}
+
+	/// result of the fetch once it finished
+	pub fn result(&self) -> Option<Result<()>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(..) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
 }
 
 impl AsyncJob for AsyncFetchJob {
@@ -48,10 +64,11 @@ impl AsyncJob for AsyncFetchJob {
 	) -> Result<Self::Notification> {
 		if let Ok(mut state) = self.state.lock() {
 			*state = state.take().map(|state| match state {
-				JobState::Request(basic_credentials) => {
+				JobState::Request(settings, basic_credentials) => {
 					//TODO: support progress
 					let result = fetch_all(
 						&self.repo,
+						&settings,
 						&basic_credentials,
 						&None,
 					);
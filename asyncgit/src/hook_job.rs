@@ -0,0 +1,135 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		hooks_pre_commit_controlled, HookControl, HookResult,
+		RepoPath,
+	},
+	AsyncGitNotification, ProgressPercent,
+};
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant},
+};
+
+/// output lines trigger a notification at most this often,
+/// the one at the end of the job shows whatever came in since
+const OUTPUT_NOTIFY_INTERVAL: Duration = Duration::from_millis(100);
+
+enum JobState {
+	Request,
+	Response(Result<HookResult>),
+}
+
+/// runs the pre-commit hook collecting its output while it runs
+#[derive(Clone)]
+pub struct AsyncHookJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	output: Arc<Mutex<Vec<String>>>,
+	cancel: Arc<AtomicBool>,
+	timeout: Option<Duration>,
+	repo: RepoPath,
+}
+
+///
+impl AsyncHookJob {
+	/// the hook gets killed if it runs longer than `timeout`
+	pub fn new(repo: RepoPath, timeout: Option<Duration>) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request))),
+			output: Arc::new(Mutex::new(Vec::new())),
+			cancel: Arc::new(AtomicBool::new(false)),
+			timeout,
+			repo,
+		}
+	}
+
+	/// lines the hook wrote to stdout and stderr so far
+	pub fn output(&self) -> Vec<String> {
+		self.output
+			.lock()
+			.map(|output| output.clone())
+			.unwrap_or_default()
+	}
+
+	/// kills the hook and everything it spawned
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	/// result of the hook once it exited
+	pub fn result(&self) -> Option<Result<HookResult>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncHookJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ProgressPercent;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request => {
+					let output = Arc::clone(&self.output);
+					let last_notify = Mutex::new(None::<Instant>);
+					let control = HookControl {
+						output: Some(Arc::new(move |line: &str| {
+							if let Ok(mut output) = output.lock() {
+								output.push(line.to_string());
+							}
+
+							let due = last_notify.lock().is_ok_and(
+								|mut last| {
+									let due =
+										last.is_none_or(|last| {
+											last.elapsed()
+											>= OUTPUT_NOTIFY_INTERVAL
+										});
+									if due {
+										*last = Some(Instant::now());
+									}
+									due
+								},
+							);
+
+							if due {
+								params
+									.send(AsyncGitNotification::Hook)
+									.ok();
+							}
+						})),
+						cancel: Arc::clone(&self.cancel),
+						timeout: self.timeout,
+					};
+
+					JobState::Response(hooks_pre_commit_controlled(
+						&self.repo, &control,
+					))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Hook)
+	}
+}
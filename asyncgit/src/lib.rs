@@ -47,11 +47,14 @@ pub mod asyncjob;
 mod blame;
 mod branches;
 pub mod cached;
+mod clone_job;
 mod commit_files;
+pub mod describe;
 mod diff;
 mod error;
 mod fetch_job;
 mod filter_commits;
+mod hook_job;
 mod progress;
 mod pull;
 mod push;
@@ -63,15 +66,19 @@ mod status;
 pub mod sync;
 mod tags;
 mod treefiles;
+pub mod verify_commits;
+pub mod verify_tags;
 
 pub use crate::{
 	blame::{AsyncBlame, BlameParams},
 	branches::AsyncBranchesJob,
+	clone_job::AsyncCloneJob,
 	commit_files::{AsyncCommitFiles, CommitFilesParams},
 	diff::{AsyncDiff, DiffParams, DiffType},
 	error::{Error, Result},
 	fetch_job::AsyncFetchJob,
 	filter_commits::{AsyncCommitFilterJob, CommitFilterResult},
+	hook_job::AsyncHookJob,
 	progress::ProgressPercent,
 	pull::{AsyncPull, FetchRequest},
 	push::{AsyncPush, PushRequest},
@@ -81,7 +88,7 @@ pub use crate::{
 	status::{AsyncStatus, StatusParams},
 	sync::{
 		diff::{DiffLine, DiffLineType, FileDiff},
-		remotes::push::PushType,
+		remotes::push::{PushForce, PushType},
 		status::{StatusItem, StatusItemType},
 	},
 	tags::AsyncTags,
@@ -128,6 +135,16 @@ pub enum AsyncGitNotification {
 	TreeFiles,
 	///
 	CommitFilter,
+	///
+	Clone,
+	///
+	Hook,
+	///
+	VerifyTags,
+	///
+	VerifyCommits,
+	///
+	Describe,
 }
 
 /// helper function to calculate the hash of an arbitrary type that implements the `Hash` trait
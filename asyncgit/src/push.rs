@@ -3,7 +3,7 @@ use crate::{
 	sync::{
 		cred::BasicAuthCredential,
 		remotes::push::push_raw,
-		remotes::push::{ProgressNotification, PushType},
+		remotes::push::{ProgressNotification, PushForce, PushType},
 		RepoPath,
 	},
 	AsyncGitNotification, RemoteProgress,
@@ -21,12 +21,16 @@ pub struct PushRequest {
 	pub remote: String,
 	///
 	pub branch: String,
+	/// name of the branch on the remote, same as `branch` if `None`
+	pub dst_branch: Option<String>,
 	///
 	pub push_type: PushType,
 	///
-	pub force: bool,
+	pub force: PushForce,
 	///
 	pub delete: bool,
+	/// make the pushed branch the upstream (`-u`)
+	pub set_upstream: bool,
 	///
 	pub basic_credential: Option<BasicAuthCredential>,
 }
@@ -108,9 +112,11 @@ impl AsyncPush {
 				&repo,
 				params.remote.as_str(),
 				params.branch.as_str(),
+				params.dst_branch.as_deref(),
 				params.push_type,
 				params.force,
 				params.delete,
+				params.set_upstream,
 				params.basic_credential.clone(),
 				Some(progress_sender.clone()),
 			);
@@ -130,6 +130,16 @@ impl AsyncLog {
 		self.background.store(true, Ordering::Relaxed);
 	}
 
+	/// forget the walked log so the next `fetch` starts over,
+	/// needed when history changed below an unchanged HEAD
+	pub fn reset(&self) -> Result<()> {
+		if !self.is_pending() {
+			self.clear()?;
+		}
+
+		Ok(())
+	}
+
 	///
 	fn current_head(&self) -> Result<Option<CommitId>> {
 		Ok(*self.current_head.lock()?)
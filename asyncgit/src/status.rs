@@ -9,6 +9,7 @@ use crate::{
 use crossbeam_channel::Sender;
 use std::{
 	hash::Hash,
+	path::Path,
 	sync::{
 		atomic::{AtomicUsize, Ordering},
 		Arc, Mutex,
@@ -16,6 +17,10 @@ use std::{
 	time::{SystemTime, UNIX_EPOCH},
 };
 
+/// identifies a state of the index file, any change to it invalidates
+/// a previous worktree status (even without any worktree changes)
+type IndexStamp = Option<(SystemTime, u64)>;
+
 fn current_tick() -> u128 {
 	SystemTime::now()
 		.duration_since(UNIX_EPOCH)
@@ -29,11 +34,12 @@ pub struct Status {
 }
 
 ///
-#[derive(Default, Hash, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, PartialEq, Eq)]
 pub struct StatusParams {
 	tick: u128,
 	status_type: StatusType,
 	config: Option<ShowUntrackedFilesConfig>,
+	paths: Option<Vec<String>>,
 }
 
 impl StatusParams {
@@ -46,16 +52,54 @@ impl StatusParams {
 			tick: current_tick(),
 			status_type,
 			config,
+			paths: None,
 		}
 	}
+
+	/// only `paths` (relative to the workdir) changed since the last request
+	#[must_use]
+	pub fn with_changed_paths(mut self, paths: Vec<String>) -> Self {
+		self.paths = Some(paths);
+		self
+	}
+
+	/// same request limited to the changes that came in while it ran
+	fn with_missed(mut self, missed: Missed) -> Self {
+		self.paths = (!missed.full).then_some(missed.paths);
+		self
+	}
 }
 
 struct Request<R, A>(R, Option<A>);
 
+/// previous result that path limited refreshes are based on
+#[derive(Default)]
+struct Incremental {
+	base: Option<(StatusType, Option<ShowUntrackedFilesConfig>)>,
+	index: IndexStamp,
+	items: Vec<StatusItem>,
+	fsmonitor_token: Option<String>,
+}
+
+/// changes of requests that were blocked by a pending one
+#[derive(Default)]
+struct Missed {
+	full: bool,
+	paths: Vec<String>,
+}
+
+impl Missed {
+	fn is_empty(&self) -> bool {
+		!self.full && self.paths.is_empty()
+	}
+}
+
 ///
 pub struct AsyncStatus {
 	current: Arc<Mutex<Request<u64, Status>>>,
 	last: Arc<Mutex<Status>>,
+	incremental: Arc<Mutex<Incremental>>,
+	missed: Arc<Mutex<Missed>>,
 	sender: Sender<AsyncGitNotification>,
 	pending: Arc<AtomicUsize>,
 	repo: RepoPath,
@@ -71,6 +115,8 @@ impl AsyncStatus {
 			repo,
 			current: Arc::new(Mutex::new(Request(0, None))),
 			last: Arc::new(Mutex::new(Status::default())),
+			incremental: Arc::new(Mutex::new(Incremental::default())),
+			missed: Arc::new(Mutex::new(Missed::default())),
 			sender,
 			pending: Arc::new(AtomicUsize::new(0)),
 		}
@@ -92,9 +138,22 @@ impl AsyncStatus {
 		&self,
 		params: &StatusParams,
 	) -> Result<Option<Status>> {
-		if self.is_pending() {
-			log::trace!("request blocked, still pending");
-			return Ok(None);
+		{
+			// the worker checks for missed changes under this lock before it finishes
+			let mut missed = self.missed.lock()?;
+
+			if self.is_pending() {
+				log::trace!("request blocked, still pending");
+
+				match &params.paths {
+					Some(paths) => {
+						missed.paths.extend_from_slice(paths);
+					}
+					None => missed.full = true,
+				}
+
+				return Ok(None);
+			}
 		}
 
 		let hash_request = hash(&params);
@@ -118,45 +177,81 @@ impl AsyncStatus {
 
 		let arc_current = Arc::clone(&self.current);
 		let arc_last = Arc::clone(&self.last);
+		let arc_incremental = Arc::clone(&self.incremental);
 		let sender = self.sender.clone();
 		let arc_pending = Arc::clone(&self.pending);
-		let status_type = params.status_type;
-		let config = params.config;
+		let arc_missed = Arc::clone(&self.missed);
 		let repo = self.repo.clone();
+		let mut params = params.clone();
 
 		self.pending.fetch_add(1, Ordering::Relaxed);
 
-		rayon_core::spawn(move || {
+		rayon_core::spawn(move || loop {
 			if let Err(e) = Self::fetch_helper(
 				&repo,
-				status_type,
-				config,
+				params.clone(),
 				hash_request,
 				&arc_current,
 				&arc_last,
+				&arc_incremental,
 			) {
 				log::error!("fetch_helper: {e}");
 			}
 
-			arc_pending.fetch_sub(1, Ordering::Relaxed);
+			let missed = Self::take_missed(&arc_missed, &arc_pending);
 
 			sender
 				.send(AsyncGitNotification::Status)
 				.expect("error sending status");
+
+			match missed {
+				Some(missed) => {
+					log::trace!("status refresh of missed changes");
+					params = params.with_missed(missed);
+				}
+				None => break,
+			}
 		});
 
 		Ok(None)
 	}
 
+	/// changes that were blocked while the worker ran,
+	/// finishes the pending request if there are none
+	fn take_missed(
+		missed: &Mutex<Missed>,
+		pending: &AtomicUsize,
+	) -> Option<Missed> {
+		let mut missed = match missed.lock() {
+			Ok(missed) => missed,
+			Err(e) => {
+				log::error!("missed status changes: {e}");
+				pending.fetch_sub(1, Ordering::Relaxed);
+				return None;
+			}
+		};
+
+		if missed.is_empty() {
+			pending.fetch_sub(1, Ordering::Relaxed);
+			return None;
+		}
+
+		Some(std::mem::take(&mut *missed))
+	}
+
 	fn fetch_helper(
 		repo: &RepoPath,
-		status_type: StatusType,
-		config: Option<ShowUntrackedFilesConfig>,
+		params: StatusParams,
 		hash_request: u64,
 		arc_current: &Arc<Mutex<Request<u64, Status>>>,
 		arc_last: &Arc<Mutex<Status>>,
+		arc_incremental: &Arc<Mutex<Incremental>>,
 	) -> Result<()> {
-		let res = Self::get_status(repo, status_type, config)?;
+		let status_type = params.status_type;
+		let res = {
+			let mut incremental = arc_incremental.lock()?;
+			Self::get_status(repo, params, &mut incremental)?
+		};
 		log::trace!(
 			"status fetched: {hash_request} (type: {status_type:?})",
 		);
@@ -178,15 +273,176 @@ impl AsyncStatus {
 
 	fn get_status(
 		repo: &RepoPath,
-		status_type: StatusType,
-		config: Option<ShowUntrackedFilesConfig>,
+		params: StatusParams,
+		incremental: &mut Incremental,
 	) -> Result<Status> {
-		Ok(Status {
-			items: sync::status::get_status(
+		let StatusParams {
+			status_type,
+			config,
+			paths,
+			..
+		} = params;
+
+		// explicitly reported paths are only about worktree files as well
+		let changed_paths = if status_type == StatusType::WorkingDir
+			&& paths.is_some()
+		{
+			paths
+		} else {
+			Self::changed_paths(
 				repo,
 				status_type,
 				config,
-			)?,
-		})
+				incremental,
+			)
+		};
+
+		// ignore rules can change the status of files anywhere below them
+		let changed_paths = changed_paths.filter(|paths| {
+			!paths.iter().any(|path| Self::is_ignore_file(path))
+		});
+
+		let index = Self::index_stamp(repo);
+		let base = Some((status_type, config));
+
+		let items = match changed_paths {
+			Some(paths)
+				if incremental.base == base
+					&& incremental.index == index =>
+			{
+				log::trace!("status of {} paths", paths.len());
+
+				sync::status::get_status_of_paths(
+					repo,
+					status_type,
+					config,
+					&incremental.items,
+					&paths,
+				)?
+			}
+			_ => sync::status::get_status(repo, status_type, config)?,
+		};
+
+		incremental.base = base;
+		incremental.index = index;
+		incremental.items.clone_from(&items);
+
+		Ok(Status { items })
+	}
+
+	/// paths changed since the last run according to `core.fsmonitor`,
+	/// `None` if a full scan is necessary
+	fn changed_paths(
+		repo: &RepoPath,
+		status_type: StatusType,
+		config: Option<ShowUntrackedFilesConfig>,
+		incremental: &mut Incremental,
+	) -> Option<Vec<String>> {
+		// fsmonitor only knows about the worktree but not the index
+		if status_type != StatusType::WorkingDir {
+			return None;
+		}
+
+		let acceleration = sync::status_acceleration_config(repo)
+			.inspect_err(|e| log::error!("status config: {e}"))
+			.ok()?;
+
+		let hook = acceleration.fsmonitor?;
+
+		let changes = sync::query_fsmonitor(
+			repo,
+			&hook,
+			acceleration.fsmonitor_version,
+			incremental.fsmonitor_token.as_deref(),
+		);
+
+		let changes = match changes {
+			Ok(changes) => changes,
+			Err(e) => {
+				log::error!("fsmonitor: {e}");
+				incremental.fsmonitor_token = None;
+				return None;
+			}
+		};
+
+		incremental.fsmonitor_token = Some(changes.token);
+
+		// without an untracked cache git scans for untracked files on every run
+		let include_untracked = config
+			.map_or_else(|| sync::untracked_files_config(repo), Ok)
+			.map_or(
+				true,
+				ShowUntrackedFilesConfig::include_untracked,
+			);
+
+		if include_untracked && !acceleration.untracked_cache {
+			return None;
+		}
+
+		changes.paths
+	}
+
+	fn is_ignore_file(path: &str) -> bool {
+		path == ".git/info/exclude"
+			|| Path::new(path)
+				.file_name()
+				.is_some_and(|name| name == ".gitignore")
+	}
+
+	fn index_stamp(repo: &RepoPath) -> IndexStamp {
+		let metadata = sync::utils::repo_dir(repo)
+			.and_then(|dir| Ok(dir.join("index").metadata()?))
+			.ok()?;
+
+		Some((metadata.modified().ok()?, metadata.len()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{AsyncStatus, Incremental, StatusParams};
+	use crate::sync::{
+		status::StatusType, tests::repo_init, RepoPath,
+	};
+	use std::fs;
+
+	#[test]
+	fn test_gitignore_change_rescans_all() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::write(root.join("a.log"), "a").unwrap();
+
+		let mut incremental = Incremental::default();
+		let params = StatusParams::new(StatusType::WorkingDir, None);
+
+		let status = AsyncStatus::get_status(
+			repo_path,
+			params.clone(),
+			&mut incremental,
+		)
+		.unwrap();
+		assert_eq!(status.items.len(), 1);
+
+		fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+
+		let status = AsyncStatus::get_status(
+			repo_path,
+			params
+				.with_changed_paths(vec![String::from(".gitignore")]),
+			&mut incremental,
+		)
+		.unwrap();
+
+		assert_eq!(
+			status
+				.items
+				.iter()
+				.map(|item| item.path.as_str())
+				.collect::<Vec<_>>(),
+			vec![".gitignore"]
+		);
 	}
 }
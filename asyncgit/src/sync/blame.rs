@@ -259,4 +259,35 @@ mod tests {
 
 		assert!(blame_file(repo_path, "bar\\foo", None).is_ok());
 	}
+
+	#[test]
+	fn test_blame_mailmap_file() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"line 1\n")?;
+
+		stage_add_file(repo_path, file_path)?;
+		commit(repo_path, "first commit")?;
+
+		let mailmap_path = repo.path().join("mailmap");
+		File::create(&mailmap_path)?
+			.write_all(b"new name <newemail> <email>\n")?;
+		repo.config()?.set_str(
+			"mailmap.file",
+			mailmap_path.to_str().unwrap(),
+		)?;
+
+		let blame = blame_file(repo_path, "foo", None)?;
+
+		assert!(matches!(
+			blame.lines.as_slice(),
+			[(Some(BlameHunk { author, .. }), _)] if author == "new name"
+		));
+
+		Ok(())
+	}
 }
@@ -0,0 +1,168 @@
+//! comparing two branches
+
+use crate::{
+	error::Result,
+	sync::{repository::repo, CommitId, RepoPath},
+};
+use git2::{ErrorCode, Oid, Repository, Sort};
+use scopetime::scope_time;
+
+/// how two branches relate to each other
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BranchesCompare {
+	/// top commit of the first branch
+	pub a: CommitId,
+	/// top commit of the second branch
+	pub b: CommitId,
+	/// best common ancestor of `a` and `b`,
+	/// `None` if their histories are unrelated
+	pub merge_base: Option<CommitId>,
+	/// commits only reachable from `a` (`b..a`), newest first
+	pub only_a: Vec<CommitId>,
+	/// commits only reachable from `b` (`a..b`), newest first
+	pub only_b: Vec<CommitId>,
+}
+
+/// compares the branches (full reference names) `a` and `b`
+pub fn compare_branches(
+	repo_path: &RepoPath,
+	a: &str,
+	b: &str,
+) -> Result<BranchesCompare> {
+	scope_time!("compare_branches");
+
+	let repo = repo(repo_path)?;
+
+	let a = repo.find_reference(a)?.peel_to_commit()?.id();
+	let b = repo.find_reference(b)?.peel_to_commit()?.id();
+
+	let merge_base = match repo.merge_base(a, b) {
+		Ok(id) => Some(id.into()),
+		Err(e) if e.code() == ErrorCode::NotFound => None,
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(BranchesCompare {
+		a: a.into(),
+		b: b.into(),
+		merge_base,
+		only_a: commits_range(&repo, b, a)?,
+		only_b: commits_range(&repo, a, b)?,
+	})
+}
+
+/// commits in `from..to`
+fn commits_range(
+	repo: &Repository,
+	from: Oid,
+	to: Oid,
+) -> Result<Vec<CommitId>> {
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+	walk.push(to)?;
+	walk.hide(from)?;
+
+	Ok(walk
+		.map(|id| id.map(CommitId::from))
+		.collect::<std::result::Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_compare_branches() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "f.txt", "base", "base");
+
+		create_branch(repo_path, "feature").unwrap();
+		let f1 = write_commit_file(&repo, "f1.txt", "1", "f1");
+		let f2 = write_commit_file(&repo, "f2.txt", "2", "f2");
+
+		checkout_branch(repo_path, "master").unwrap();
+		let m1 = write_commit_file(&repo, "m1.txt", "1", "m1");
+
+		let res = compare_branches(
+			repo_path,
+			"refs/heads/master",
+			"refs/heads/feature",
+		)
+		.unwrap();
+
+		assert_eq!(res.a, m1);
+		assert_eq!(res.b, f2);
+		assert_eq!(res.merge_base, Some(base));
+		assert_eq!(res.only_a, vec![m1]);
+		assert_eq!(res.only_b, vec![f2, f1]);
+	}
+
+	#[test]
+	fn test_compare_same_branch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head = write_commit_file(&repo, "f.txt", "base", "base");
+		create_branch(repo_path, "other").unwrap();
+
+		let res = compare_branches(
+			repo_path,
+			"refs/heads/master",
+			"refs/heads/other",
+		)
+		.unwrap();
+
+		assert_eq!(res.merge_base, Some(head));
+		assert!(res.only_a.is_empty());
+		assert!(res.only_b.is_empty());
+	}
+
+	#[test]
+	fn test_compare_orphan_branch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let m1 = write_commit_file(&repo, "f.txt", "base", "m1");
+		let initial =
+			repo.find_commit(m1.into()).unwrap().parent_id(0);
+
+		let sig = repo.signature().unwrap();
+		let tree = repo
+			.find_tree(repo.index().unwrap().write_tree().unwrap())
+			.unwrap();
+		let orphan: CommitId = repo
+			.commit(
+				Some("refs/heads/gh-pages"),
+				&sig,
+				&sig,
+				"orphan",
+				&tree,
+				&[],
+			)
+			.unwrap()
+			.into();
+
+		let res = compare_branches(
+			repo_path,
+			"refs/heads/master",
+			"refs/heads/gh-pages",
+		)
+		.unwrap();
+
+		assert_eq!(res.merge_base, None);
+		assert_eq!(res.only_a, vec![m1, initial.unwrap().into()]);
+		assert_eq!(res.only_b, vec![orphan]);
+	}
+}
@@ -3,7 +3,10 @@
 use super::BranchType;
 use crate::{
 	error::{Error, Result},
-	sync::{merge_msg, repository::repo, CommitId, RepoPath},
+	sync::{
+		hooks::hooks_pre_merge_commit_check, merge_msg,
+		repository::repo, CommitId, RepoPath,
+	},
 };
 use git2::Commit;
 use scopetime::scope_time;
@@ -50,6 +53,8 @@ pub fn merge_upstream_commit(
 	repo.merge(&[&annotated_upstream], None, None)?;
 
 	if !repo.index()?.has_conflicts() {
+		hooks_pre_merge_commit_check(&repo)?;
+
 		let msg = merge_msg(repo_path)?;
 
 		let commit_id =
@@ -4,17 +4,18 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		rebase::conflict_free_rebase, repository::repo, CommitId,
-		RepoPath,
+		HookResult, RepoPath,
 	},
 };
 use git2::BranchType;
 use scopetime::scope_time;
 
-/// tries merging current branch with its upstream using rebase
+/// tries merging current branch with its upstream using rebase,
+/// see [`conflict_free_rebase`] for the result
 pub fn merge_upstream_rebase(
 	repo_path: &RepoPath,
 	branch_name: &str,
-) -> Result<CommitId> {
+) -> Result<(CommitId, HookResult)> {
 	scope_time!("merge_upstream_rebase");
 
 	let repo = repo(repo_path)?;
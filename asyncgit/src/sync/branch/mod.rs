@@ -1,5 +1,6 @@
 //! branch functions
 
+pub mod compare;
 pub mod merge_commit;
 pub mod merge_ff;
 pub mod merge_rebase;
@@ -9,6 +10,7 @@ use super::{utils::bytes2string, RepoPath};
 use crate::{
 	error::{Error, Result},
 	sync::{
+		commit_details::get_author_of_commit,
 		remotes::get_default_remote_for_push_in_repo,
 		repository::repo, utils::get_head_repo, CommitId,
 	},
@@ -91,6 +93,10 @@ pub struct BranchInfo {
 	pub top_commit_message: String,
 	///
 	pub top_commit: CommitId,
+	/// commit time of `top_commit` in seconds since epoch
+	pub top_commit_time: i64,
+	/// author name of `top_commit`
+	pub top_commit_author: String,
 	///
 	pub details: BranchDetails,
 }
@@ -124,6 +130,7 @@ pub fn get_branches_info(
 	scope_time!("get_branches_info");
 
 	let repo = repo(repo_path)?;
+	let mailmap = repo.mailmap()?;
 
 	let (filter, remotes_with_tracking) = if local {
 		(BranchType::Local, HashSet::default())
@@ -159,12 +166,17 @@ pub fn get_branches_info(
 				.map(String::from);
 
 			let name_bytes = branch.name_bytes()?;
+			let top_commit_author = bytes2string(
+				get_author_of_commit(&top_commit, &mailmap)
+					.name_bytes(),
+			)?;
 
 			let upstream_branch =
 				upstream.ok().and_then(|upstream| {
-					bytes2string(upstream.get().name_bytes())
-						.ok()
-						.map(|reference| UpstreamBranch { reference })
+					let reference =
+						bytes2string(upstream.get().name_bytes())
+							.ok()?;
+					Some(UpstreamBranch { reference })
 				});
 
 			let details = if local {
@@ -188,6 +200,8 @@ pub fn get_branches_info(
 					top_commit.summary_bytes().unwrap_or_default(),
 				)?,
 				top_commit: top_commit.id().into(),
+				top_commit_time: top_commit.time().seconds(),
+				top_commit_author,
 				details,
 			})
 		})
@@ -199,8 +213,37 @@ pub fn get_branches_info(
 	Ok(branches_for_display)
 }
 
+/// returns the local (or remote) branches that are fully merged into
+/// `base` (a full reference name), `base` itself is not part of the list
+pub fn get_merged_branches_info(
+	repo_path: &RepoPath,
+	local: bool,
+	base: &str,
+) -> Result<Vec<BranchInfo>> {
+	scope_time!("get_merged_branches_info");
+
+	let branches = get_branches_info(repo_path, local)?;
+
+	let repo = repo(repo_path)?;
+	let base_commit =
+		repo.find_reference(base)?.peel_to_commit()?.id();
+
+	Ok(branches
+		.into_iter()
+		.filter(|branch| {
+			let top_commit = branch.top_commit.into();
+
+			branch.reference != base
+				&& (top_commit == base_commit
+					|| repo
+						.graph_descendant_of(base_commit, top_commit)
+						.unwrap_or_default())
+		})
+		.collect())
+}
+
 ///
-#[derive(Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct BranchCompare {
 	///
 	pub ahead: usize,
@@ -262,6 +305,92 @@ pub fn get_branch_upstream_merge(
 	}
 }
 
+/// sets the upstream of the local `branch` to `merge_ref` (the full
+/// ref name on the remote) of `remote`, like `git branch --set-upstream-to`
+pub fn set_branch_upstream(
+	repo_path: &RepoPath,
+	branch: &str,
+	remote: &str,
+	merge_ref: &str,
+) -> Result<()> {
+	scope_time!("set_branch_upstream");
+
+	let repo = repo(repo_path)?;
+	set_branch_upstream_repo(&repo, branch, remote, merge_ref)
+}
+
+pub(crate) fn set_branch_upstream_repo(
+	repo: &Repository,
+	branch: &str,
+	remote: &str,
+	merge_ref: &str,
+) -> Result<()> {
+	// make sure the branch exists before writing its config
+	repo.find_branch(branch, BranchType::Local)?;
+
+	let mut config = repo.config()?;
+	config.set_str(&format!("branch.{branch}.remote"), remote)?;
+	config.set_str(&format!("branch.{branch}.merge"), merge_ref)?;
+
+	Ok(())
+}
+
+/// removes the upstream configuration of the local `branch`,
+/// like `git branch --unset-upstream`
+pub fn unset_branch_upstream(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<()> {
+	scope_time!("unset_branch_upstream");
+
+	let repo = repo(repo_path)?;
+	repo.find_branch(branch, BranchType::Local)?;
+
+	let mut config = repo.config()?;
+	for key in ["remote", "merge"] {
+		match config.remove(&format!("branch.{branch}.{key}")) {
+			Err(e) if e.code() != git2::ErrorCode::NotFound => {
+				return Err(e.into());
+			}
+			_ => (),
+		}
+	}
+
+	Ok(())
+}
+
+/// returns the `(remote, merge)` pair that makes a local branch
+/// track the remote tracking branch `reference`
+/// (e.g. `refs/remotes/origin/master`)
+pub fn get_remote_branch_upstream(
+	repo_path: &RepoPath,
+	reference: &str,
+) -> Result<(String, String)> {
+	scope_time!("get_remote_branch_upstream");
+
+	let repo = repo(repo_path)?;
+
+	let remote_name =
+		bytes2string(repo.branch_remote_name(reference)?.as_ref())?;
+	let remote = repo.find_remote(&remote_name)?;
+
+	let merge = remote
+		.refspecs()
+		.filter(|spec| spec.direction() == git2::Direction::Fetch)
+		.find_map(|spec| {
+			spec.rtransform(reference)
+				.ok()
+				.and_then(|buf| buf.as_str().map(String::from))
+		})
+		.ok_or_else(|| {
+			Error::Generic(format!(
+				"no fetch refspec of '{remote_name}' matches '{reference}'"
+			))
+		})?;
+
+	Ok((remote_name, merge))
+}
+
 /// returns whether the pull merge strategy is set to rebase
 pub fn config_is_pull_rebase(repo_path: &RepoPath) -> Result<bool> {
 	let repo = repo(repo_path)?;
@@ -289,11 +418,16 @@ pub fn branch_compare_upstream(
 
 	let upstream = branch.upstream()?;
 
-	let branch_commit =
-		branch.into_reference().peel_to_commit()?.id();
+	compare_with_upstream(&repo, branch.get(), upstream.get())
+}
 
-	let upstream_commit =
-		upstream.into_reference().peel_to_commit()?.id();
+fn compare_with_upstream(
+	repo: &Repository,
+	branch: &git2::Reference,
+	upstream: &git2::Reference,
+) -> Result<BranchCompare> {
+	let branch_commit = branch.peel_to_commit()?.id();
+	let upstream_commit = upstream.peel_to_commit()?.id();
 
 	let (ahead, behind) =
 		repo.graph_ahead_behind(branch_commit, upstream_commit)?;
@@ -500,6 +634,113 @@ mod tests_create_branch {
 	}
 }
 
+#[cfg(test)]
+mod tests_set_upstream {
+	use super::*;
+	use crate::sync::tests::repo_init;
+
+	#[test]
+	fn test_set_branch_upstream() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(
+			get_branch_upstream_merge(repo_path, "master").unwrap(),
+			None
+		);
+
+		set_branch_upstream(
+			repo_path,
+			"master",
+			"origin",
+			"refs/heads/review/master",
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_branch_remote(repo_path, "master").unwrap(),
+			Some(String::from("origin"))
+		);
+		assert_eq!(
+			get_branch_upstream_merge(repo_path, "master").unwrap(),
+			Some(String::from("refs/heads/review/master"))
+		);
+
+		assert!(set_branch_upstream(
+			repo_path,
+			"missing",
+			"origin",
+			"refs/heads/missing"
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn test_unset_branch_upstream() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		set_branch_upstream(
+			repo_path,
+			"master",
+			"origin",
+			"refs/heads/master",
+		)
+		.unwrap();
+
+		unset_branch_upstream(repo_path, "master").unwrap();
+
+		assert_eq!(
+			get_branch_remote(repo_path, "master").unwrap(),
+			None
+		);
+		assert_eq!(
+			get_branch_upstream_merge(repo_path, "master").unwrap(),
+			None
+		);
+
+		// unsetting twice is not an error
+		unset_branch_upstream(repo_path, "master").unwrap();
+	}
+
+	#[test]
+	fn test_remote_branch_upstream() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.remote_with_fetch(
+			"upstream",
+			"https://example.com/foo.git",
+			"+refs/heads/*:refs/remotes/up/*",
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_remote_branch_upstream(
+				repo_path,
+				"refs/remotes/up/feature/x"
+			)
+			.unwrap(),
+			(
+				String::from("upstream"),
+				String::from("refs/heads/feature/x")
+			)
+		);
+
+		assert!(get_remote_branch_upstream(
+			repo_path,
+			"refs/remotes/nope/master"
+		)
+		.is_err());
+	}
+}
+
 #[cfg(test)]
 mod tests_branch_compare {
 	use super::*;
@@ -568,6 +809,59 @@ mod tests_branches {
 		);
 	}
 
+	#[test]
+	fn test_top_commit_author_mailmap() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::write(
+			root.join(".mailmap"),
+			"new name <newemail> <email>\n",
+		)
+		.unwrap();
+
+		let branches = get_branches_info(repo_path, true).unwrap();
+
+		assert_eq!(branches[0].top_commit_author, "new name");
+	}
+
+	#[test]
+	fn test_merged_branches() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "merged").unwrap();
+		create_branch(repo_path, "unmerged").unwrap();
+		write_commit_file(&repo, "f1.txt", "foo", "c1");
+		checkout_branch(repo_path, "master").unwrap();
+		create_branch(repo_path, "ahead").unwrap();
+		write_commit_file(&repo, "f2.txt", "bar", "c2");
+
+		let names = |base: &str| {
+			get_merged_branches_info(repo_path, true, base)
+				.unwrap()
+				.into_iter()
+				.map(|b| b.name)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(names("refs/heads/master"), vec!["merged"]);
+		assert_eq!(
+			names("refs/heads/ahead"),
+			vec!["master", "merged"]
+		);
+		assert!(get_merged_branches_info(
+			repo_path,
+			true,
+			"refs/heads/missing"
+		)
+		.is_err());
+	}
+
 	fn clone_branch_commit_push(target: &str, branch_name: &str) {
 		let (dir, repo) = repo_clone(target).unwrap();
 		let dir = dir.path().to_str().unwrap();
@@ -670,6 +964,59 @@ mod tests_branches {
 		);
 	}
 
+	#[test]
+	fn test_branches_info_upstream_compare() {
+		let (r1_path, _remote1) = repo_init_bare().unwrap();
+		let (_r, repo) = repo_init().unwrap();
+
+		let r1_path = r1_path.path().to_str().unwrap();
+
+		clone_branch_commit_push(r1_path, "r1branch");
+
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.remote("r1", r1_path).unwrap();
+
+		debug_cmd_print(repo_path, "git fetch r1");
+		debug_cmd_print(
+			repo_path,
+			"git checkout --track r1/r1branch",
+		);
+		debug_cmd_print(
+			repo_path,
+			"git commit --allow-empty -m local1",
+		);
+		debug_cmd_print(
+			repo_path,
+			"git commit --allow-empty -m local2",
+		);
+
+		let branches = get_branches_info(repo_path, true).unwrap();
+		let upstream = branches[1]
+			.local_details()
+			.unwrap()
+			.upstream
+			.clone()
+			.unwrap();
+
+		assert_eq!(upstream.reference, "refs/remotes/r1/r1branch");
+		assert_eq!(
+			branch_compare_upstream(repo_path, &branches[1].name)
+				.unwrap(),
+			BranchCompare {
+				ahead: 2,
+				behind: 0
+			}
+		);
+		assert!(branches[0]
+			.local_details()
+			.unwrap()
+			.upstream
+			.is_none());
+	}
+
 	#[test]
 	fn test_branch_remote_no_upstream() {
 		let (_r, repo) = repo_init().unwrap();
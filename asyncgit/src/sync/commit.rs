@@ -6,7 +6,8 @@ use crate::{
 	sync::{repository::repo, utils::get_head_repo},
 };
 use git2::{
-	message_prettify, ErrorCode, ObjectType, Repository, Signature,
+	message_prettify, ErrorCode, ObjectType, Oid, Repository,
+	Signature,
 };
 use scopetime::scope_time;
 
@@ -155,6 +156,8 @@ pub fn commit(repo_path: &RepoPath, msg: &str) -> Result<CommitId> {
 
 /// Tag a commit.
 ///
+/// Annotated tags get signed if `tag.gpgSign` is set.
+///
 /// This function will return an `Err(…)` variant if the tag’s name is refused
 /// by git or if the tag already exists.
 pub fn tag_commit(
@@ -173,7 +176,16 @@ pub fn tag_commit(
 
 	let c = if let Some(message) = message {
 		let signature = signature_allow_undefined_name(&repo)?;
-		repo.tag(tag, &target, &signature, message, false)?.into()
+		let config = repo.config()?;
+
+		if config.get_bool("tag.gpgSign").unwrap_or(false) {
+			tag_signed(
+				&repo, &config, tag, &target, &signature, message,
+			)?
+			.into()
+		} else {
+			repo.tag(tag, &target, &signature, message, false)?.into()
+		}
 	} else {
 		repo.tag_lightweight(tag, &target, false)?.into()
 	};
@@ -181,6 +193,41 @@ pub fn tag_commit(
 	Ok(c)
 }
 
+/// the signature of a tag is appended to its message
+fn tag_signed(
+	repo: &Repository,
+	config: &git2::Config,
+	tag: &str,
+	target: &git2::Object,
+	tagger: &Signature,
+	message: &str,
+) -> Result<Oid> {
+	let message = if message.ends_with('\n') {
+		message.to_string()
+	} else {
+		format!("{message}\n")
+	};
+
+	let odb = repo.odb()?;
+	let unsigned =
+		repo.tag_annotation_create(tag, target, tagger, &message)?;
+	let mut buffer = odb.read(unsigned)?.data().to_vec();
+
+	let signer = SignBuilder::from_gitconfig(repo, config)?;
+	let (signature, _) = signer.sign(&buffer)?;
+	buffer.extend_from_slice(signature.as_bytes());
+
+	let id = odb.write(ObjectType::Tag, &buffer)?;
+	repo.reference(
+		&format!("refs/tags/{tag}"),
+		id,
+		false,
+		&format!("tag: {tag}"),
+	)?;
+
+	Ok(id)
+}
+
 /// Loads the comment prefix from config & uses it to prettify commit messages
 pub fn commit_message_prettify(
 	repo_path: &RepoPath,
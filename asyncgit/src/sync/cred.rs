@@ -10,7 +10,10 @@ use super::{
 	RepoPath,
 };
 use crate::error::{Error, Result};
-use git2::CredentialHelper;
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
 
 /// basic Authentication Credentials
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -85,6 +88,23 @@ pub fn need_username_password_for_push(
 	Ok(is_http)
 }
 
+/// know if username and password are needed for `url`,
+/// used where there is no repository yet (e.g. to clone)
+pub fn need_username_password_for_url(url: &str) -> bool {
+	url.starts_with("http")
+}
+
+/// extract username and password for `url` using the global git
+/// config, used where there is no repository yet (e.g. to clone)
+pub fn extract_username_password_for_url(
+	url: &str,
+) -> BasicAuthCredential {
+	git2::Config::open_default().map_or_else(
+		|_| extract_cred_from_url(url),
+		|config| fill_credential(&config, url),
+	)
+}
+
 /// extract username and password
 pub fn extract_username_password(
 	repo_path: &RepoPath,
@@ -95,22 +115,11 @@ pub fn extract_username_password(
 		.url()
 		.ok_or(Error::UnknownRemote)?
 		.to_owned();
-	let mut helper = CredentialHelper::new(&url);
-
-	//TODO: look at Cred::credential_helper,
-	//if the username is in the url we need to set it here,
-	//I dont think `config` will pick it up
-
-	if let Ok(config) = repo.config() {
-		helper.config(&config);
-	}
 
-	Ok(match helper.execute() {
-		Some((username, password)) => {
-			BasicAuthCredential::new(Some(username), Some(password))
-		}
-		None => extract_cred_from_url(&url),
-	})
+	Ok(repo.config().map_or_else(
+		|_| extract_cred_from_url(&url),
+		|config| fill_credential(&config, &url),
+	))
 }
 
 /// extract username and password
@@ -124,22 +133,11 @@ pub fn extract_username_password_for_fetch(
 		.url()
 		.ok_or(Error::UnknownRemote)?
 		.to_owned();
-	let mut helper = CredentialHelper::new(&url);
-
-	//TODO: look at Cred::credential_helper,
-	//if the username is in the url we need to set it here,
-	//I dont think `config` will pick it up
 
-	if let Ok(config) = repo.config() {
-		helper.config(&config);
-	}
-
-	Ok(match helper.execute() {
-		Some((username, password)) => {
-			BasicAuthCredential::new(Some(username), Some(password))
-		}
-		None => extract_cred_from_url(&url),
-	})
+	Ok(repo.config().map_or_else(
+		|_| extract_cred_from_url(&url),
+		|config| fill_credential(&config, &url),
+	))
 }
 
 /// extract username and password
@@ -153,22 +151,11 @@ pub fn extract_username_password_for_push(
 		.url()
 		.ok_or(Error::UnknownRemote)?
 		.to_owned();
-	let mut helper = CredentialHelper::new(&url);
 
-	//TODO: look at Cred::credential_helper,
-	//if the username is in the url we need to set it here,
-	//I dont think `config` will pick it up
-
-	if let Ok(config) = repo.config() {
-		helper.config(&config);
-	}
-
-	Ok(match helper.execute() {
-		Some((username, password)) => {
-			BasicAuthCredential::new(Some(username), Some(password))
-		}
-		None => extract_cred_from_url(&url),
-	})
+	Ok(repo.config().map_or_else(
+		|_| extract_cred_from_url(&url),
+		|config| fill_credential(&config, &url),
+	))
 }
 
 /// extract credentials from url
@@ -188,18 +175,329 @@ pub fn extract_cred_from_url(url: &str) -> BasicAuthCredential {
 	)
 }
 
+/// fills in credentials for `url` like `git credential fill` does.
+///
+/// first asks every configured `credential.helper`, then prompts via
+/// `GIT_ASKPASS`, `core.askPass` or `SSH_ASKPASS` for what is still
+/// missing. falls back to what the url itself contains.
+pub fn fill_credential(
+	config: &git2::Config,
+	url: &str,
+) -> BasicAuthCredential {
+	let askpass = std::env::var("GIT_ASKPASS")
+		.ok()
+		.or_else(|| config.get_string("core.askPass").ok())
+		.or_else(|| std::env::var("SSH_ASKPASS").ok())
+		.filter(|askpass| !askpass.is_empty());
+
+	fill_credential_with_askpass(config, url, askpass.as_deref())
+}
+
+fn fill_credential_with_askpass(
+	config: &git2::Config,
+	url: &str,
+	askpass: Option<&str>,
+) -> BasicAuthCredential {
+	let mut cred = extract_cred_from_url(url);
+
+	for helper in credential_helpers(config, url) {
+		let output = match run_credential_helper(
+			&helper,
+			"get",
+			&credential_description(url, &cred),
+		) {
+			Ok(output) => output,
+			Err(e) => {
+				log::error!("credential helper '{helper}': {e}");
+				continue;
+			}
+		};
+
+		let quit = parse_credential_output(&output, &mut cred);
+
+		if cred.is_complete() || quit {
+			break;
+		}
+	}
+
+	if let Some(askpass) = askpass {
+		prompt_askpass(askpass, url, &mut cred);
+	}
+
+	cred
+}
+
+/// tells all configured credential helpers to remember `cred`
+/// because the remote accepted it
+pub fn approve_credential(
+	config: &git2::Config,
+	url: &str,
+	cred: &BasicAuthCredential,
+) {
+	notify_credential_helpers(config, url, cred, "store");
+}
+
+/// tells all configured credential helpers to forget `cred`
+/// because the remote rejected it, so the next attempt asks again
+pub fn reject_credential(
+	config: &git2::Config,
+	url: &str,
+	cred: &BasicAuthCredential,
+) {
+	notify_credential_helpers(config, url, cred, "erase");
+}
+
+fn notify_credential_helpers(
+	config: &git2::Config,
+	url: &str,
+	cred: &BasicAuthCredential,
+	action: &str,
+) {
+	if !cred.is_complete() {
+		return;
+	}
+
+	let input = credential_description(url, cred);
+
+	for helper in credential_helpers(config, url) {
+		if let Err(e) = run_credential_helper(&helper, action, &input)
+		{
+			log::error!("credential helper '{helper}' {action}: {e}");
+		}
+	}
+}
+
+/// `credential.helper` and `credential.<url>.helper` entries
+/// matching `url` in config order, an empty value resets the list
+fn credential_helpers(
+	config: &git2::Config,
+	url: &str,
+) -> Vec<String> {
+	let mut helpers = Vec::new();
+
+	let Ok(mut entries) =
+		config.entries(Some(r"credential\..*helper"))
+	else {
+		return helpers;
+	};
+
+	while let Some(Ok(entry)) = entries.next() {
+		let (Some(name), Some(value)) = (entry.name(), entry.value())
+		else {
+			continue;
+		};
+
+		let context = name
+			.strip_prefix("credential.")
+			.and_then(|name| name.strip_suffix("helper"))
+			.map(|context| context.trim_end_matches('.'))
+			.unwrap_or_default();
+
+		if !context.is_empty() && !url.starts_with(context) {
+			continue;
+		}
+
+		if value.is_empty() {
+			helpers.clear();
+		} else {
+			helpers.push(value.to_string());
+		}
+	}
+
+	helpers
+}
+
+/// runs `helper` the way git does: `!cmd` as shell snippet, an
+/// absolute path as is and anything else as `git credential-<name>`
+fn run_credential_helper(
+	helper: &str,
+	action: &str,
+	input: &str,
+) -> Result<String> {
+	let is_path = std::path::Path::new(
+		helper.split_whitespace().next().unwrap_or_default(),
+	)
+	.is_absolute();
+
+	let cmd = helper.strip_prefix('!').map_or_else(
+		|| {
+			if is_path {
+				helper.to_string()
+			} else {
+				format!("git credential-{helper}")
+			}
+		},
+		str::to_string,
+	);
+
+	let mut child = Command::new(gix::path::env::shell())
+		.arg("-c")
+		.arg(format!("{cmd} \"$@\""))
+		.arg(&cmd)
+		.arg(action)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		stdin.write_all(input.as_bytes())?;
+	}
+
+	let output = child.wait_with_output()?;
+
+	if !output.status.success() {
+		return Err(Error::Generic(format!(
+			"exited with {}: {}",
+			output.status,
+			String::from_utf8_lossy(&output.stderr).trim()
+		)));
+	}
+
+	Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// the `key=value` lines describing a credential for a helper
+fn credential_description(
+	url: &str,
+	cred: &BasicAuthCredential,
+) -> String {
+	let mut lines = Vec::new();
+
+	if let Ok(url) = url::Url::parse(url) {
+		lines.push(format!("protocol={}", url.scheme()));
+
+		if let Some(host) = url.host_str() {
+			lines.push(url.port().map_or_else(
+				|| format!("host={host}"),
+				|port| format!("host={host}:{port}"),
+			));
+		}
+	}
+
+	if let Some(username) = &cred.username {
+		lines.push(format!("username={username}"));
+	}
+	if let Some(password) = &cred.password {
+		lines.push(format!("password={password}"));
+	}
+
+	format!("{}\n\n", lines.join("\n"))
+}
+
+/// fills `cred` from a helper's answer, returns whether the helper
+/// asked to stop consulting further helpers
+fn parse_credential_output(
+	output: &str,
+	cred: &mut BasicAuthCredential,
+) -> bool {
+	let mut quit = false;
+
+	for (key, value) in
+		output.lines().filter_map(|line| line.split_once('='))
+	{
+		match key {
+			"username" if cred.username.is_none() => {
+				cred.username = Some(value.to_string());
+			}
+			"password" if cred.password.is_none() => {
+				cred.password = Some(value.to_string());
+			}
+			"quit" => quit = matches!(value, "1" | "true"),
+			_ => (),
+		}
+	}
+
+	quit
+}
+
+fn prompt_askpass(
+	askpass: &str,
+	url: &str,
+	cred: &mut BasicAuthCredential,
+) {
+	let Ok(parsed) = url::Url::parse(url) else {
+		return;
+	};
+	let host = parsed.host_str().unwrap_or_default();
+
+	if cred.username.is_none() {
+		cred.username = run_askpass(
+			askpass,
+			&format!("Username for '{}://{host}': ", parsed.scheme()),
+		);
+	}
+
+	if cred.password.is_none() {
+		let user = cred.username.as_deref().unwrap_or_default();
+		cred.password = run_askpass(
+			askpass,
+			&format!(
+				"Password for '{}://{user}@{host}': ",
+				parsed.scheme()
+			),
+		);
+	}
+}
+
+fn run_askpass(askpass: &str, prompt: &str) -> Option<String> {
+	let output = Command::new(askpass)
+		.arg(prompt)
+		.stdin(Stdio::null())
+		.output()
+		.map_err(|e| log::error!("askpass '{askpass}': {e}"))
+		.ok()?;
+
+	if !output.status.success() {
+		return None;
+	}
+
+	let answer = String::from_utf8_lossy(&output.stdout)
+		.trim_end_matches(['\r', '\n'])
+		.to_string();
+
+	(!answer.is_empty()).then_some(answer)
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::sync::{
 		cred::{
+			approve_credential, credential_helpers,
 			extract_cred_from_url, extract_username_password,
-			need_username_password, BasicAuthCredential,
+			fill_credential_with_askpass, need_username_password,
+			reject_credential, BasicAuthCredential,
 		},
 		remotes::DEFAULT_REMOTE_NAME,
 		tests::repo_init,
 		RepoPath,
 	};
 	use serial_test::serial;
+	use std::{fs, path::Path};
+
+	/// an empty helper in the repo config drops the helpers of the
+	/// global and system config, so tests never touch real keychains
+	fn reset_credential_helpers(repo: &git2::Repository) {
+		repo.config()
+			.unwrap()
+			.set_multivar("credential.helper", "^ $", "")
+			.unwrap();
+	}
+
+	/// answers `get` with fixed credentials and logs every other
+	/// action together with the credential it was given
+	fn set_logging_helper(repo: &git2::Repository, log: &Path) {
+		let helper = format!(
+			r#"!f() {{ if [ "$1" = get ]; then echo username=helper-user; echo password=helper-pwd; else echo "$1" >> {0}; cat >> {0}; fi; }}; f"#,
+			log.display()
+		);
+
+		reset_credential_helpers(repo);
+		repo.config()
+			.unwrap()
+			.set_multivar("credential.helper", "^ $", &helper)
+			.unwrap();
+	}
 
 	#[test]
 	fn test_credential_complete() {
@@ -364,4 +662,106 @@ mod tests {
 
 		extract_username_password(repo_path).unwrap();
 	}
+
+	#[test]
+	#[serial]
+	fn test_extract_username_password_from_helper() {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.remote(DEFAULT_REMOTE_NAME, "https://github.com/a/b")
+			.unwrap();
+		set_logging_helper(&repo, &td.path().join("log"));
+
+		assert_eq!(
+			extract_username_password(repo_path).unwrap(),
+			BasicAuthCredential::new(
+				Some("helper-user".to_owned()),
+				Some("helper-pwd".to_owned())
+			)
+		);
+	}
+
+	#[test]
+	fn test_credential_helpers_context_and_reset() {
+		let (_td, repo) = repo_init().unwrap();
+		let mut config = repo.config().unwrap();
+
+		config
+			.set_multivar("credential.helper", "^ $", "a")
+			.unwrap();
+		config.set_multivar("credential.helper", "^ $", "").unwrap();
+		config
+			.set_multivar("credential.helper", "^ $", "b")
+			.unwrap();
+		config
+			.set_str("credential.https://example.com.helper", "c")
+			.unwrap();
+
+		assert_eq!(
+			credential_helpers(&config, "https://github.com/x"),
+			vec![String::from("b")]
+		);
+		assert_eq!(
+			credential_helpers(&config, "https://example.com/x"),
+			vec![String::from("b"), String::from("c")]
+		);
+	}
+
+	#[test]
+	fn test_approve_and_reject_credential() {
+		let (td, repo) = repo_init().unwrap();
+		let log = td.path().join("log");
+		set_logging_helper(&repo, &log);
+
+		let config = repo.config().unwrap();
+		let url = "https://example.com:8080/repo";
+		let cred = BasicAuthCredential::new(
+			Some("user".to_owned()),
+			Some("pwd".to_owned()),
+		);
+
+		approve_credential(&config, url, &cred);
+		reject_credential(&config, url, &cred);
+
+		let input = "protocol=https\nhost=example.com:8080\nusername=user\npassword=pwd\n\n";
+		assert_eq!(
+			fs::read_to_string(&log).unwrap(),
+			format!("store\n{input}erase\n{input}")
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_fill_credential_with_askpass() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let (td, repo) = repo_init().unwrap();
+		reset_credential_helpers(&repo);
+		let askpass = td.path().join("askpass.sh");
+		fs::write(
+			&askpass,
+			"#!/bin/sh\ncase \"$1\" in Password*user@*) echo secret;; esac\n",
+		)
+		.unwrap();
+		fs::set_permissions(
+			&askpass,
+			fs::Permissions::from_mode(0o755),
+		)
+		.unwrap();
+
+		assert_eq!(
+			fill_credential_with_askpass(
+				&repo.config().unwrap(),
+				"https://user@example.com/repo",
+				askpass.to_str(),
+			),
+			BasicAuthCredential::new(
+				Some("user".to_owned()),
+				Some("secret".to_owned())
+			)
+		);
+	}
 }
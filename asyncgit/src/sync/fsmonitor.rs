@@ -0,0 +1,264 @@
+//! sync git api to query a `core.fsmonitor` hook
+//!
+//! see <https://git-scm.com/docs/githooks#_fsmonitor_watchman>
+
+use super::{
+	config::get_config_string_repo, repository::repo,
+	utils::work_dir, RepoPath,
+};
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use std::{
+	process::Command,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+/// settings that allow `status` to avoid scanning the whole worktree
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StatusAccelerationConfig {
+	/// hook command configured in `core.fsmonitor`
+	pub fsmonitor: Option<String>,
+	/// protocol version of the hook (`core.fsmonitorHookVersion`)
+	pub fsmonitor_version: u8,
+	/// untracked files may be reused between runs (`core.untrackedCache`)
+	pub untracked_cache: bool,
+}
+
+/// result of querying the fsmonitor hook
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FsMonitorChanges {
+	/// token to hand to the next query
+	pub token: String,
+	/// paths changed since the last query (relative to the workdir),
+	/// `None` if the hook could not narrow the changes down
+	pub paths: Option<Vec<String>>,
+}
+
+///
+pub fn status_acceleration_config(
+	repo_path: &RepoPath,
+) -> Result<StatusAccelerationConfig> {
+	scope_time!("status_acceleration_config");
+
+	let repo = repo(repo_path)?;
+
+	let fsmonitor = get_config_string_repo(&repo, "core.fsmonitor")?
+		.filter(|value| {
+			match git2::Config::parse_bool(value.as_str()) {
+				Ok(true) => {
+					// `true` selects the builtin fsmonitor daemon which is talked to via IPC
+					log::warn!("core.fsmonitor: builtin daemon is not supported");
+					false
+				}
+				Ok(false) => false,
+				Err(_) => !value.is_empty(),
+			}
+		});
+
+	let fsmonitor_version =
+		get_config_string_repo(&repo, "core.fsmonitorHookVersion")?
+			.as_deref()
+			.map_or(2, |version| if version == "1" { 1 } else { 2 });
+
+	let untracked_cache =
+		match get_config_string_repo(&repo, "core.untrackedCache")? {
+			Some(value) if value == "keep" => {
+				index_has_untracked_cache(repo_path)
+			}
+			Some(value) => git2::Config::parse_bool(value.as_str())
+				.unwrap_or_default(),
+			None => index_has_untracked_cache(repo_path),
+		};
+
+	Ok(StatusAccelerationConfig {
+		fsmonitor,
+		fsmonitor_version,
+		untracked_cache,
+	})
+}
+
+/// `keep` (the default) means git maintains the untracked cache whenever the index already has one
+fn index_has_untracked_cache(repo_path: &RepoPath) -> bool {
+	super::gix_repo(repo_path)
+		.ok()
+		.and_then(|repo| repo.index_or_empty().ok())
+		.is_some_and(|index| index.untracked().is_some())
+}
+
+/// run the `core.fsmonitor` hook asking for changes since `token`.
+/// the hook protocol expects an empty token on the first query.
+pub fn query_fsmonitor(
+	repo_path: &RepoPath,
+	hook: &str,
+	version: u8,
+	token: Option<&str>,
+) -> Result<FsMonitorChanges> {
+	scope_time!("query_fsmonitor");
+
+	let repo = repo(repo_path)?;
+	let workdir = work_dir(&repo)?;
+
+	// version 1 hooks do not report a token, they get a timestamp in nanoseconds instead
+	let next_v1_token = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_nanos().to_string())
+		.unwrap_or_default();
+
+	let output = Command::new(gix::path::env::shell())
+		.arg("-c")
+		.arg(format!("{hook} \"$@\""))
+		.arg(hook)
+		.arg(version.to_string())
+		.arg(token.unwrap_or_default())
+		.current_dir(workdir)
+		.output()?;
+
+	if !output.status.success() {
+		return Err(Error::Generic(format!(
+			"fsmonitor hook '{hook}' failed: {}",
+			String::from_utf8_lossy(&output.stderr)
+		)));
+	}
+
+	let stdout = String::from_utf8_lossy(&output.stdout);
+
+	Ok(if version == 1 {
+		parse_hook_output(next_v1_token, &stdout)
+	} else {
+		let (token, paths) =
+			stdout.split_once('\0').unwrap_or((&stdout, ""));
+		parse_hook_output(token.to_string(), paths)
+	})
+}
+
+fn parse_hook_output(token: String, paths: &str) -> FsMonitorChanges {
+	let paths: Vec<String> = paths
+		.split('\0')
+		.filter(|path| !path.is_empty())
+		.map(ToString::to_string)
+		.collect();
+
+	// "/" tells us that everything needs to be rescanned
+	let paths =
+		(!paths.iter().any(|path| path == "/")).then_some(paths);
+
+	FsMonitorChanges { token, paths }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init;
+	use std::{fs::File, io::Write, path::Path};
+
+	#[cfg(unix)]
+	fn create_hook(path: &Path, script: &str) {
+		use std::os::unix::fs::PermissionsExt;
+
+		File::create(path)
+			.unwrap()
+			.write_all(script.as_bytes())
+			.unwrap();
+
+		std::fs::set_permissions(
+			path,
+			std::fs::Permissions::from_mode(0o755),
+		)
+		.unwrap();
+	}
+
+	#[test]
+	fn test_acceleration_config() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(
+			status_acceleration_config(repo_path).unwrap(),
+			StatusAccelerationConfig {
+				fsmonitor: None,
+				fsmonitor_version: 2,
+				untracked_cache: false,
+			}
+		);
+
+		let mut config = repo.config().unwrap();
+		config.set_str("core.fsmonitor", "true").unwrap();
+		config.set_str("core.untrackedCache", "true").unwrap();
+
+		assert_eq!(
+			status_acceleration_config(repo_path).unwrap(),
+			StatusAccelerationConfig {
+				fsmonitor: None,
+				fsmonitor_version: 2,
+				untracked_cache: true,
+			}
+		);
+
+		config
+			.set_str("core.fsmonitor", ".git/hooks/query-watchman")
+			.unwrap();
+		config.set_str("core.fsmonitorHookVersion", "1").unwrap();
+
+		assert_eq!(
+			status_acceleration_config(repo_path).unwrap(),
+			StatusAccelerationConfig {
+				fsmonitor: Some(".git/hooks/query-watchman".into()),
+				fsmonitor_version: 1,
+				untracked_cache: true,
+			}
+		);
+	}
+
+	#[test]
+	fn test_parse_hook_output() {
+		assert_eq!(
+			parse_hook_output(String::from("t"), "a\0b/c\0"),
+			FsMonitorChanges {
+				token: String::from("t"),
+				paths: Some(vec!["a".into(), "b/c".into()]),
+			}
+		);
+
+		assert_eq!(
+			parse_hook_output(String::from("t"), "a\0/\0"),
+			FsMonitorChanges {
+				token: String::from("t"),
+				paths: None,
+			}
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_query_hook() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let hook = root.join("query-hook");
+		create_hook(
+			&hook,
+			"#!/bin/sh\ntest \"$1\" = 2 || exit 1\nif [ -z \"$2\" ]; then printf 'c:1\\0/\\0'; else printf 'c:2\\0foo.txt\\0dir/bar\\0'; fi\n",
+		);
+
+		let hook = hook.to_str().unwrap();
+
+		let changes =
+			query_fsmonitor(repo_path, hook, 2, None).unwrap();
+		assert_eq!(changes.token, "c:1");
+		assert_eq!(changes.paths, None);
+
+		let changes =
+			query_fsmonitor(repo_path, hook, 2, Some("c:1")).unwrap();
+		assert_eq!(changes.token, "c:2");
+		assert_eq!(
+			changes.paths,
+			Some(vec!["foo.txt".into(), "dir/bar".into()])
+		);
+
+		assert!(query_fsmonitor(repo_path, hook, 1, None).is_err());
+	}
+}
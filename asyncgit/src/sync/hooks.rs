@@ -1,7 +1,17 @@
-use super::{repository::repo, RepoPath};
-use crate::error::Result;
-pub use git2_hooks::PrepareCommitMsgSource;
+use super::{
+	repository::repo, utils::get_head_repo, CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{Oid, Repository};
+pub use git2_hooks::{
+	HookControl, PrePushRef, PrepareCommitMsgSource, RewriteCommand,
+};
 use scopetime::scope_time;
+use std::time::Duration;
+
+/// git config for the number of seconds after which hooks run in the
+/// background get killed, unset or `0` means they may run forever
+const CONFIG_HOOKS_TIMEOUT: &str = "gitui.hooksTimeout";
 
 ///
 #[derive(Debug, PartialEq, Eq)]
@@ -47,6 +57,49 @@ pub fn hooks_pre_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	Ok(git2_hooks::hooks_pre_commit(&repo, None)?.into())
 }
 
+/// see `git2_hooks::hooks_pre_commit_controlled`
+pub fn hooks_pre_commit_controlled(
+	repo_path: &RepoPath,
+	control: &HookControl,
+) -> Result<HookResult> {
+	scope_time!("hooks_pre_commit_controlled");
+
+	let repo = repo(repo_path)?;
+
+	Ok(
+		git2_hooks::hooks_pre_commit_controlled(
+			&repo, None, control,
+		)?
+		.into(),
+	)
+}
+
+/// whether there is a pre-commit hook to run
+pub fn hooks_pre_commit_found(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::hook_found(
+		&repo,
+		None,
+		git2_hooks::HOOK_PRE_COMMIT,
+	)?)
+}
+
+/// timeout for hooks configured in `gitui.hooksTimeout` (seconds)
+pub fn hooks_timeout(
+	repo_path: &RepoPath,
+) -> Result<Option<Duration>> {
+	let repo = repo(repo_path)?;
+
+	Ok(repo
+		.config()?
+		.get_i64(CONFIG_HOOKS_TIMEOUT)
+		.ok()
+		.and_then(|secs| u64::try_from(secs).ok())
+		.filter(|secs| *secs > 0)
+		.map(Duration::from_secs))
+}
+
 /// see `git2_hooks::hooks_post_commit`
 pub fn hooks_post_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	scope_time!("hooks_post_commit");
@@ -73,12 +126,147 @@ pub fn hooks_prepare_commit_msg(
 }
 
 /// see `git2_hooks::hooks_pre_push`
-pub fn hooks_pre_push(repo_path: &RepoPath) -> Result<HookResult> {
+pub fn hooks_pre_push(
+	repo_path: &RepoPath,
+	remote: &str,
+	updates: &[PrePushRef],
+) -> Result<HookResult> {
 	scope_time!("hooks_pre_push");
 
 	let repo = repo(repo_path)?;
 
-	Ok(git2_hooks::hooks_pre_push(&repo, None)?.into())
+	hooks_pre_push_repo(&repo, remote, updates)
+}
+
+/// runs the pre-push hook with the url of `remote` as git would
+/// before pushing `updates`, rejects the push if the hook fails
+pub fn hooks_pre_push_check(
+	repo: &Repository,
+	remote: &str,
+	updates: &[PrePushRef],
+) -> Result<()> {
+	match hooks_pre_push_repo(repo, remote, updates)? {
+		HookResult::Ok => Ok(()),
+		HookResult::NotOk(e) => {
+			log::error!("pre-push hook failed: {e}");
+			Err(Error::Generic(format!("pre-push hook failed:\n{e}")))
+		}
+	}
+}
+
+/// see `git2_hooks::hooks_post_checkout`
+///
+/// `prev_head` is HEAD before the checkout, the new HEAD is looked up
+pub fn hooks_post_checkout(
+	repo_path: &RepoPath,
+	prev_head: Option<CommitId>,
+) -> Result<HookResult> {
+	scope_time!("hooks_post_checkout");
+
+	let repo = repo(repo_path)?;
+	let new_head =
+		get_head_repo(&repo).map_or_else(|_| Oid::zero(), Into::into);
+
+	Ok(git2_hooks::hooks_post_checkout(
+		&repo,
+		None,
+		prev_head.map_or_else(Oid::zero, Into::into),
+		new_head,
+		true,
+	)?
+	.into())
+}
+
+/// see `git2_hooks::hooks_post_merge`
+pub fn hooks_post_merge(
+	repo_path: &RepoPath,
+	squash: bool,
+) -> Result<HookResult> {
+	scope_time!("hooks_post_merge");
+
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::hooks_post_merge(&repo, None, squash)?.into())
+}
+
+/// see `git2_hooks::hooks_pre_rebase`
+pub fn hooks_pre_rebase(
+	repo_path: &RepoPath,
+	upstream: &str,
+	branch: Option<&str>,
+) -> Result<HookResult> {
+	scope_time!("hooks_pre_rebase");
+
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::hooks_pre_rebase(&repo, None, upstream, branch)?
+		.into())
+}
+
+/// see `git2_hooks::hooks_post_rewrite`
+pub fn hooks_post_rewrite(
+	repo_path: &RepoPath,
+	command: RewriteCommand,
+	rewritten: &[(CommitId, CommitId)],
+) -> Result<HookResult> {
+	scope_time!("hooks_post_rewrite");
+
+	let repo = repo(repo_path)?;
+
+	hooks_post_rewrite_repo(&repo, command, rewritten)
+}
+
+pub fn hooks_post_rewrite_repo(
+	repo: &Repository,
+	command: RewriteCommand,
+	rewritten: &[(CommitId, CommitId)],
+) -> Result<HookResult> {
+	let rewritten = rewritten
+		.iter()
+		.map(|(old, new)| ((*old).into(), (*new).into()))
+		.collect::<Vec<_>>();
+
+	Ok(git2_hooks::hooks_post_rewrite(
+		repo, None, command, &rewritten,
+	)?
+	.into())
+}
+
+/// runs the pre-merge-commit hook before committing a merge that
+/// went through without conflicts, a failing hook leaves the merge
+/// uncommitted
+pub fn hooks_pre_merge_commit_check(repo: &Repository) -> Result<()> {
+	match git2_hooks::hooks_pre_merge_commit(repo, None)?.into() {
+		HookResult::Ok => Ok(()),
+		HookResult::NotOk(e) => {
+			log::error!("pre-merge-commit hook failed: {e}");
+			Err(Error::Generic(format!(
+				"pre-merge-commit hook failed:\n{e}"
+			)))
+		}
+	}
+}
+
+fn hooks_pre_push_repo(
+	repo: &Repository,
+	remote: &str,
+	updates: &[PrePushRef],
+) -> Result<HookResult> {
+	let url = {
+		let remote = repo.find_remote(remote)?;
+		remote
+			.pushurl()
+			.or_else(|| remote.url())
+			.unwrap_or_default()
+			.to_string()
+	};
+
+	Ok(
+		git2_hooks::hooks_pre_push(
+			repo, None, remote, &url, updates,
+		)?
+		.into(),
+	)
 }
 
 #[cfg(test)]
@@ -89,7 +277,13 @@ mod tests {
 	use tempfile::TempDir;
 
 	use super::*;
-	use crate::sync::tests::repo_init_with_prefix;
+	use crate::sync::{
+		checkout_branch, create_branch,
+		tests::{
+			repo_init as repo_init_utf8, repo_init_with_prefix,
+			write_commit_file,
+		},
+	};
 
 	fn repo_init() -> Result<(TempDir, Repository)> {
 		let mut os_string: OsString = OsString::new();
@@ -248,4 +442,54 @@ mod tests {
 
 		assert_eq!(msg, String::from("msg\n"));
 	}
+
+	#[test]
+	fn test_post_checkout_heads() {
+		let (_td, repo) = repo_init_utf8().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_path_buf().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		create_branch(repo_path, "other").unwrap();
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+
+		let hook = b"#!/bin/sh
+	echo \"$1 $2 $3\"
+	exit 1
+		";
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_POST_CHECKOUT,
+			hook,
+		);
+
+		checkout_branch(repo_path, "master").unwrap();
+
+		let res = hooks_post_checkout(repo_path, Some(c2)).unwrap();
+		assert_eq!(res, HookResult::NotOk(format!("{c2} {c1} 1\n")));
+	}
+
+	#[test]
+	fn test_hooks_timeout_config() {
+		let (_td, repo) = repo_init_utf8().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_path_buf().into();
+
+		assert_eq!(hooks_timeout(repo_path).unwrap(), None);
+
+		repo.config()
+			.unwrap()
+			.set_i64(CONFIG_HOOKS_TIMEOUT, 0)
+			.unwrap();
+		assert_eq!(hooks_timeout(repo_path).unwrap(), None);
+
+		repo.config()
+			.unwrap()
+			.set_i64(CONFIG_HOOKS_TIMEOUT, 30)
+			.unwrap();
+		assert_eq!(
+			hooks_timeout(repo_path).unwrap(),
+			Some(Duration::from_secs(30))
+		);
+	}
 }
@@ -383,4 +383,36 @@ mod tests {
 
 		assert_eq!(items.len(), 2);
 	}
+
+	#[test]
+	fn test_logwalker_with_filter_search_mailmap() {
+		let (_td, repo) = repo_init_empty().unwrap();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		write_commit_file(&repo, "foo", "b", "commit2");
+
+		let root = repo.path().parent().unwrap();
+		File::create(root.join(".mailmap"))
+			.unwrap()
+			.write_all(
+				b"Canonical Person <canon@example.com> <email>",
+			)
+			.unwrap();
+
+		let log_filter = filter_commit_by_search(
+			LogFilterSearch::new(LogFilterSearchOptions {
+				fields: SearchFields::AUTHORS,
+				options: SearchOptions::default(),
+				search_pattern: String::from("canon@example"),
+			}),
+		);
+
+		let mut items = Vec::new();
+		let mut walker = LogWalker::new(&repo, 100)
+			.unwrap()
+			.filter(Some(log_filter));
+		walker.read(&mut items).unwrap();
+
+		assert_eq!(items.len(), 2);
+	}
 }
@@ -2,6 +2,7 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		branch::merge_commit::commit_merge_with_head,
+		hooks::hooks_pre_merge_commit_check,
 		rebase::{
 			abort_rebase, continue_rebase, get_rebase_progress,
 		},
@@ -64,6 +65,181 @@ pub fn merge_branch(
 	Ok(())
 }
 
+/// how [`merge_branch_with_options`] combines the branch into HEAD
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeMode {
+	/// merge into the index and workdir, leaving a pending merge
+	/// to be committed (fast-forwards included)
+	#[default]
+	Default,
+	/// always create a merge commit, even if a fast-forward
+	/// is possible (`--no-ff`)
+	NoFastForward,
+	/// only fast-forward, fail otherwise (`--ff-only`)
+	FastForwardOnly,
+	/// stage the combined changes without recording a merge
+	/// (`--squash`)
+	Squash,
+}
+
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BranchMergeOptions {
+	///
+	pub mode: MergeMode,
+	/// commit message used for `NoFastForward` and `Squash`,
+	/// a `Squash` merge is only committed if this is set
+	pub message: Option<String>,
+}
+
+/// what [`merge_branch_with_options`] did
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeOutcome {
+	/// HEAD already contains the branch
+	UpToDate,
+	/// HEAD was fast-forwarded to this commit
+	FastForward(CommitId),
+	/// this commit was created
+	Committed(CommitId),
+	/// index and workdir contain the merge (possibly with conflicts)
+	/// waiting to be committed
+	Pending,
+}
+
+/// merges `branch` into HEAD according to `options`
+pub fn merge_branch_with_options(
+	repo_path: &RepoPath,
+	branch: &str,
+	branch_type: BranchType,
+	options: &BranchMergeOptions,
+) -> Result<MergeOutcome> {
+	scope_time!("merge_branch_with_options");
+
+	let repo = repo(repo_path)?;
+
+	let branch = repo.find_branch(branch, branch_type)?;
+	let reference = branch.into_reference();
+	let commit = reference.peel_to_commit()?;
+	let annotated = repo.reference_to_annotated_commit(&reference)?;
+
+	let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+
+	//TODO: support merge on unborn
+	if analysis.is_unborn() {
+		return Err(Error::Generic("head is unborn".into()));
+	}
+
+	if analysis.is_up_to_date() {
+		return Ok(MergeOutcome::UpToDate);
+	}
+
+	match options.mode {
+		MergeMode::Default => {
+			repo.merge(&[&annotated], None, None)?;
+			Ok(MergeOutcome::Pending)
+		}
+		MergeMode::FastForwardOnly => {
+			if !analysis.is_fast_forward() {
+				return Err(Error::Generic(
+					"fast-forward not possible".into(),
+				));
+			}
+
+			let msg = format!(
+				"merge {}: Fast-forward",
+				String::from_utf8_lossy(reference.shorthand_bytes())
+			);
+
+			repo.checkout_tree(commit.as_object(), None)?;
+			repo.head()?.set_target(commit.id(), &msg)?;
+
+			Ok(MergeOutcome::FastForward(commit.id().into()))
+		}
+		MergeMode::NoFastForward => {
+			repo.merge(&[&annotated], None, None)?;
+
+			if repo.index()?.has_conflicts() {
+				return Ok(MergeOutcome::Pending);
+			}
+
+			hooks_pre_merge_commit_check(&repo)?;
+
+			let msg = match &options.message {
+				Some(msg) => msg.clone(),
+				None => repo.message()?,
+			};
+
+			let id = commit_merge_with_head(&repo, &[commit], &msg)?;
+			repo.cleanup_state()?;
+
+			Ok(MergeOutcome::Committed(id))
+		}
+		MergeMode::Squash => {
+			repo.merge(&[&annotated], None, None)?;
+			// forget the merge head so the result is a plain commit
+			repo.cleanup_state()?;
+
+			match &options.message {
+				Some(msg) if !repo.index()?.has_conflicts() => {
+					let id = commit_merge_with_head(&repo, &[], msg)?;
+					Ok(MergeOutcome::Committed(id))
+				}
+				_ => Ok(MergeOutcome::Pending),
+			}
+		}
+	}
+}
+
+/// paths that would conflict when merging `branch` into HEAD,
+/// computed in memory without touching index or workdir
+pub fn merge_conflicts_preview(
+	repo_path: &RepoPath,
+	branch: &str,
+	branch_type: BranchType,
+) -> Result<Vec<String>> {
+	scope_time!("merge_conflicts_preview");
+
+	let repo = repo(repo_path)?;
+
+	let ours = repo.head()?.peel_to_commit()?;
+	let theirs = repo
+		.find_branch(branch, branch_type)?
+		.into_reference()
+		.peel_to_commit()?;
+
+	let ancestor = repo
+		.find_commit(repo.merge_base(ours.id(), theirs.id())?)?
+		.tree()?;
+
+	let index = repo.merge_trees(
+		&ancestor,
+		&ours.tree()?,
+		&theirs.tree()?,
+		None,
+	)?;
+
+	let mut paths = index
+		.conflicts()?
+		.map(|conflict| {
+			let conflict = conflict?;
+			let entry = conflict
+				.our
+				.or(conflict.their)
+				.or(conflict.ancestor)
+				.ok_or_else(|| {
+					Error::Generic("empty conflict entry".into())
+				})?;
+
+			Ok(String::from_utf8_lossy(&entry.path).into_owned())
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	paths.sort();
+	paths.dedup();
+
+	Ok(paths)
+}
+
 ///
 pub fn rebase_progress(
 	repo_path: &RepoPath,
@@ -155,8 +331,9 @@ pub fn merge_commit(
 mod tests {
 	use super::*;
 	use crate::sync::{
-		create_branch,
+		checkout_branch, create_branch,
 		tests::{repo_init, write_commit_file},
+		utils::get_head_repo,
 		RepoPath,
 	};
 	use pretty_assertions::assert_eq;
@@ -185,4 +362,228 @@ mod tests {
 
 		assert_eq!(mergeheads[0], c1);
 	}
+
+	fn diverged_repo() -> (tempfile::TempDir, Repository, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "base", "base");
+
+		create_branch(&repo_path, "feature").unwrap();
+		write_commit_file(&repo, "test.txt", "feature", "feature");
+		write_commit_file(&repo, "other.txt", "feature", "other");
+
+		checkout_branch(&repo_path, "master").unwrap();
+		write_commit_file(&repo, "test.txt", "master", "master");
+
+		(td, repo, repo_path)
+	}
+
+	#[test]
+	fn test_conflicts_preview() {
+		let (_td, repo, repo_path) = diverged_repo();
+
+		let conflicts = merge_conflicts_preview(
+			&repo_path,
+			"feature",
+			BranchType::Local,
+		)
+		.unwrap();
+
+		assert_eq!(conflicts, vec![String::from("test.txt")]);
+
+		// nothing was touched
+		assert_eq!(repo.state(), git2::RepositoryState::Clean);
+		assert!(!repo.index().unwrap().has_conflicts());
+		assert_eq!(
+			std::fs::read_to_string(
+				repo.workdir().unwrap().join("test.txt")
+			)
+			.unwrap(),
+			"master"
+		);
+	}
+
+	#[test]
+	fn test_ff_only() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "base", "base");
+		create_branch(repo_path, "feature").unwrap();
+		let c2 = write_commit_file(&repo, "test.txt", "2", "c2");
+		checkout_branch(repo_path, "master").unwrap();
+
+		let options = BranchMergeOptions {
+			mode: MergeMode::FastForwardOnly,
+			message: None,
+		};
+
+		let res = merge_branch_with_options(
+			repo_path,
+			"feature",
+			BranchType::Local,
+			&options,
+		)
+		.unwrap();
+
+		assert_eq!(res, MergeOutcome::FastForward(c2));
+		assert_eq!(get_head_repo(&repo).unwrap(), c2);
+		assert_eq!(
+			repo.reflog("HEAD").unwrap().get(0).unwrap().message(),
+			Some("merge feature: Fast-forward")
+		);
+
+		let res = merge_branch_with_options(
+			repo_path,
+			"feature",
+			BranchType::Local,
+			&options,
+		)
+		.unwrap();
+
+		assert_eq!(res, MergeOutcome::UpToDate);
+
+		write_commit_file(&repo, "test.txt", "3", "c3");
+		checkout_branch(repo_path, "feature").unwrap();
+		write_commit_file(&repo, "other.txt", "4", "c4");
+		checkout_branch(repo_path, "master").unwrap();
+
+		assert!(merge_branch_with_options(
+			repo_path,
+			"feature",
+			BranchType::Local,
+			&options,
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn test_no_ff() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "test.txt", "base", "base");
+		create_branch(repo_path, "feature").unwrap();
+		let c2 = write_commit_file(&repo, "test.txt", "2", "c2");
+		checkout_branch(repo_path, "master").unwrap();
+
+		let res = merge_branch_with_options(
+			repo_path,
+			"feature",
+			BranchType::Local,
+			&BranchMergeOptions {
+				mode: MergeMode::NoFastForward,
+				message: Some(String::from("merge it")),
+			},
+		)
+		.unwrap();
+
+		let id = get_head_repo(&repo).unwrap();
+		assert_eq!(res, MergeOutcome::Committed(id));
+
+		let commit = repo.find_commit(id.into()).unwrap();
+		assert_eq!(commit.message(), Some("merge it"));
+		assert_eq!(
+			commit
+				.parent_ids()
+				.map(CommitId::from)
+				.collect::<Vec<_>>(),
+			vec![c1, c2]
+		);
+		assert_eq!(repo.state(), git2::RepositoryState::Clean);
+	}
+
+	#[test]
+	fn test_no_ff_pre_merge_commit_rejects() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "test.txt", "base", "base");
+		create_branch(repo_path, "feature").unwrap();
+		write_commit_file(&repo, "test.txt", "2", "c2");
+		checkout_branch(repo_path, "master").unwrap();
+
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_PRE_MERGE_COMMIT,
+			b"#!/bin/sh\necho 'rejected'\nexit 1\n",
+		);
+
+		let res = merge_branch_with_options(
+			repo_path,
+			"feature",
+			BranchType::Local,
+			&BranchMergeOptions {
+				mode: MergeMode::NoFastForward,
+				message: None,
+			},
+		);
+
+		assert!(res.is_err());
+		// the merge is left for the user to commit
+		assert_eq!(get_head_repo(&repo).unwrap(), c1);
+		assert_eq!(repo.state(), git2::RepositoryState::Merge);
+	}
+
+	#[test]
+	fn test_squash() {
+		let (_td, repo, repo_path) = diverged_repo();
+
+		// resolve the conflict upfront by matching master
+		checkout_branch(&repo_path, "feature").unwrap();
+		write_commit_file(&repo, "test.txt", "master", "align");
+		checkout_branch(&repo_path, "master").unwrap();
+		let head = get_head_repo(&repo).unwrap();
+
+		let res = merge_branch_with_options(
+			&repo_path,
+			"feature",
+			BranchType::Local,
+			&BranchMergeOptions {
+				mode: MergeMode::Squash,
+				message: None,
+			},
+		)
+		.unwrap();
+
+		assert_eq!(res, MergeOutcome::Pending);
+		assert_eq!(repo.state(), git2::RepositoryState::Clean);
+		assert!(!repo.path().join("MERGE_HEAD").exists());
+
+		abort_pending_state(&repo_path).unwrap();
+
+		let res = merge_branch_with_options(
+			&repo_path,
+			"feature",
+			BranchType::Local,
+			&BranchMergeOptions {
+				mode: MergeMode::Squash,
+				message: Some(String::from("squashed")),
+			},
+		)
+		.unwrap();
+
+		let id = get_head_repo(&repo).unwrap();
+		assert_eq!(res, MergeOutcome::Committed(id));
+
+		let commit = repo.find_commit(id.into()).unwrap();
+		assert_eq!(commit.message(), Some("squashed"));
+		assert_eq!(
+			commit
+				.parent_ids()
+				.map(CommitId::from)
+				.collect::<Vec<_>>(),
+			vec![head]
+		);
+		assert!(repo.workdir().unwrap().join("other.txt").exists());
+	}
 }
@@ -14,17 +14,20 @@ mod commits_info;
 mod config;
 pub mod cred;
 pub mod diff;
+mod fsmonitor;
 mod hooks;
 mod hunks;
 mod ignore;
 mod logwalker;
 mod merge;
+mod notes;
 mod patches;
 mod rebase;
 pub mod remotes;
 mod repository;
 mod reset;
 mod reword;
+mod shallow;
 pub mod sign;
 mod staging;
 mod stash;
@@ -34,16 +37,21 @@ mod submodules;
 mod tags;
 mod tree;
 pub mod utils;
+mod verify;
 
 pub use blame::{blame_file, BlameHunk, FileBlame};
 pub use branch::{
 	branch_compare_upstream, checkout_branch, checkout_commit,
+	compare::{compare_branches, BranchesCompare},
 	config_is_pull_rebase, create_branch, delete_branch,
 	get_branch_remote, get_branch_upstream_merge, get_branches_info,
+	get_merged_branches_info, get_remote_branch_upstream,
 	merge_commit::merge_upstream_commit,
 	merge_ff::branch_merge_upstream_fastforward,
-	merge_rebase::merge_upstream_rebase, rename::rename_branch,
-	validate_branch_name, BranchCompare, BranchDetails, BranchInfo,
+	merge_rebase::merge_upstream_rebase,
+	rename::rename_branch,
+	set_branch_upstream, unset_branch_upstream, validate_branch_name,
+	BranchCompare, BranchDetails, BranchInfo,
 };
 pub use commit::{amend, commit, tag_commit};
 pub use commit_details::{
@@ -64,34 +72,47 @@ pub use config::{
 	ShowUntrackedFilesConfig,
 };
 pub use diff::get_diff_commit;
+pub use fsmonitor::{
+	query_fsmonitor, status_acceleration_config, FsMonitorChanges,
+	StatusAccelerationConfig,
+};
 pub use git2::BranchType;
 pub use hooks::{
-	hooks_commit_msg, hooks_post_commit, hooks_pre_commit,
-	hooks_pre_push, hooks_prepare_commit_msg, HookResult,
-	PrepareCommitMsgSource,
+	hooks_commit_msg, hooks_post_checkout, hooks_post_commit,
+	hooks_post_merge, hooks_post_rewrite, hooks_pre_commit,
+	hooks_pre_commit_controlled, hooks_pre_commit_found,
+	hooks_pre_push, hooks_pre_rebase, hooks_prepare_commit_msg,
+	hooks_timeout, HookControl, HookResult, PrePushRef,
+	PrepareCommitMsgSource, RewriteCommand,
 };
 pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
 pub use ignore::add_to_ignore;
 pub use logwalker::{LogWalker, LogWalkerWithoutFilter};
 pub use merge::{
 	abort_pending_rebase, abort_pending_state,
-	continue_pending_rebase, merge_branch, merge_commit, merge_msg,
-	mergehead_ids, rebase_progress,
+	continue_pending_rebase, merge_branch, merge_branch_with_options,
+	merge_commit, merge_conflicts_preview, merge_msg, mergehead_ids,
+	rebase_progress, BranchMergeOptions, MergeMode, MergeOutcome,
 };
-pub use rebase::rebase_branch;
+pub use notes::{default_notes_ref, get_note, remove_note, set_note};
+pub use rebase::{rebase_branch, RebaseState};
 pub use remotes::{
-	add_remote, delete_remote, get_default_remote,
+	add_remote, clone, delete_remote, get_default_remote,
 	get_default_remote_for_fetch, get_default_remote_for_push,
 	get_remote_url, get_remotes, push::AsyncProgress, rename_remote,
 	tags::PushTagsProgress, update_remote_url, validate_remote_name,
+	CloneOptions,
 };
 pub(crate) use repository::{gix_repo, repo};
 pub use repository::{RepoPath, RepoPathRef};
 pub use reset::{reset_repo, reset_stage, reset_workdir};
 pub use reword::reword;
+pub use shallow::{is_shallow, shallow_boundary};
 pub use staging::{discard_lines, stage_lines};
 pub use stash::{
-	get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
+	get_stash_parts, get_stashes, stash_apply, stash_branch,
+	stash_drop, stash_pop, stash_rename, stash_save,
+	stash_save_paths, stash_save_staged, StashParts,
 };
 pub use state::{repo_state, RepoState};
 pub use status::is_workdir_clean;
@@ -100,14 +121,17 @@ pub use submodules::{
 	SubmoduleInfo, SubmoduleParentInfo, SubmoduleStatus,
 };
 pub use tags::{
-	delete_tag, get_tags, get_tags_with_metadata, CommitTags, Tag,
-	TagWithMetadata, Tags,
+	cmp_tag_versions, delete_tag, describe_commit, get_tags,
+	get_tags_with_metadata, CommitTags, Tag, TagWithMetadata, Tags,
 };
 pub use tree::{tree_file_content, tree_files, TreeFile};
 pub use utils::{
 	get_head, get_head_tuple, repo_dir, repo_open_error,
 	stage_add_all, stage_add_file, stage_addremoved, Head,
 };
+pub use verify::{
+	verify_commit_signature, verify_tag_signature, SignatureState,
+};
 
 pub use git2::ResetType;
 
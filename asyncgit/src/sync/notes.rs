@@ -0,0 +1,163 @@
+//! git notes of commits
+
+use super::{
+	commit::signature_allow_undefined_name, repository::repo,
+	CommitId, RepoPath,
+};
+use crate::error::Result;
+use git2::ErrorCode;
+use scopetime::scope_time;
+
+/// notes ref used if `notes_ref` is `None`:
+/// `core.notesRef` or `refs/notes/commits`
+pub fn default_notes_ref(repo_path: &RepoPath) -> Result<String> {
+	let repo = repo(repo_path)?;
+
+	Ok(repo.note_default_ref()?)
+}
+
+/// note of a commit in `notes_ref` (see [`default_notes_ref`])
+pub fn get_note(
+	repo_path: &RepoPath,
+	commit_id: CommitId,
+	notes_ref: Option<&str>,
+) -> Result<Option<String>> {
+	scope_time!("get_note");
+
+	let repo = repo(repo_path)?;
+
+	let note = match repo.find_note(notes_ref, commit_id.into()) {
+		Ok(note) => note,
+		Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(Some(String::from_utf8_lossy(note.message_bytes()).into()))
+}
+
+/// adds a note to a commit or replaces its existing note
+pub fn set_note(
+	repo_path: &RepoPath,
+	commit_id: CommitId,
+	notes_ref: Option<&str>,
+	msg: &str,
+) -> Result<()> {
+	scope_time!("set_note");
+
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	repo.note(
+		&signature,
+		&signature,
+		notes_ref,
+		commit_id.into(),
+		msg,
+		true,
+	)?;
+
+	Ok(())
+}
+
+/// removes the note of a commit, if there is any
+pub fn remove_note(
+	repo_path: &RepoPath,
+	commit_id: CommitId,
+	notes_ref: Option<&str>,
+) -> Result<()> {
+	scope_time!("remove_note");
+
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	match repo.note_delete(
+		commit_id.into(),
+		notes_ref,
+		&signature,
+		&signature,
+	) {
+		Err(e) if e.code() != ErrorCode::NotFound => Err(e.into()),
+		_ => Ok(()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_notes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let id = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		assert_eq!(get_note(repo_path, id, None).unwrap(), None);
+
+		set_note(repo_path, id, None, "reviewed\n").unwrap();
+
+		assert_eq!(
+			get_note(repo_path, id, None).unwrap().as_deref(),
+			Some("reviewed\n")
+		);
+		assert!(repo.find_reference("refs/notes/commits").is_ok());
+
+		set_note(repo_path, id, None, "approved\n").unwrap();
+
+		assert_eq!(
+			get_note(repo_path, id, None).unwrap().as_deref(),
+			Some("approved\n")
+		);
+
+		remove_note(repo_path, id, None).unwrap();
+		remove_note(repo_path, id, None).unwrap();
+
+		assert_eq!(get_note(repo_path, id, None).unwrap(), None);
+	}
+
+	#[test]
+	fn test_notes_ref() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let id = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		assert_eq!(
+			default_notes_ref(repo_path).unwrap(),
+			"refs/notes/commits"
+		);
+
+		repo.config()
+			.unwrap()
+			.set_str("core.notesRef", "refs/notes/review")
+			.unwrap();
+
+		assert_eq!(
+			default_notes_ref(repo_path).unwrap(),
+			"refs/notes/review"
+		);
+
+		set_note(repo_path, id, None, "lgtm").unwrap();
+		set_note(repo_path, id, Some("refs/notes/other"), "other")
+			.unwrap();
+
+		assert!(repo.find_reference("refs/notes/review").is_ok());
+		assert_eq!(
+			get_note(repo_path, id, Some("refs/notes/review"))
+				.unwrap()
+				.as_deref(),
+			Some("lgtm")
+		);
+		assert_eq!(
+			get_note(repo_path, id, Some("refs/notes/other"))
+				.unwrap()
+				.as_deref(),
+			Some("other")
+		);
+	}
+}
@@ -1,9 +1,14 @@
-use git2::{BranchType, Repository};
+use git2::{BranchType, Oid, Rebase, Repository, Signature};
 use scopetime::scope_time;
 
 use crate::{
 	error::{Error, Result},
-	sync::repository::repo,
+	sync::{
+		hooks::{
+			hooks_post_rewrite_repo, HookResult, RewriteCommand,
+		},
+		repository::repo,
+	},
 };
 
 use super::{CommitId, RepoPath};
@@ -34,11 +39,12 @@ fn rebase_branch_repo(
 	rebase(repo, &annotated)
 }
 
-/// rebase attempt which aborts and undo's rebase if any conflict appears
+/// rebase attempt which aborts and undo's rebase if any conflict appears,
+/// returns the last rebased commit and the result of the post-rewrite hook
 pub fn conflict_free_rebase(
 	repo: &git2::Repository,
 	commit: &git2::AnnotatedCommit,
-) -> Result<CommitId> {
+) -> Result<(CommitId, HookResult)> {
 	let mut rebase = repo.rebase(None, Some(commit), None, None)?;
 	let signature =
 		crate::sync::commit::signature_allow_undefined_name(repo)?;
@@ -61,18 +67,52 @@ pub fn conflict_free_rebase(
 		return Err(Error::RebaseConflict);
 	}
 
-	rebase.finish(Some(&signature))?;
+	let hook = finish_rebase(repo, &mut rebase, Some(&signature))?;
 
-	last_commit.ok_or_else(|| {
+	let last_commit = last_commit.ok_or_else(|| {
 		Error::Generic(String::from("no commit rebased"))
-	})
+	})?;
+
+	Ok((last_commit, hook))
+}
+
+/// finishes `rebase` and runs the post-rewrite hook with the
+/// commits libgit2 recorded as rewritten along the way
+pub fn finish_rebase(
+	repo: &Repository,
+	rebase: &mut Rebase,
+	signature: Option<&Signature>,
+) -> Result<HookResult> {
+	let rewritten = rebase_rewritten(repo);
+
+	rebase.finish(signature)?;
+
+	hooks_post_rewrite_repo(repo, RewriteCommand::Rebase, &rewritten)
+}
+
+/// `(old, new)` pairs from the `rewritten` file libgit2 keeps in the
+/// rebase state dir
+fn rebase_rewritten(repo: &Repository) -> Vec<(CommitId, CommitId)> {
+	let path = repo.path().join("rebase-merge").join("rewritten");
+
+	std::fs::read_to_string(path)
+		.unwrap_or_default()
+		.lines()
+		.filter_map(|line| {
+			let (old, new) = line.split_once(' ')?;
+			Some((
+				Oid::from_str(old).ok()?.into(),
+				Oid::from_str(new).ok()?.into(),
+			))
+		})
+		.collect()
 }
 
 ///
 #[derive(PartialEq, Eq, Debug)]
 pub enum RebaseState {
-	///
-	Finished,
+	/// contains the result of the post-rewrite hook
+	Finished(HookResult),
 	///
 	Conflicted,
 }
@@ -101,9 +141,9 @@ pub fn rebase(
 		return Ok(RebaseState::Conflicted);
 	}
 
-	rebase.finish(Some(&signature))?;
+	let hook = finish_rebase(repo, &mut rebase, Some(&signature))?;
 
-	Ok(RebaseState::Finished)
+	Ok(RebaseState::Finished(hook))
 }
 
 /// continue pending rebase
@@ -138,9 +178,9 @@ pub fn continue_rebase(
 		return Ok(RebaseState::Conflicted);
 	}
 
-	rebase.finish(Some(&signature))?;
+	let hook = finish_rebase(repo, &mut rebase, Some(&signature))?;
 
-	Ok(RebaseState::Finished)
+	Ok(RebaseState::Finished(hook))
 }
 
 ///
@@ -191,7 +231,7 @@ mod test_conflict_free_rebase {
 		repo_state,
 		repository::repo,
 		tests::{repo_init, write_commit_file},
-		CommitId, RepoPath, RepoState,
+		CommitId, HookResult, RepoPath, RepoState,
 	};
 	use git2::{BranchType, Repository};
 
@@ -222,7 +262,7 @@ mod test_conflict_free_rebase {
 			.reference_to_annotated_commit(&branch.into_reference())
 			.unwrap();
 
-		conflict_free_rebase(&repo, &annotated).unwrap()
+		conflict_free_rebase(&repo, &annotated).unwrap().0
 	}
 
 	#[test]
@@ -280,6 +320,69 @@ mod test_conflict_free_rebase {
 
 		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
 	}
+
+	#[test]
+	fn test_post_rewrite_hook() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test1.txt", "test", "commit1");
+		create_branch(repo_path, "foo").unwrap();
+		let c2 =
+			write_commit_file(&repo, "test2.txt", "test", "commit2");
+		checkout_branch(repo_path, "master").unwrap();
+		write_commit_file(&repo, "test3.txt", "test", "commit3");
+		checkout_branch(repo_path, "foo").unwrap();
+
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_POST_REWRITE,
+			b"#!/bin/sh\necho \"$1\" > .git/rewritten-log\ncat >> .git/rewritten-log\n",
+		);
+
+		let r = test_rebase_branch_repo(repo_path, "master");
+
+		assert_eq!(
+			std::fs::read_to_string(
+				repo.path().join("rewritten-log")
+			)
+			.unwrap(),
+			format!("rebase\n{c2} {r}\n")
+		);
+	}
+
+	#[test]
+	fn test_post_rewrite_hook_fails() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test1.txt", "test", "commit1");
+		create_branch(repo_path, "foo").unwrap();
+		write_commit_file(&repo, "test2.txt", "test", "commit2");
+		checkout_branch(repo_path, "master").unwrap();
+		write_commit_file(&repo, "test3.txt", "test", "commit3");
+		checkout_branch(repo_path, "foo").unwrap();
+
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_POST_REWRITE,
+			b"#!/bin/sh\necho 'rejected' >&2\nexit 1\n",
+		);
+
+		let res =
+			rebase_branch(repo_path, "master", BranchType::Local)
+				.unwrap();
+
+		assert!(matches!(
+			res,
+			RebaseState::Finished(HookResult::NotOk(_))
+		));
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+	}
 }
 
 #[cfg(test)]
@@ -1,7 +1,15 @@
 use super::push::ProgressNotification;
-use crate::{error::Result, sync::cred::BasicAuthCredential};
+use crate::{
+	error::Result,
+	sync::cred::{
+		approve_credential, reject_credential, BasicAuthCredential,
+	},
+};
 use crossbeam_channel::Sender;
-use git2::{Cred, Error as GitError, RemoteCallbacks};
+use git2::{
+	Cred, Error as GitError, Oid, PushUpdate, RemoteCallbacks,
+	Repository,
+};
 use std::sync::{
 	atomic::{AtomicBool, Ordering},
 	Arc, Mutex,
@@ -11,6 +19,10 @@ use std::sync::{
 #[derive(Default, Clone)]
 pub struct CallbackStats {
 	pub push_rejected_msg: Option<(String, String)>,
+	/// url the basic credential was handed out for
+	pub credential_url: Option<String>,
+	/// the remote asked again after getting the basic credential
+	pub credential_rejected: bool,
 }
 
 ///
@@ -20,6 +32,8 @@ pub struct Callbacks {
 	basic_credential: Option<BasicAuthCredential>,
 	stats: Arc<Mutex<CallbackStats>>,
 	first_call_to_credentials: Arc<AtomicBool>,
+	/// remote ref and the id we expect it to point to on the remote
+	lease: Option<(String, Oid)>,
 }
 
 impl Callbacks {
@@ -37,15 +51,65 @@ impl Callbacks {
 			first_call_to_credentials: Arc::new(AtomicBool::new(
 				true,
 			)),
+			lease: None,
 		}
 	}
 
+	/// only allow the push if `refname` on the remote still points
+	/// to `expected` (zero meaning it must not exist)
+	#[must_use]
+	pub fn with_lease(
+		mut self,
+		refname: String,
+		expected: Oid,
+	) -> Self {
+		self.lease = Some((refname, expected));
+		self
+	}
+
 	///
 	pub fn get_stats(&self) -> Result<CallbackStats> {
 		let stats = self.stats.lock()?;
 		Ok(stats.clone())
 	}
 
+	/// lets the credential helpers store the basic credential if the
+	/// remote operation `succeeded` or erase it if it was rejected
+	pub fn report_credential(
+		&self,
+		config: &git2::Config,
+		succeeded: bool,
+	) {
+		let Some(cred) = &self.basic_credential else {
+			return;
+		};
+		let Ok(stats) = self.stats.lock() else {
+			return;
+		};
+		let Some(url) = &stats.credential_url else {
+			return;
+		};
+
+		if stats.credential_rejected {
+			reject_credential(config, url, cred);
+		} else if succeeded {
+			approve_credential(config, url, cred);
+		}
+	}
+
+	/// [`Self::report_credential`] with the config of `repo`, failing
+	/// to open it is only logged to not hide the remote operation result
+	pub fn report_credential_to_repo(
+		&self,
+		repo: &Repository,
+		succeeded: bool,
+	) {
+		match repo.config() {
+			Ok(config) => self.report_credential(&config, succeeded),
+			Err(e) => log::error!("credential helpers: {e}"),
+		}
+	}
+
 	///
 	pub fn callbacks<'a>(&self) -> RemoteCallbacks<'a> {
 		let mut callbacks = RemoteCallbacks::new();
@@ -80,6 +144,11 @@ impl Callbacks {
 			Ok(())
 		});
 
+		let this = self.clone();
+		callbacks.push_negotiation(move |updates| {
+			this.push_negotiation(updates)
+		});
+
 		let this = self.clone();
 		callbacks.credentials(
 			move |url, username_from_url, allowed_types| {
@@ -115,6 +184,34 @@ impl Callbacks {
 		}
 	}
 
+	fn push_negotiation(
+		&self,
+		updates: &[PushUpdate<'_>],
+	) -> std::result::Result<(), GitError> {
+		let Some((refname, expected)) = &self.lease else {
+			return Ok(());
+		};
+
+		for update in updates {
+			if update.dst_refname() != Some(refname.as_str()) {
+				continue;
+			}
+
+			log::debug!(
+				"push lease: '{refname}' remote [{}] expected [{expected}]",
+				update.src()
+			);
+
+			if update.src() != *expected {
+				return Err(GitError::from_str(&format!(
+					"stale info: '{refname}' on the remote changed since the last fetch"
+				)));
+			}
+		}
+
+		Ok(())
+	}
+
 	fn pack_progress(
 		&self,
 		stage: git2::PackBuilderStage,
@@ -191,6 +288,10 @@ impl Callbacks {
 			self.first_call_to_credentials
 				.store(false, Ordering::Relaxed);
 		} else {
+			if let Ok(mut stats) = self.stats.lock() {
+				stats.credential_rejected =
+					stats.credential_url.is_some();
+			}
 			return Err(GitError::from_str("Bad credentials."));
 		}
 
@@ -208,6 +309,9 @@ impl Callbacks {
 				username: Some(user),
 				password: Some(pwd),
 			}) if allowed_types.is_user_pass_plaintext() => {
+				if let Ok(mut stats) = self.stats.lock() {
+					stats.credential_url = Some(url.to_string());
+				}
 				Cred::userpass_plaintext(user, pwd)
 			}
 			Some(BasicAuthCredential {
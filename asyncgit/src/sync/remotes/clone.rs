@@ -0,0 +1,379 @@
+//! cloning a remote repository
+
+use super::{proxy_auto, push::ProgressNotification, Callbacks};
+use crate::{error::Result, sync::cred::BasicAuthCredential};
+use crossbeam_channel::Sender;
+use git2::{
+	build::RepoBuilder, FetchOptions, Repository,
+	SubmoduleUpdateOptions,
+};
+use scopetime::scope_time;
+use std::path::Path;
+
+///
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct CloneOptions {
+	/// branch to check out instead of the remote `HEAD`
+	pub branch: Option<String>,
+	/// only fetch this many commits of history (shallow clone)
+	pub depth: Option<u32>,
+	/// clone and check out all submodules, recursively
+	pub recurse_submodules: bool,
+}
+
+/// clones `url` into the directory `path`
+pub fn clone(
+	url: &str,
+	path: &Path,
+	options: &CloneOptions,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	scope_time!("clone");
+
+	let callbacks = Callbacks::new(progress_sender, basic_credential);
+
+	let mut builder = RepoBuilder::new();
+	builder.fetch_options(fetch_options(&callbacks, options.depth));
+
+	if let Some(branch) = &options.branch {
+		builder.branch(branch);
+	}
+
+	let repo = builder.clone(url, path);
+	if let Ok(config) = git2::Config::open_default() {
+		callbacks.report_credential(&config, repo.is_ok());
+	}
+	let repo = repo?;
+
+	if options.recurse_submodules {
+		update_submodules(&repo, &callbacks)?;
+	}
+
+	Ok(())
+}
+
+fn fetch_options<'a>(
+	callbacks: &Callbacks,
+	depth: Option<u32>,
+) -> FetchOptions<'a> {
+	let mut options = FetchOptions::new();
+	options.remote_callbacks(callbacks.callbacks());
+	options.proxy_options(proxy_auto());
+
+	if let Some(depth) = depth {
+		options.depth(i32::try_from(depth).unwrap_or(i32::MAX));
+	}
+
+	options
+}
+
+fn update_submodules(
+	repo: &Repository,
+	callbacks: &Callbacks,
+) -> Result<()> {
+	for mut submodule in repo.submodules()? {
+		let mut options = SubmoduleUpdateOptions::new();
+		options.fetch(fetch_options(callbacks, None));
+
+		submodule.update(true, Some(&mut options))?;
+
+		update_submodules(&submodule.open()?, callbacks)?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		create_branch, get_commits_info,
+		tests::{repo_init, write_commit_file},
+		utils::{get_head_repo, repo_work_dir},
+		RepoPath,
+	};
+	use tempfile::TempDir;
+
+	fn file_url(repo: &Repository) -> String {
+		format!(
+			"file://{}",
+			repo.workdir().unwrap().to_str().unwrap()
+		)
+	}
+
+	#[test]
+	fn test_clone() {
+		let (_remote_dir, remote) = repo_init().unwrap();
+		let c1 = write_commit_file(&remote, "a.txt", "a", "c1");
+
+		let target = TempDir::new().unwrap();
+		let path = target.path().join("clone");
+
+		clone(
+			&file_url(&remote),
+			&path,
+			&CloneOptions::default(),
+			None,
+			None,
+		)
+		.unwrap();
+
+		let cloned = Repository::open(&path).unwrap();
+		assert_eq!(get_head_repo(&cloned).unwrap(), c1);
+		assert!(path.join("a.txt").exists());
+	}
+
+	#[test]
+	fn test_clone_branch() {
+		let (_remote_dir, remote) = repo_init().unwrap();
+		let remote_path: &RepoPath =
+			&remote.workdir().unwrap().to_str().unwrap().into();
+
+		write_commit_file(&remote, "a.txt", "a", "c1");
+		create_branch(remote_path, "feature").unwrap();
+		let c2 = write_commit_file(&remote, "b.txt", "b", "c2");
+
+		let target = TempDir::new().unwrap();
+		let path = target.path().join("clone");
+
+		clone(
+			&file_url(&remote),
+			&path,
+			&CloneOptions {
+				branch: Some(String::from("feature")),
+				..CloneOptions::default()
+			},
+			None,
+			None,
+		)
+		.unwrap();
+
+		let cloned = Repository::open(&path).unwrap();
+		assert_eq!(get_head_repo(&cloned).unwrap(), c2);
+		assert_eq!(
+			cloned.head().unwrap().shorthand().unwrap(),
+			"feature"
+		);
+	}
+
+	/// `git daemon` serving a bare copy of `repo` from its own
+	/// directory, libgit2 does not fetch shallow from local repositories
+	#[cfg(unix)]
+	struct GitDaemon {
+		child: std::process::Child,
+		url: String,
+		_base: TempDir,
+	}
+
+	#[cfg(unix)]
+	impl GitDaemon {
+		/// `None` if `git daemon` can not be started
+		fn new(repo: &Repository) -> Option<Self> {
+			use std::{
+				net::{TcpListener, TcpStream},
+				process::{Command, Stdio},
+				thread,
+				time::Duration,
+			};
+
+			let base = TempDir::new().unwrap();
+			RepoBuilder::new()
+				.bare(true)
+				.clone(
+					&file_url(repo),
+					&base.path().join("remote.git"),
+				)
+				.unwrap();
+
+			// the free port can get taken before the daemon binds it,
+			// the daemon exits then and we retry with another one
+			for _ in 0..5 {
+				let port = TcpListener::bind("127.0.0.1:0")
+					.unwrap()
+					.local_addr()
+					.unwrap()
+					.port();
+
+				let mut child =
+					match Command::new("git")
+						.arg("daemon")
+						.arg("--export-all")
+						.arg("--listen=127.0.0.1")
+						.arg(format!("--port={port}"))
+						.arg(format!(
+							"--base-path={}",
+							base.path().display()
+						))
+						.stdout(Stdio::null())
+						.stderr(Stdio::null())
+						.spawn()
+					{
+						Ok(child) => child,
+						Err(e) => {
+							eprintln!("skipped, can not run git daemon: {e}");
+							return None;
+						}
+					};
+
+				for _ in 0..50 {
+					if TcpStream::connect(("127.0.0.1", port)).is_ok()
+					{
+						return Some(Self {
+							child,
+							url: format!(
+								"git://127.0.0.1:{port}/remote.git"
+							),
+							_base: base,
+						});
+					}
+					if child.try_wait().unwrap().is_some() {
+						break;
+					}
+					thread::sleep(Duration::from_millis(100));
+				}
+
+				let running = child.try_wait().unwrap().is_none();
+				if running {
+					child.kill().ok();
+					child.wait().ok();
+				}
+				assert!(
+					!running,
+					"git daemon not reachable on port {port}"
+				);
+			}
+
+			eprintln!("skipped, git daemon did not start");
+			None
+		}
+	}
+
+	#[cfg(unix)]
+	impl Drop for GitDaemon {
+		fn drop(&mut self) {
+			self.child.kill().ok();
+			self.child.wait().ok();
+		}
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_clone_depth() {
+		use crate::sync::{is_shallow, CommitId};
+
+		let (_remote_dir, remote) = repo_init().unwrap();
+		write_commit_file(&remote, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&remote, "a.txt", "b", "c2");
+
+		let Some(daemon) = GitDaemon::new(&remote) else {
+			return;
+		};
+
+		let target = TempDir::new().unwrap();
+		let path = target.path().join("clone");
+
+		clone(
+			&daemon.url,
+			&path,
+			&CloneOptions {
+				depth: Some(1),
+				..CloneOptions::default()
+			},
+			None,
+			None,
+		)
+		.unwrap();
+
+		let repo_path: &RepoPath = &path.to_str().unwrap().into();
+		assert!(is_shallow(repo_path).unwrap());
+
+		let cloned = Repository::open(&path).unwrap();
+		let mut walk = cloned.revwalk().unwrap();
+		walk.push_head().unwrap();
+		assert_eq!(
+			walk.map(|id| CommitId::new(id.unwrap()))
+				.collect::<Vec<_>>(),
+			vec![c2]
+		);
+	}
+
+	#[test]
+	fn test_clone_progress() {
+		let (_remote_dir, remote) = repo_init().unwrap();
+		write_commit_file(&remote, "a.txt", "a", "c1");
+
+		let target = TempDir::new().unwrap();
+		let (sender, receiver) = crossbeam_channel::unbounded();
+
+		clone(
+			&file_url(&remote),
+			&target.path().join("clone"),
+			&CloneOptions::default(),
+			None,
+			Some(sender),
+		)
+		.unwrap();
+
+		assert!(receiver.try_iter().any(|progress| matches!(
+			progress,
+			ProgressNotification::Transfer { .. }
+		)));
+	}
+
+	#[test]
+	fn test_clone_submodules() {
+		let (_sub_dir, sub) = repo_init().unwrap();
+		let sub_commit =
+			write_commit_file(&sub, "sub.txt", "sub", "sub");
+
+		let (_remote_dir, remote) = repo_init().unwrap();
+		let mut submodule = remote
+			.submodule(&file_url(&sub), Path::new("sub"), true)
+			.unwrap();
+		submodule
+			.clone(Some(&mut SubmoduleUpdateOptions::new()))
+			.unwrap();
+		submodule.add_finalize().unwrap();
+		let mut index = remote.index().unwrap();
+		let tree =
+			remote.find_tree(index.write_tree().unwrap()).unwrap();
+		let sig = remote.signature().unwrap();
+		let parent = remote.head().unwrap().peel_to_commit().unwrap();
+		remote
+			.commit(
+				Some("HEAD"),
+				&sig,
+				&sig,
+				"add submodule",
+				&tree,
+				&[&parent],
+			)
+			.unwrap();
+
+		let target = TempDir::new().unwrap();
+		let path = target.path().join("clone");
+
+		clone(
+			&file_url(&remote),
+			&path,
+			&CloneOptions {
+				recurse_submodules: true,
+				..CloneOptions::default()
+			},
+			None,
+			None,
+		)
+		.unwrap();
+
+		let sub_path: &RepoPath =
+			&path.join("sub").to_str().unwrap().into();
+		assert!(path.join("sub/sub.txt").exists());
+		assert!(!repo_work_dir(sub_path).unwrap().is_empty());
+		assert_eq!(
+			get_commits_info(sub_path, &[sub_commit], 10).unwrap()[0]
+				.message,
+			"sub"
+		);
+	}
+}
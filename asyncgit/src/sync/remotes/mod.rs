@@ -1,6 +1,7 @@
 //!
 
 mod callbacks;
+pub(crate) mod clone;
 pub(crate) mod push;
 pub(crate) mod tags;
 
@@ -9,17 +10,21 @@ use crate::{
 	sync::{
 		cred::BasicAuthCredential,
 		remotes::push::ProgressNotification, repository::repo, utils,
+		verify::TempFile,
 	},
 	ProgressPercent,
 };
 use crossbeam_channel::Sender;
 use git2::{
-	BranchType, FetchOptions, ProxyOptions, Remote, Repository,
+	AutotagOption, BranchType, FetchOptions, FetchPrune,
+	ProxyOptions, Remote, Repository,
 };
 use scopetime::scope_time;
+use std::process::{Command, Stdio};
 use utils::bytes2string;
 
 pub use callbacks::Callbacks;
+pub use clone::{clone, CloneOptions};
 pub use tags::tags_missing_remote;
 
 use super::RepoPath;
@@ -266,55 +271,273 @@ pub(crate) fn get_default_remote_in_repo(
 	Err(Error::NoDefaultRemoteFound)
 }
 
+/// which tags to download when fetching
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FetchTags {
+	/// follow `remote.<name>.tagOpt`
+	#[default]
+	Config,
+	/// tags pointing into the fetched history
+	Auto,
+	/// all tags of the remote
+	All,
+	/// no tags
+	None,
+}
+
+/// how to extend the history of a shallow clone
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FetchDeepen {
+	/// fetch this many more commits behind the shallow boundary
+	By(u32),
+	/// fetch the history newer than this date
+	Since(String),
+	/// fetch the complete history
+	Unshallow,
+}
+
+/// what and how to fetch
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FetchSettings {
+	/// remote to fetch from, all remotes if `None`
+	pub remote: Option<String>,
+	/// remove remote-tracking refs that are gone on the remote,
+	/// `None` follows `remote.<name>.prune`/`fetch.prune`
+	pub prune: Option<bool>,
+	/// also remove local tags that are gone on the remote,
+	/// `None` follows `remote.<name>.pruneTags`/`fetch.pruneTags`
+	pub prune_tags: Option<bool>,
+	///
+	pub tags: FetchTags,
+	/// refspec to fetch instead of the configured ones
+	pub refspec: Option<String>,
+	/// change the depth of a shallow clone
+	pub deepen: Option<FetchDeepen>,
+}
+
+/// `remote.<name>.<key>` falling back to `fetch.<key>`
+fn fetch_config_bool(
+	repo: &Repository,
+	remote: &str,
+	key: &str,
+) -> Result<bool> {
+	let config = repo.config()?;
+
+	Ok(config
+		.get_bool(&format!("remote.{remote}.{key}"))
+		.or_else(|_| config.get_bool(&format!("fetch.{key}")))
+		.unwrap_or_default())
+}
+
 ///
 fn fetch_from_remote(
 	repo_path: &RepoPath,
 	remote: &str,
+	settings: &FetchSettings,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
 ) -> Result<()> {
 	let repo = repo(repo_path)?;
 
+	let prune = match settings.prune {
+		Some(prune) => prune,
+		None => fetch_config_bool(&repo, remote, "prune")?,
+	};
+	let prune_tags = prune
+		&& match settings.prune_tags {
+			Some(prune_tags) => prune_tags,
+			None => fetch_config_bool(&repo, remote, "pruneTags")?,
+		};
+
+	if let Some(deepen) = &settings.deepen {
+		return fetch_deepen(
+			&repo,
+			remote,
+			settings,
+			deepen,
+			prune,
+			prune_tags,
+			basic_credential.as_ref(),
+		);
+	}
+
 	let mut remote = repo.find_remote(remote)?;
 
 	let mut options = FetchOptions::new();
 	let callbacks = Callbacks::new(progress_sender, basic_credential);
-	options.prune(git2::FetchPrune::On);
+	options.prune(if prune {
+		FetchPrune::On
+	} else {
+		FetchPrune::Off
+	});
 	options.proxy_options(proxy_auto());
-	options.download_tags(git2::AutotagOption::All);
+	options.download_tags(match settings.tags {
+		FetchTags::Config => AutotagOption::Unspecified,
+		FetchTags::Auto => AutotagOption::Auto,
+		FetchTags::All => AutotagOption::All,
+		FetchTags::None => AutotagOption::None,
+	});
 	options.remote_callbacks(callbacks.callbacks());
-	remote.fetch(&[] as &[&str], Some(&mut options), None)?;
-	// fetch tags (also removing remotely deleted ones)
-	remote.fetch(
-		&["refs/tags/*:refs/tags/*"],
-		Some(&mut options),
-		None,
-	)?;
+
+	let refspecs: Vec<&str> =
+		settings.refspec.iter().map(String::as_str).collect();
+	let res = remote
+		.fetch(&refspecs, Some(&mut options), None)
+		.and_then(|()| {
+			if prune_tags {
+				// fetch tags (also removing remotely deleted ones)
+				remote.fetch(
+					&["refs/tags/*:refs/tags/*"],
+					Some(&mut options),
+					None,
+				)
+			} else {
+				Ok(())
+			}
+		});
+	callbacks.report_credential_to_repo(&repo, res.is_ok());
+	res?;
 
 	Ok(())
 }
 
-/// updates/prunes all branches from all remotes
+/// answers the prompts of `git` with the basic credential, which is
+/// passed in the environment to keep it out of the file
+const ASKPASS_SCRIPT: &[u8] = br#"#!/bin/sh
+case "$1" in
+Username*) printf '%s\n' "$GITUI_ASKPASS_USERNAME" ;;
+*) printf '%s\n' "$GITUI_ASKPASS_PASSWORD" ;;
+esac
+"#;
+
+/// lets `git` ask for `cred` via `GIT_ASKPASS`,
+/// the returned script has to outlive the command
+fn set_askpass(
+	cmd: &mut Command,
+	cred: &BasicAuthCredential,
+) -> Result<TempFile> {
+	let askpass = TempFile::new(ASKPASS_SCRIPT)?;
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		std::fs::set_permissions(
+			askpass.path(),
+			std::fs::Permissions::from_mode(0o700),
+		)?;
+	}
+
+	cmd.env("GIT_ASKPASS", askpass.path())
+		.env(
+			"GITUI_ASKPASS_USERNAME",
+			cred.username.as_deref().unwrap_or_default(),
+		)
+		.env(
+			"GITUI_ASKPASS_PASSWORD",
+			cred.password.as_deref().unwrap_or_default(),
+		);
+
+	Ok(askpass)
+}
+
+/// libgit2 only supports an absolute depth (and none at all over
+/// the local transport), so deepening is left to `git fetch`
+fn fetch_deepen(
+	repo: &Repository,
+	remote: &str,
+	settings: &FetchSettings,
+	deepen: &FetchDeepen,
+	prune: bool,
+	prune_tags: bool,
+	basic_credential: Option<&BasicAuthCredential>,
+) -> Result<()> {
+	if *deepen == FetchDeepen::Unshallow && !repo.is_shallow() {
+		// an earlier remote already fetched everything
+		return Ok(());
+	}
+
+	let mut cmd = Command::new("git");
+	cmd.arg("--git-dir")
+		.arg(repo.path())
+		.arg("fetch")
+		.arg(remote)
+		// never prompt on the terminal the ui is drawn on
+		.env("GIT_TERMINAL_PROMPT", "0")
+		.stdin(Stdio::null());
+
+	// git runs the credential helpers itself (including storing or
+	// erasing what it got), only the credential entered in the ui
+	// has to be handed over
+	let _askpass = basic_credential
+		.filter(|cred| cred.is_complete())
+		.map(|cred| set_askpass(&mut cmd, cred))
+		.transpose()?;
+
+	match deepen {
+		FetchDeepen::By(depth) => {
+			cmd.arg(format!("--deepen={depth}"))
+		}
+		FetchDeepen::Since(date) => {
+			cmd.arg(format!("--shallow-since={date}"))
+		}
+		FetchDeepen::Unshallow => cmd.arg("--unshallow"),
+	};
+
+	if prune {
+		cmd.arg("--prune");
+	}
+	if prune_tags {
+		cmd.arg("--prune-tags");
+	}
+	match settings.tags {
+		FetchTags::Config | FetchTags::Auto => (),
+		FetchTags::All => {
+			cmd.arg("--tags");
+		}
+		FetchTags::None => {
+			cmd.arg("--no-tags");
+		}
+	}
+	if let Some(refspec) = &settings.refspec {
+		cmd.arg(refspec);
+	}
+
+	log::trace!("deepen command: {cmd:?}");
+
+	let output = cmd.output()?;
+
+	if !output.status.success() {
+		return Err(Error::Generic(format!(
+			"git fetch failed: {}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		)));
+	}
+
+	Ok(())
+}
+
+/// updates the remote-tracking refs of all remotes
+/// (or just the one picked in `settings`)
 pub fn fetch_all(
 	repo_path: &RepoPath,
+	settings: &FetchSettings,
 	basic_credential: &Option<BasicAuthCredential>,
 	progress_sender: &Option<Sender<ProgressPercent>>,
 ) -> Result<()> {
 	scope_time!("fetch_all");
 
-	let repo = repo(repo_path)?;
-	let remotes = repo
-		.remotes()?
-		.iter()
-		.flatten()
-		.map(String::from)
-		.collect::<Vec<_>>();
+	let remotes = if let Some(remote) = &settings.remote {
+		vec![remote.clone()]
+	} else {
+		get_remotes(repo_path)?
+	};
 	let remotes_count = remotes.len();
 
 	for (idx, remote) in remotes.into_iter().enumerate() {
 		fetch_from_remote(
 			repo_path,
 			&remote,
+			settings,
 			basic_credential.clone(),
 			None,
 		)?;
@@ -352,7 +575,9 @@ pub(crate) fn fetch(
 	options.remote_callbacks(callbacks.callbacks());
 	options.proxy_options(proxy_auto());
 
-	remote.fetch(&[branch], Some(&mut options), None)?;
+	let res = remote.fetch(&[branch], Some(&mut options), None);
+	callbacks.report_credential_to_repo(&repo, res.is_ok());
+	res?;
 
 	Ok(remote.stats().received_bytes())
 }
@@ -360,9 +585,13 @@ pub(crate) fn fetch(
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::sync::tests::{
-		debug_cmd_print, repo_clone, repo_init,
+	use crate::sync::{
+		is_shallow, shallow_boundary,
+		tests::{
+			debug_cmd_print, repo_clone, repo_init, write_commit_file,
+		},
 	};
+	use std::collections::HashSet;
 
 	#[test]
 	fn test_smoke() {
@@ -379,6 +608,200 @@ mod tests {
 		fetch(repo_path, "master", None, None).unwrap();
 	}
 
+	#[test]
+	fn test_fetch_keeps_local_tags() {
+		let (remote_dir, _remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let (repo_dir, repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		let head = repo.head().unwrap().target().unwrap();
+		repo.tag_lightweight(
+			"local",
+			&repo.find_object(head, None).unwrap(),
+			false,
+		)
+		.unwrap();
+
+		let settings = FetchSettings {
+			prune: Some(true),
+			tags: FetchTags::All,
+			..FetchSettings::default()
+		};
+		fetch_all(repo_path, &settings, &None, &None).unwrap();
+
+		assert!(repo.find_reference("refs/tags/local").is_ok());
+	}
+
+	#[test]
+	fn test_fetch_prune_config() {
+		let (remote_dir, remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let head = remote.head().unwrap().target().unwrap();
+		remote
+			.branch("gone", &remote.find_commit(head).unwrap(), false)
+			.unwrap();
+
+		let (repo_dir, repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+		assert!(repo
+			.find_reference("refs/remotes/origin/gone")
+			.is_ok());
+
+		remote
+			.find_branch("gone", BranchType::Local)
+			.unwrap()
+			.delete()
+			.unwrap();
+
+		fetch_all(repo_path, &FetchSettings::default(), &None, &None)
+			.unwrap();
+		assert!(repo
+			.find_reference("refs/remotes/origin/gone")
+			.is_ok());
+
+		repo.config()
+			.unwrap()
+			.set_bool("fetch.prune", true)
+			.unwrap();
+
+		fetch_all(repo_path, &FetchSettings::default(), &None, &None)
+			.unwrap();
+		assert!(repo
+			.find_reference("refs/remotes/origin/gone")
+			.is_err());
+	}
+
+	#[test]
+	fn test_fetch_single_remote_refspec() {
+		let (remote_dir, remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let (repo_dir, repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		debug_cmd_print(
+			repo_path,
+			&format!("git remote add second {remote_path}")[..],
+		);
+
+		let head = remote.head().unwrap().target().unwrap();
+		remote
+			.reference("refs/pull/1/head", head, false, "")
+			.unwrap();
+
+		let settings = FetchSettings {
+			remote: Some("origin".into()),
+			refspec: Some(
+				"refs/pull/1/head:refs/remotes/origin/pr/1".into(),
+			),
+			..FetchSettings::default()
+		};
+		fetch_all(repo_path, &settings, &None, &None).unwrap();
+
+		assert_eq!(
+			repo.refname_to_id("refs/remotes/origin/pr/1").unwrap(),
+			head
+		);
+		assert!(repo
+			.find_reference("refs/remotes/second/master")
+			.is_err());
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_deepen_askpass() {
+		let mut cmd = Command::new("sh");
+		cmd.arg("-c").arg(
+			r#""$GIT_ASKPASS" 'Username for x'; "$GIT_ASKPASS" 'Password for x'"#,
+		);
+
+		let _askpass = set_askpass(
+			&mut cmd,
+			&BasicAuthCredential::new(
+				Some(String::from("user")),
+				Some(String::from("secret")),
+			),
+		)
+		.unwrap();
+
+		let output = cmd.output().unwrap();
+
+		assert_eq!(
+			String::from_utf8_lossy(&output.stdout),
+			"user\nsecret\n"
+		);
+	}
+
+	#[test]
+	fn test_fetch_deepen() {
+		let (remote_dir, remote) = repo_init().unwrap();
+		let c1 = write_commit_file(&remote, "a.txt", "1", "c1");
+		let c2 = write_commit_file(&remote, "a.txt", "2", "c2");
+		let c3 = write_commit_file(&remote, "a.txt", "3", "c3");
+
+		let target = tempfile::TempDir::new().unwrap();
+		let target_path: &RepoPath =
+			&target.path().to_str().unwrap().into();
+		debug_cmd_print(
+			target_path,
+			&format!(
+				"git clone --depth 1 file://{} clone",
+				remote_dir.path().to_str().unwrap()
+			),
+		);
+		let repo_path: &RepoPath =
+			&target.path().join("clone").to_str().unwrap().into();
+
+		assert_eq!(
+			shallow_boundary(repo_path).unwrap(),
+			HashSet::from([c3])
+		);
+
+		let deepen = |deepen| FetchSettings {
+			deepen: Some(deepen),
+			..FetchSettings::default()
+		};
+
+		fetch_all(
+			repo_path,
+			&deepen(FetchDeepen::By(1)),
+			&None,
+			&None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			shallow_boundary(repo_path).unwrap(),
+			HashSet::from([c2])
+		);
+
+		fetch_all(
+			repo_path,
+			&deepen(FetchDeepen::Unshallow),
+			&None,
+			&None,
+		)
+		.unwrap();
+
+		assert!(!is_shallow(repo_path).unwrap());
+		assert!(repo(repo_path)
+			.unwrap()
+			.find_commit(c1.into())
+			.is_ok());
+
+		// nothing left to unshallow
+		fetch_all(
+			repo_path,
+			&deepen(FetchDeepen::Unshallow),
+			&None,
+			&None,
+		)
+		.unwrap();
+	}
+
 	#[test]
 	fn test_default_remote() {
 		let (remote_dir, _remote) = repo_init().unwrap();
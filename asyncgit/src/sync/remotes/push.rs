@@ -2,22 +2,26 @@ use crate::{
 	error::{Error, Result},
 	progress::ProgressPercent,
 	sync::{
-		branch::branch_set_upstream_after_push,
+		branch::{
+			branch_set_upstream_after_push, set_branch_upstream_repo,
+		},
 		config::{
 			push_default_strategy_config_repo,
 			PushDefaultStrategyConfig,
 		},
 		cred::BasicAuthCredential,
 		get_branch_upstream_merge,
+		hooks::{hooks_pre_push_check, PrePushRef},
 		remotes::{proxy_auto, Callbacks},
 		repository::repo,
 		CommitId, RepoPath,
 	},
 };
 use crossbeam_channel::Sender;
-use git2::{PackBuilderStage, PushOptions};
+use git2::{
+	Direction, Oid, PackBuilderStage, PushOptions, Remote, Repository,
+};
 use scopetime::scope_time;
-use std::fmt::Write as _;
 
 ///
 pub trait AsyncProgress: Clone + Send + Sync {
@@ -107,6 +111,26 @@ pub enum PushType {
 	Tag,
 }
 
+/// how to deal with a remote ref that is not an ancestor of what we push
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PushForce {
+	/// reject non fast-forward updates
+	#[default]
+	None,
+	/// overwrite the remote ref only if it still points to where our
+	/// remote-tracking ref says it does
+	WithLease,
+	/// overwrite the remote ref unconditionally
+	Force,
+}
+
+impl PushForce {
+	///
+	pub const fn is_force(self) -> bool {
+		!matches!(self, Self::None)
+	}
+}
+
 #[cfg(test)]
 pub fn push_branch(
 	repo_path: &RepoPath,
@@ -121,42 +145,46 @@ pub fn push_branch(
 		repo_path,
 		remote,
 		branch,
+		None,
 		PushType::Branch,
-		force,
+		if force {
+			PushForce::Force
+		} else {
+			PushForce::None
+		},
 		delete,
+		false,
 		basic_credential,
 		progress_sender,
 	)
 }
 
+/// pushes `branch` to `dst_branch` (same name if `None`) on `remote`,
+/// `set_upstream` makes the pushed ref the upstream of `branch`
 //TODO: cleanup
 #[allow(clippy::too_many_arguments)]
 pub fn push_raw(
 	repo_path: &RepoPath,
 	remote: &str,
 	branch: &str,
+	dst_branch: Option<&str>,
 	ref_type: PushType,
-	force: bool,
+	force: PushForce,
 	delete: bool,
+	set_upstream: bool,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
 ) -> Result<()> {
 	scope_time!("push");
 
 	let repo = repo(repo_path)?;
-	let mut remote = repo.find_remote(remote)?;
+	let remote_name = remote;
+	let mut remote = repo.find_remote(remote_name)?;
 
 	let push_default_strategy =
 		push_default_strategy_config_repo(&repo)?;
 
-	let mut options = PushOptions::new();
-	options.proxy_options(proxy_auto());
-
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
-	options.remote_callbacks(callbacks.callbacks());
-	options.packbuilder_parallelism(0);
-
-	let branch_modifier = match (force, delete) {
+	let branch_modifier = match (force.is_force(), delete) {
 		(true, true) => "+:",
 		(false, true) => ":",
 		(true, false) => "+",
@@ -167,10 +195,14 @@ pub fn push_raw(
 		PushType::Tag => "tags",
 	};
 
-	let mut push_ref =
-		format!("{branch_modifier}refs/{git_ref_type}/{branch}");
+	let src_ref = format!("refs/{git_ref_type}/{branch}");
+	let mut dst_ref = dst_branch.map_or_else(
+		|| src_ref.clone(),
+		|dst| format!("refs/{git_ref_type}/{dst}"),
+	);
 
-	if !delete
+	if dst_branch.is_none()
+		&& !delete
 		&& ref_type == PushType::Branch
 		&& push_default_strategy
 			== PushDefaultStrategyConfig::Upstream
@@ -178,12 +210,55 @@ pub fn push_raw(
 		if let Ok(Some(branch_upstream_merge)) =
 			get_branch_upstream_merge(repo_path, branch)
 		{
-			let _ = write!(push_ref, ":{branch_upstream_merge}");
+			dst_ref = branch_upstream_merge;
 		}
 	}
 
+	let push_ref = if delete {
+		format!("{branch_modifier}{dst_ref}")
+	} else if dst_ref == src_ref {
+		format!("{branch_modifier}{src_ref}")
+	} else {
+		format!("{branch_modifier}{src_ref}:{dst_ref}")
+	};
+
+	let remote_id = remote_tracking_id(&repo, &remote, &dst_ref)?;
+
+	hooks_pre_push_check(
+		&repo,
+		remote_name,
+		&[if delete {
+			PrePushRef {
+				local_ref: String::from("(delete)"),
+				local_oid: Oid::zero(),
+				remote_ref: dst_ref.clone(),
+				remote_oid: remote_id,
+			}
+		} else {
+			PrePushRef {
+				local_oid: repo.refname_to_id(&src_ref)?,
+				local_ref: src_ref,
+				remote_ref: dst_ref.clone(),
+				remote_oid: remote_id,
+			}
+		}],
+	)?;
+
+	let mut callbacks =
+		Callbacks::new(progress_sender, basic_credential);
+	if force == PushForce::WithLease {
+		callbacks = callbacks.with_lease(dst_ref.clone(), remote_id);
+	}
+
+	let mut options = PushOptions::new();
+	options.proxy_options(proxy_auto());
+	options.remote_callbacks(callbacks.callbacks());
+	options.packbuilder_parallelism(0);
+
 	log::debug!("push to: {push_ref}");
-	remote.push(&[push_ref], Some(&mut options))?;
+	let res = remote.push(&[push_ref], Some(&mut options));
+	callbacks.report_credential_to_repo(&repo, res.is_ok());
+	res?;
 
 	if let Some((reference, msg)) =
 		callbacks.get_stats()?.push_rejected_msg
@@ -193,13 +268,48 @@ pub fn push_raw(
 		)));
 	}
 
-	if !delete {
+	if set_upstream && !delete && ref_type == PushType::Branch {
+		set_branch_upstream_repo(
+			&repo,
+			branch,
+			remote_name,
+			&dst_ref,
+		)?;
+	} else if !delete && dst_branch.is_none() {
 		branch_set_upstream_after_push(&repo, branch)?;
 	}
 
 	Ok(())
 }
 
+/// where our remote-tracking ref of `dst_ref` points to,
+/// zero if we do not know the ref on the remote
+fn remote_tracking_id(
+	repo: &Repository,
+	remote: &Remote,
+	dst_ref: &str,
+) -> Result<Oid> {
+	for refspec in remote.refspecs() {
+		if refspec.direction() != Direction::Fetch
+			|| !refspec.src_matches(dst_ref)
+		{
+			continue;
+		}
+
+		let tracking_ref = refspec.transform(dst_ref)?;
+		let tracking_ref =
+			tracking_ref.as_str().ok_or_else(|| {
+				Error::Generic("invalid remote-tracking ref".into())
+			})?;
+
+		if let Ok(id) = repo.refname_to_id(tracking_ref) {
+			return Ok(id);
+		}
+	}
+
+	Ok(Oid::zero())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -440,6 +550,160 @@ mod tests {
 		assert_eq!(new_upstream_parent, repo_2_parent,);
 	}
 
+	#[test]
+	fn test_force_push_with_lease() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+
+		let (repo_dir, repo) = repo_clone(upstream_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let (other_dir, other) = repo_clone(upstream_path).unwrap();
+		let other_path: &RepoPath =
+			&other_dir.path().to_str().unwrap().into();
+
+		// someone else pushes after we last fetched
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let c3 = write_commit_file(&other, "a.txt", "c", "c3");
+
+		let res = push_raw(
+			other_path,
+			"origin",
+			"master",
+			None,
+			PushType::Branch,
+			PushForce::WithLease,
+			false,
+			false,
+			None,
+			None,
+		);
+		assert!(
+			matches!(res, Err(e) if e.to_string().contains("stale info"))
+		);
+		assert!(get_commit_ids(&upstream, 1).contains(&c2));
+
+		// once we know about c2 the lease allows overwriting it
+		other
+			.find_remote("origin")
+			.unwrap()
+			.fetch(&["master"], None, None)
+			.unwrap();
+
+		push_raw(
+			other_path,
+			"origin",
+			"master",
+			None,
+			PushType::Branch,
+			PushForce::WithLease,
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+		assert!(get_commit_ids(&upstream, 1).contains(&c3));
+	}
+
+	#[test]
+	fn test_force_push_with_lease_new_branch() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+
+		let (repo_dir, repo) = repo_clone(upstream_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		push_raw(
+			repo_path,
+			"origin",
+			"master",
+			None,
+			PushType::Branch,
+			PushForce::WithLease,
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+		assert!(get_commit_ids(&upstream, 1).contains(&c1));
+	}
+
+	#[test]
+	fn test_push_to_other_name() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let (repo_dir, repo) = repo_init().unwrap();
+		repo.remote("origin", upstream_dir.path().to_str().unwrap())
+			.unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		push_raw(
+			repo_path,
+			"origin",
+			"master",
+			Some("review/master"),
+			PushType::Branch,
+			PushForce::None,
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			upstream
+				.refname_to_id("refs/heads/review/master")
+				.unwrap(),
+			c1.into()
+		);
+		assert!(upstream
+			.find_reference("refs/heads/master")
+			.is_err());
+		assert_eq!(
+			get_branch_upstream_merge(repo_path, "master").unwrap(),
+			None
+		);
+
+		push_raw(
+			repo_path,
+			"origin",
+			"master",
+			Some("review/master"),
+			PushType::Branch,
+			PushForce::None,
+			false,
+			true,
+			None,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_branch_upstream_merge(repo_path, "master").unwrap(),
+			Some(String::from("refs/heads/review/master"))
+		);
+	}
+
 	#[test]
 	fn test_delete_remote_branch() {
 		// This test mimics the scenario of a user creating a branch, push it, and then remove it on the remote
@@ -519,4 +783,39 @@ mod tests {
 			.map(|(i, _)| i.name().unwrap().unwrap().to_string())
 			.any(|i| &i == "test_branch"));
 	}
+
+	#[test]
+	fn test_pre_push_hook_rejects() {
+		let (upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let (repo_dir, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+		let url = upstream_dir.path().to_str().unwrap();
+
+		repo.remote("origin", url).unwrap();
+		let commit = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_PRE_PUSH,
+			b"#!/bin/sh\necho \"$1 $2\"\ncat\nexit 1\n",
+		);
+
+		let res = push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		);
+
+		assert_eq!(
+			res.map_err(|e| e.to_string()),
+			Err(format!(
+				"`pre-push hook failed:\norigin {url}\nrefs/heads/master {commit} refs/heads/master {}\n`",
+				Oid::zero()
+			))
+		);
+		assert!(upstream_dir
+			.path()
+			.join("refs/heads/master")
+			.metadata()
+			.is_err());
+	}
 }
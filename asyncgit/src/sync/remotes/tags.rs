@@ -6,6 +6,7 @@ use crate::{
 	progress::ProgressPercent,
 	sync::{
 		cred::BasicAuthCredential,
+		hooks::{hooks_pre_push_check, PrePushRef},
 		remotes::{proxy_auto, Callbacks},
 		repository::repo,
 		RepoPath,
@@ -62,7 +63,9 @@ fn remote_tag_refs(
 		Direction::Fetch,
 		Some(callbacks.callbacks()),
 		Some(proxy_auto()),
-	)?;
+	);
+	callbacks.report_credential_to_repo(&repo, conn.is_ok());
+	let conn = conn?;
 
 	let remote_heads = conn.list()?;
 	let remote_tags = remote_heads
@@ -121,6 +124,23 @@ pub fn push_tags(
 	)?;
 
 	let repo = repo(repo_path)?;
+
+	if !tags_missing.is_empty() {
+		let updates = tags_missing
+			.iter()
+			.map(|tag| {
+				Ok(PrePushRef {
+					local_ref: tag.clone(),
+					local_oid: repo.refname_to_id(tag)?,
+					remote_ref: tag.clone(),
+					remote_oid: git2::Oid::zero(),
+				})
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		hooks_pre_push_check(&repo, remote, &updates)?;
+	}
+
 	let mut remote = repo.find_remote(remote)?;
 
 	let total = tags_missing.len();
@@ -136,7 +156,9 @@ pub fn push_tags(
 		options.remote_callbacks(callbacks.callbacks());
 		options.packbuilder_parallelism(0);
 		options.proxy_options(proxy_auto());
-		remote.push(&[tag.as_str()], Some(&mut options))?;
+		let res = remote.push(&[tag.as_str()], Some(&mut options));
+		callbacks.report_credential_to_repo(&repo, res.is_ok());
+		res?;
 
 		progress_sender.as_ref().map(|sender| {
 			sender.send(PushTagsProgress::Push {
@@ -161,7 +183,8 @@ mod tests {
 			self, delete_tag,
 			remotes::{
 				fetch, fetch_all,
-				push::{push_branch, push_raw},
+				push::{push_branch, push_raw, PushForce},
+				FetchSettings,
 			},
 			tests::{repo_clone, repo_init_bare},
 		},
@@ -365,7 +388,13 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(
+			clone2_dir,
+			&FetchSettings::default(),
+			&None,
+			&None,
+		)
+		.unwrap();
 
 		let tags2 = sync::get_tags(clone2_dir).unwrap();
 
@@ -399,7 +428,13 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		let settings = FetchSettings {
+			prune: Some(true),
+			prune_tags: Some(true),
+			..FetchSettings::default()
+		};
+
+		fetch_all(clone2_dir, &settings, &None, &None).unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 1);
 
 		// delete on clone 1
@@ -410,9 +445,11 @@ mod tests {
 			clone1_dir,
 			"origin",
 			"tag1",
+			None,
 			PushType::Tag,
-			false,
+			PushForce::None,
 			true,
+			false,
 			None,
 			None,
 		)
@@ -422,7 +459,7 @@ mod tests {
 
 		// clone 2
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &settings, &None, &None).unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 0);
 	}
 }
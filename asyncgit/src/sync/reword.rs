@@ -2,6 +2,8 @@ use git2::{Oid, RebaseOptions, Repository};
 
 use super::{
 	commit::signature_allow_undefined_name,
+	hooks::{hooks_post_rewrite_repo, HookResult, RewriteCommand},
+	rebase::finish_rebase,
 	repo,
 	utils::{bytes2string, get_head_refname, get_head_repo},
 	CommitId, RepoPath,
@@ -9,11 +11,13 @@ use super::{
 use crate::error::{Error, Result};
 
 /// This is the same as reword, but will abort and fix the repo if something goes wrong
+///
+/// returns the reworded commit and the result of the post-rewrite hook
 pub fn reword(
 	repo_path: &RepoPath,
 	commit: CommitId,
 	message: &str,
-) -> Result<CommitId> {
+) -> Result<(CommitId, HookResult)> {
 	let repo = repo(repo_path)?;
 	let config = repo.config()?;
 
@@ -32,7 +36,14 @@ pub fn reword(
 				.len() == 0
 			{
 				undo_last_commit(repo_path)?;
-				return super::commit(repo_path, message);
+				let id = super::commit(repo_path, message)?;
+				let hook = hooks_post_rewrite_repo(
+					&repo,
+					RewriteCommand::Amend,
+					&[(head, id)],
+				)?;
+
+				return Ok((id, hook));
 			}
 
 			return Err(Error::SignRewordLastCommitStaged);
@@ -44,7 +55,7 @@ pub fn reword(
 	let cur_branch_ref = get_head_refname(&repo)?;
 
 	match reword_internal(&repo, commit.get_oid(), message) {
-		Ok(id) => Ok(id.into()),
+		Ok((id, hook)) => Ok((id.into(), hook)),
 		// Something went wrong, checkout the previous branch then error
 		Err(e) => {
 			if let Ok(mut rebase) = repo.open_rebase(None) {
@@ -84,7 +95,7 @@ fn reword_internal(
 	repo: &Repository,
 	commit: Oid,
 	message: &str,
-) -> Result<Oid> {
+) -> Result<(Oid, HookResult)> {
 	let sig = signature_allow_undefined_name(repo)?;
 
 	let parent_commit_oid = repo
@@ -129,7 +140,7 @@ fn reword_internal(
 		while rebase.next().is_some() {
 			target = rebase.commit(None, &sig, None)?;
 		}
-		rebase.finish(None)?;
+		let hook = finish_rebase(repo, &mut rebase, None)?;
 
 		// Now override the previous branch
 		repo.branch(
@@ -141,7 +152,7 @@ fn reword_internal(
 		// Reset the head back to the branch then checkout head
 		repo.set_head(&cur_branch_ref)?;
 		repo.checkout_head(None)?;
-		return Ok(reworded_commit);
+		return Ok((reworded_commit, hook));
 	}
 	// Repo is not on a branch, possibly detached head
 	Err(Error::NoBranch)
@@ -176,7 +187,7 @@ mod tests {
 
 		assert_eq!(message, "commit2");
 
-		let reworded =
+		let (reworded, _) =
 			reword(repo_path, oid2, "NewCommitMessage").unwrap();
 
 		// Need to get the branch again as top oid has changed
@@ -192,4 +203,54 @@ mod tests {
 			get_commit_info(repo_path, &reworded).unwrap().message
 		);
 	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_reword_signed_runs_post_rewrite() {
+		use std::{fs, os::unix::fs::PermissionsExt};
+
+		let (td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		let head = write_commit_file(&repo, "foo", "ab", "commit2");
+
+		let gpg = td.path().join("fake-gpg");
+		fs::write(
+			&gpg,
+			"#!/bin/sh
+cat > /dev/null
+printf '[GNUPG:] BEGIN_SIGNING\\n[GNUPG:] SIG_CREATED \\n' >&2
+printf -- '-----BEGIN PGP SIGNATURE-----\\nsig\\n-----END PGP SIGNATURE-----\\n'",
+		)
+		.unwrap();
+		fs::set_permissions(&gpg, fs::Permissions::from_mode(0o755))
+			.unwrap();
+
+		{
+			let mut config = repo.config().unwrap();
+			config.set_bool("commit.gpgsign", true).unwrap();
+			config
+				.set_str("gpg.program", gpg.to_str().unwrap())
+				.unwrap();
+		}
+
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_POST_REWRITE,
+			b"#!/bin/sh\necho \"$1\" > .git/rewritten-log\ncat >> .git/rewritten-log\n",
+		);
+
+		let (reworded, hook) =
+			reword(repo_path, head, "NewCommitMessage").unwrap();
+
+		assert_eq!(hook, HookResult::Ok);
+		assert_eq!(
+			fs::read_to_string(repo.path().join("rewritten-log"))
+				.unwrap(),
+			format!("amend\n{head} {reworded}\n")
+		);
+	}
 }
@@ -0,0 +1,76 @@
+//! shallow clones
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use scopetime::scope_time;
+use std::{collections::HashSet, fs, io};
+
+/// whether the repository is a shallow clone
+pub fn is_shallow(repo_path: &RepoPath) -> Result<bool> {
+	Ok(repo(repo_path)?.is_shallow())
+}
+
+/// the commits listed in `.git/shallow`: their parents are not
+/// part of the local history, walking the log stops there
+pub fn shallow_boundary(
+	repo_path: &RepoPath,
+) -> Result<HashSet<CommitId>> {
+	scope_time!("shallow_boundary");
+
+	let repo = repo(repo_path)?;
+
+	let content =
+		match fs::read_to_string(repo.path().join("shallow")) {
+			Ok(content) => content,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => {
+				return Ok(HashSet::new());
+			}
+			Err(e) => return Err(e.into()),
+		};
+
+	Ok(content
+		.lines()
+		.filter_map(|line| {
+			CommitId::from_str_unchecked(line.trim()).ok()
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_not_shallow() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		assert!(!is_shallow(repo_path).unwrap());
+		assert!(shallow_boundary(repo_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_shallow_boundary() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+
+		fs::write(repo.path().join("shallow"), format!("{c2}\n"))
+			.unwrap();
+
+		assert!(is_shallow(repo_path).unwrap());
+		assert_eq!(
+			shallow_boundary(repo_path).unwrap(),
+			HashSet::from([c2])
+		);
+	}
+}
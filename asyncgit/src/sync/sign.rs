@@ -1,7 +1,7 @@
 //! Sign commit data.
 
-use ssh_key::{HashAlg, LineEnding, PrivateKey};
-use std::path::PathBuf;
+use super::verify::TempFile;
+use std::process::{Command, Stdio};
 
 /// Error type for [`SignBuilder`], used to create [`Sign`]'s
 #[derive(thiserror::Error, Debug)]
@@ -21,11 +21,6 @@ pub enum SignBuilderError {
 	/// No signing signature could be built from the configuration data present
 	#[error("Failed to build signing signature: {0}")]
 	Signature(String),
-
-	/// Failure on unimplemented signing methods
-	/// to be removed once all methods have been implemented
-	#[error("Select signing method '{0}' has not been implemented")]
-	MethodNotImplemented(String),
 }
 
 /// Error type for [`Sign`], used to sign data
@@ -124,67 +119,145 @@ impl SignBuilder {
 					.or_else(|_| config.get_string("gpg.program"))
 					.unwrap_or_else(|_| "gpg".to_string());
 
-				// Optional signing key.
-				// If 'user.signingKey' is not set, we'll use 'user.name' and 'user.email'
-				// to build a default signature in the format 'name <email>'.
-				// https://git-scm.com/docs/git-config#Documentation/git-config.txt-usersigningKey
-				let signing_key = config
-					.get_string("user.signingKey")
-					.or_else(
-						|_| -> Result<String, SignBuilderError> {
-							Ok(crate::sync::commit::signature_allow_undefined_name(repo)
-                                .map_err(|err| {
-                                    SignBuilderError::Signature(
-                                        err.to_string(),
-                                    )
-                                })?
-                                .to_string())
-						},
-					)
-					.map_err(|err| {
-						SignBuilderError::GPGSigningKey(
-							err.to_string(),
-						)
-					})?;
+				let signing_key = Self::signing_key(repo, config)?;
 
 				Ok(Box::new(GPGSign {
 					program,
 					signing_key,
 				}))
 			}
-			"x509" => Err(SignBuilderError::MethodNotImplemented(
-				String::from("x509"),
-			)),
+			"x509" => {
+				// Like git, default to "gpgsm", which shares its
+				// interface with gpg ("smimesign" is a drop-in replacement)
+				// https://git-scm.com/docs/git-config#Documentation/git-config.txt-gpgltformatgtprogram
+				let program = config
+					.get_string("gpg.x509.program")
+					.unwrap_or_else(|_| "gpgsm".to_string());
+
+				let signing_key = Self::signing_key(repo, config)?;
+
+				Ok(Box::new(X509Sign {
+					program,
+					signing_key,
+				}))
+			}
 			"ssh" => {
-				let ssh_signer = config
-					.get_string("user.signingKey")
-					.ok()
-					.and_then(|key_path| {
-						key_path.strip_prefix('~').map_or_else(
-							|| Some(PathBuf::from(&key_path)),
-							|ssh_key_path| {
-								dirs::home_dir().map(|home| {
-									home.join(
-										ssh_key_path
-											.strip_prefix('/')
-											.unwrap_or(ssh_key_path),
-									)
-								})
-							},
-						)
-					})
-					.ok_or_else(|| {
-						SignBuilderError::SSHSigningKey(String::from(
-							"ssh key setting absent",
-						))
-					})
-					.and_then(SSHSign::new)?;
-				let signer: Box<dyn Sign> = Box::new(ssh_signer);
-				Ok(signer)
+				// https://git-scm.com/docs/git-config#Documentation/git-config.txt-gpgltformatgtprogram
+				let program = config
+					.get_string("gpg.ssh.program")
+					.unwrap_or_else(|_| "ssh-keygen".to_string());
+
+				let signing_key = Self::ssh_signing_key(config)?;
+
+				Ok(Box::new(SSHSign {
+					program,
+					signing_key,
+				}))
 			}
 			_ => Err(SignBuilderError::InvalidFormat(format)),
 		}
 	}
+
+	/// Public key used for ssh signing, either a path or a literal
+	/// key (`key::ssh-ed25519 AAAA...`). If 'user.signingKey' is not
+	/// set, the first line printed by 'gpg.ssh.defaultKeyCommand' is used.
+	/// <https://git-scm.com/docs/git-config#Documentation/git-config.txt-gpgsshdefaultKeyCommand>
+	fn ssh_signing_key(
+		config: &git2::Config,
+	) -> Result<String, SignBuilderError> {
+		if let Ok(key) = config.get_string("user.signingKey") {
+			if is_literal_ssh_key(&key) {
+				return Ok(key);
+			}
+
+			return key.strip_prefix('~').map_or_else(
+				|| Ok(key.clone()),
+				|key_path| {
+					dirs::home_dir()
+						.map(|home| {
+							home.join(
+								key_path
+									.strip_prefix('/')
+									.unwrap_or(key_path),
+							)
+							.to_string_lossy()
+							.to_string()
+						})
+						.ok_or_else(|| {
+							SignBuilderError::SSHSigningKey(
+								String::from("home dir not found"),
+							)
+						})
+				},
+			);
+		}
+
+		let command = config
+			.get_string("gpg.ssh.defaultKeyCommand")
+			.map_err(|_| {
+				SignBuilderError::SSHSigningKey(String::from(
+					"either 'user.signingKey' or 'gpg.ssh.defaultKeyCommand' needs to be configured",
+				))
+			})?;
+
+		let mut args = command.split_whitespace();
+		let program = args.next().ok_or_else(|| {
+			SignBuilderError::SSHSigningKey(String::from(
+				"'gpg.ssh.defaultKeyCommand' is empty",
+			))
+		})?;
+
+		let output = Command::new(program)
+			.args(args)
+			.output()
+			.map_err(|e| {
+				SignBuilderError::SSHSigningKey(format!(
+					"failed to run '{command}': {e}"
+				))
+			})?;
+
+		if !output.status.success() {
+			return Err(SignBuilderError::SSHSigningKey(format!(
+				"'{command}' exited non-zero: {}",
+				String::from_utf8_lossy(&output.stderr)
+			)));
+		}
+
+		String::from_utf8_lossy(&output.stdout)
+			.lines()
+			.next()
+			.filter(|key| is_literal_ssh_key(key))
+			.map(ToString::to_string)
+			.ok_or_else(|| {
+				SignBuilderError::SSHSigningKey(format!(
+					"'{command}' did not print a public key"
+				))
+			})
+	}
+
+	/// Optional signing key.
+	/// If 'user.signingKey' is not set, we'll use 'user.name' and 'user.email'
+	/// to build a default signature in the format 'name <email>'.
+	/// <https://git-scm.com/docs/git-config#Documentation/git-config.txt-usersigningKey>
+	fn signing_key(
+		repo: &git2::Repository,
+		config: &git2::Config,
+	) -> Result<String, SignBuilderError> {
+		config
+			.get_string("user.signingKey")
+			.or_else(|_| -> Result<String, SignBuilderError> {
+				Ok(crate::sync::commit::signature_allow_undefined_name(
+					repo,
+				)
+				.map_err(|err| {
+					SignBuilderError::Signature(err.to_string())
+				})?
+				.to_string())
+			})
+			.map_err(|err| {
+				SignBuilderError::GPGSigningKey(err.to_string())
+			})
+	}
 }
 
 /// Sign commit data using `OpenPGP`
@@ -208,56 +281,42 @@ impl Sign for GPGSign {
 		&self,
 		commit: &[u8],
 	) -> Result<(String, Option<String>), SignError> {
-		use std::io::Write;
-		use std::process::{Command, Stdio};
-
-		let mut cmd = Command::new(&self.program);
-		cmd.stdin(Stdio::piped())
-			.stdout(Stdio::piped())
-			.stderr(Stdio::piped())
-			.arg("--status-fd=2")
-			.arg("-bsau")
-			.arg(&self.signing_key);
-
-		log::trace!("signing command: {cmd:?}");
-
-		let mut child = cmd
-			.spawn()
-			.map_err(|e| SignError::Spawn(e.to_string()))?;
-
-		let mut stdin = child.stdin.take().ok_or(SignError::Stdin)?;
-
-		stdin
-			.write_all(commit)
-			.map_err(|e| SignError::WriteBuffer(e.to_string()))?;
-		drop(stdin); // close stdin to not block indefinitely
+		sign_gpg_interface(&self.program, &self.signing_key, commit)
+	}
 
-		let output = child
-			.wait_with_output()
-			.map_err(|e| SignError::Output(e.to_string()))?;
+	#[cfg(test)]
+	fn program(&self) -> &String {
+		&self.program
+	}
 
-		if !output.status.success() {
-			return Err(SignError::Shellout(format!(
-				"failed to sign data, program '{}' exited non-zero: {}",
-				&self.program,
-				std::str::from_utf8(&output.stderr)
-					.unwrap_or("[error could not be read from stderr]")
-			)));
-		}
+	#[cfg(test)]
+	fn signing_key(&self) -> &String {
+		&self.signing_key
+	}
+}
 
-		let stderr = std::str::from_utf8(&output.stderr)
-			.map_err(|e| SignError::Shellout(e.to_string()))?;
+/// Sign commit data using `X.509` (S/MIME) certificates
+pub struct X509Sign {
+	program: String,
+	signing_key: String,
+}
 
-		if !stderr.contains("\n[GNUPG:] SIG_CREATED ") {
-			return Err(SignError::Shellout(
-				format!("failed to sign data, program '{}' failed, SIG_CREATED not seen in stderr", &self.program),
-			));
+impl X509Sign {
+	/// Create new [`X509Sign`] using given program and signing key.
+	pub fn new(program: &str, signing_key: &str) -> Self {
+		Self {
+			program: program.to_string(),
+			signing_key: signing_key.to_string(),
 		}
+	}
+}
 
-		let signed_commit = std::str::from_utf8(&output.stdout)
-			.map_err(|e| SignError::Shellout(e.to_string()))?;
-
-		Ok((signed_commit.to_string(), Some("gpgsig".to_string())))
+impl Sign for X509Sign {
+	fn sign(
+		&self,
+		commit: &[u8],
+	) -> Result<(String, Option<String>), SignError> {
+		sign_gpg_interface(&self.program, &self.signing_key, commit)
 	}
 
 	#[cfg(test)]
@@ -271,43 +330,80 @@ impl Sign for GPGSign {
 	}
 }
 
-/// Sign commit data using `SSHDiskKeySign`
+/// gpg, gpgsm and smimesign share the same command line interface
+fn sign_gpg_interface(
+	program: &str,
+	signing_key: &str,
+	commit: &[u8],
+) -> Result<(String, Option<String>), SignError> {
+	use std::io::Write;
+
+	let mut cmd = Command::new(program);
+	cmd.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.arg("--status-fd=2")
+		.arg("-bsau")
+		.arg(signing_key);
+
+	log::trace!("signing command: {cmd:?}");
+
+	let mut child =
+		cmd.spawn().map_err(|e| SignError::Spawn(e.to_string()))?;
+
+	let mut stdin = child.stdin.take().ok_or(SignError::Stdin)?;
+
+	stdin
+		.write_all(commit)
+		.map_err(|e| SignError::WriteBuffer(e.to_string()))?;
+	drop(stdin); // close stdin to not block indefinitely
+
+	let output = child
+		.wait_with_output()
+		.map_err(|e| SignError::Output(e.to_string()))?;
+
+	if !output.status.success() {
+		return Err(SignError::Shellout(format!(
+			"failed to sign data, program '{program}' exited non-zero: {}",
+			std::str::from_utf8(&output.stderr)
+				.unwrap_or("[error could not be read from stderr]")
+		)));
+	}
+
+	let stderr = std::str::from_utf8(&output.stderr)
+		.map_err(|e| SignError::Shellout(e.to_string()))?;
+
+	if !stderr.contains("\n[GNUPG:] SIG_CREATED ") {
+		return Err(SignError::Shellout(
+			format!("failed to sign data, program '{program}' failed, SIG_CREATED not seen in stderr"),
+		));
+	}
+
+	let signed_commit = std::str::from_utf8(&output.stdout)
+		.map_err(|e| SignError::Shellout(e.to_string()))?;
+
+	Ok((signed_commit.to_string(), Some("gpgsig".to_string())))
+}
+
+/// Literal keys are given inline instead of as a path to a key file,
+/// git still accepts the deprecated form without the `key::` prefix
+fn is_literal_ssh_key(key: &str) -> bool {
+	key.starts_with("key::") || key.starts_with("ssh-")
+}
+
+/// Sign commit data using `ssh-keygen -Y sign`
 pub struct SSHSign {
-	#[cfg(test)]
 	program: String,
-	#[cfg(test)]
-	key_path: String,
-	secret_key: PrivateKey,
+	signing_key: String,
 }
 
 impl SSHSign {
-	/// Create new `SSHDiskKeySign` for sign.
-	pub fn new(mut key: PathBuf) -> Result<Self, SignBuilderError> {
-		key.set_extension("");
-		if key.is_file() {
-			#[cfg(test)]
-			let key_path = format!("{}", &key.display());
-			std::fs::read(key)
-				.ok()
-				.and_then(|bytes| {
-					PrivateKey::from_openssh(bytes).ok()
-				})
-				.map(|secret_key| Self {
-					#[cfg(test)]
-					program: "ssh".to_string(),
-					#[cfg(test)]
-					key_path,
-					secret_key,
-				})
-				.ok_or_else(|| {
-					SignBuilderError::SSHSigningKey(String::from(
-						"Fail to read the private key for sign.",
-					))
-				})
-		} else {
-			Err(SignBuilderError::SSHSigningKey(
-				String::from("Currently, we only support a pair of ssh key in disk."),
-			))
+	/// Create new [`SSHSign`] using given program and signing key,
+	/// which is either a path to a key file or a literal public key.
+	pub fn new(program: &str, signing_key: &str) -> Self {
+		Self {
+			program: program.to_string(),
+			signing_key: signing_key.to_string(),
 		}
 	}
 }
@@ -317,13 +413,63 @@ impl Sign for SSHSign {
 		&self,
 		commit: &[u8],
 	) -> Result<(String, Option<String>), SignError> {
-		let sig = self
-			.secret_key
-			.sign("git", HashAlg::Sha256, commit)
-			.map_err(|err| SignError::Spawn(err.to_string()))?
-			.to_pem(LineEnding::LF)
-			.map_err(|err| SignError::Spawn(err.to_string()))?;
-		Ok((sig, None))
+		use std::io::Write;
+
+		// the private half of a literal key has to be in the agent
+		let literal_key =
+			if is_literal_ssh_key(&self.signing_key) {
+				let key = self
+					.signing_key
+					.strip_prefix("key::")
+					.unwrap_or(&self.signing_key);
+				Some(TempFile::new(key.as_bytes()).map_err(|e| {
+					SignError::WriteBuffer(e.to_string())
+				})?)
+			} else {
+				None
+			};
+
+		let mut cmd = Command::new(&self.program);
+		cmd.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.args(["-Y", "sign", "-n", "git", "-f"]);
+
+		if let Some(key_file) = &literal_key {
+			cmd.arg(key_file.path()).arg("-U");
+		} else {
+			cmd.arg(&self.signing_key);
+		}
+
+		log::trace!("signing command: {cmd:?}");
+
+		let mut child = cmd
+			.spawn()
+			.map_err(|e| SignError::Spawn(e.to_string()))?;
+
+		let mut stdin = child.stdin.take().ok_or(SignError::Stdin)?;
+
+		stdin
+			.write_all(commit)
+			.map_err(|e| SignError::WriteBuffer(e.to_string()))?;
+		drop(stdin);
+
+		let output = child
+			.wait_with_output()
+			.map_err(|e| SignError::Output(e.to_string()))?;
+
+		if !output.status.success() {
+			return Err(SignError::Shellout(format!(
+				"failed to sign data, program '{}' exited non-zero: {}",
+				self.program,
+				String::from_utf8_lossy(&output.stderr)
+			)));
+		}
+
+		let signature = String::from_utf8(output.stdout)
+			.map_err(|e| SignError::Shellout(e.to_string()))?;
+
+		Ok((signature, None))
 	}
 
 	#[cfg(test)]
@@ -333,7 +479,7 @@ impl Sign for SSHSign {
 
 	#[cfg(test)]
 	fn signing_key(&self) -> &String {
-		&self.key_path
+		&self.signing_key
 	}
 }
 
@@ -421,6 +567,80 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_x509_program_and_signing_key_defaults() -> Result<()> {
+		let (_tmp_dir, repo) = repo_init_empty()?;
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.format", "x509")?;
+		}
+
+		let sign =
+			SignBuilder::from_gitconfig(&repo, &repo.config()?)?;
+
+		assert_eq!("gpgsm", sign.program());
+		assert_eq!("name <email>", sign.signing_key());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_x509_program_configs() -> Result<()> {
+		let (_tmp_dir, repo) = repo_init_empty()?;
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.format", "x509")?;
+			config.set_str("gpg.program", "GPG_PROGRAM_TEST")?;
+			config
+				.set_str("gpg.x509.program", "X509_PROGRAM_TEST")?;
+			config.set_str("user.signingKey", "0xFFAA")?;
+		}
+
+		let sign =
+			SignBuilder::from_gitconfig(&repo, &repo.config()?)?;
+
+		// gpg.program only applies to openpgp
+		assert_eq!("X509_PROGRAM_TEST", sign.program());
+		assert_eq!("0xFFAA", sign.signing_key());
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_x509_sign() -> Result<()> {
+		use std::os::unix::fs::PermissionsExt;
+
+		let (tmp_dir, _repo) = repo_init_empty()?;
+
+		let program = tmp_dir.path().join("fake-gpgsm");
+		std::fs::write(
+			&program,
+			"#!/bin/sh\n\
+			cat > /dev/null\n\
+			echo '[GNUPG:] BEGIN_SIGNING' >&2\n\
+			echo \"[GNUPG:] SIG_CREATED $3\" >&2\n\
+			echo '-----BEGIN SIGNED MESSAGE-----'\n",
+		)?;
+		std::fs::set_permissions(
+			&program,
+			std::fs::Permissions::from_mode(0o755),
+		)?;
+
+		let sign = X509Sign::new(
+			program.to_str().unwrap_or_default(),
+			"0xFFAA",
+		);
+		let (signature, field) = sign.sign(b"commit").unwrap();
+
+		assert_eq!(signature, "-----BEGIN SIGNED MESSAGE-----\n");
+		assert_eq!(field.as_deref(), Some("gpgsig"));
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_ssh_program_configs() -> Result<()> {
 		let (_tmp_dir, repo) = repo_init_empty()?;
@@ -439,4 +659,116 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_ssh_format_configs() -> Result<()> {
+		let (_tmp_dir, repo) = repo_init_empty()?;
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.format", "ssh")?;
+			config.set_str("user.signingKey", "/tmp/key.pub")?;
+		}
+
+		let sign =
+			SignBuilder::from_gitconfig(&repo, &repo.config()?)?;
+
+		assert_eq!("ssh-keygen", sign.program());
+		assert_eq!("/tmp/key.pub", sign.signing_key());
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.ssh.program", "SSH_PROGRAM_TEST")?;
+			config.set_str(
+				"user.signingKey",
+				"key::ssh-ed25519 AAAA me@host",
+			)?;
+		}
+
+		let sign =
+			SignBuilder::from_gitconfig(&repo, &repo.config()?)?;
+
+		assert_eq!("SSH_PROGRAM_TEST", sign.program());
+		assert_eq!(
+			"key::ssh-ed25519 AAAA me@host",
+			sign.signing_key()
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_ssh_default_key_command() -> Result<()> {
+		let (_tmp_dir, repo) = repo_init_empty()?;
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.format", "ssh")?;
+		}
+
+		// neither user.signingKey nor a default key command
+		assert!(SignBuilder::from_gitconfig(&repo, &repo.config()?)
+			.is_err());
+
+		{
+			let mut config = repo.config()?;
+			config.set_str(
+				"gpg.ssh.defaultKeyCommand",
+				"echo key::ssh-ed25519 AAAA",
+			)?;
+		}
+
+		let sign =
+			SignBuilder::from_gitconfig(&repo, &repo.config()?)?;
+
+		assert_eq!("key::ssh-ed25519 AAAA", sign.signing_key());
+
+		{
+			let mut config = repo.config()?;
+			config
+				.set_str("gpg.ssh.defaultKeyCommand", "echo nokey")?;
+		}
+
+		assert!(SignBuilder::from_gitconfig(&repo, &repo.config()?)
+			.is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_ssh_sign_literal_key() -> Result<()> {
+		use std::os::unix::fs::PermissionsExt;
+
+		let (tmp_dir, _repo) = repo_init_empty()?;
+
+		// prints the arguments after the key file, the key and the signed data
+		let program = tmp_dir.path().join("fake-ssh-keygen");
+		std::fs::write(
+			&program,
+			"#!/bin/sh\n\
+			echo \"$1 $2 $4 $7\"\n\
+			cat \"$6\"\n\
+			cat\n",
+		)?;
+		std::fs::set_permissions(
+			&program,
+			std::fs::Permissions::from_mode(0o755),
+		)?;
+
+		let sign = SSHSign::new(
+			program.to_str().unwrap_or_default(),
+			"key::ssh-ed25519 AAAA",
+		);
+		let (signature, field) = sign.sign(b"commit").unwrap();
+
+		assert_eq!(
+			signature,
+			"-Y sign git -U\nssh-ed25519 AAAAcommit"
+		);
+		assert_eq!(field, None);
+
+		Ok(())
+	}
 }
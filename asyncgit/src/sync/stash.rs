@@ -1,13 +1,20 @@
 use super::{CommitId, RepoPath};
 use crate::{
 	error::{Error, Result},
-	sync::repository::repo,
+	sync::{
+		repository::repo,
+		utils::{bytes2string, work_dir},
+	},
 };
 use git2::{
-	build::CheckoutBuilder, Oid, Repository, StashApplyOptions,
-	StashFlags,
+	build::CheckoutBuilder, ApplyLocation, ApplyOptions, Commit,
+	Index, IndexEntry, IndexTime, Oid, Repository, StashApplyOptions,
+	StashFlags, Status, StatusOptions,
 };
 use scopetime::scope_time;
+use std::path::Path;
+
+const STASH_REF: &str = "refs/stash";
 
 ///
 pub fn get_stashes(repo_path: &RepoPath) -> Result<Vec<CommitId>> {
@@ -77,6 +84,158 @@ pub fn stash_apply(
 	Ok(())
 }
 
+/// changes the message of a stash, returns the new id of the stash
+///
+/// as the message is part of the stash commit this rewrites the commit
+/// and its entry in the stash reflog (keeping the position in the list).
+pub fn stash_rename(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+	message: &str,
+) -> Result<CommitId> {
+	scope_time!("stash_rename");
+
+	let mut repo = repo(repo_path)?;
+
+	let index = get_stash_index(&mut repo, stash_id.get_oid())?;
+
+	let stash = repo.find_commit(stash_id.get_oid())?;
+	let message = renamed_stash_message(
+		stash.message().unwrap_or_default(),
+		message,
+	);
+
+	let parents = stash.parents().collect::<Vec<_>>();
+	let id = repo.commit(
+		None,
+		&stash.author(),
+		&stash.committer(),
+		&message,
+		&stash.tree()?,
+		parents.iter().collect::<Vec<_>>().as_slice(),
+	)?;
+
+	if index == 0 {
+		repo.reference(STASH_REF, id, true, &message)?;
+	}
+
+	let mut reflog = repo.reflog(STASH_REF)?;
+
+	// newest first, skipping what updating the ref above appended
+	let mut entries = reflog
+		.iter()
+		.skip(usize::from(index == 0))
+		.map(|entry| {
+			(
+				entry.id_new(),
+				entry.committer().to_owned(),
+				entry.message().unwrap_or_default().to_string(),
+			)
+		})
+		.collect::<Vec<_>>();
+
+	if let Some(entry) = entries.get_mut(index) {
+		entry.0 = id;
+		entry.2.clone_from(&message);
+	}
+
+	while !reflog.is_empty() {
+		reflog.remove(0, false)?;
+	}
+
+	for (id, committer, message) in entries.iter().rev() {
+		reflog.append(*id, committer, Some(message))?;
+	}
+
+	reflog.write()?;
+
+	Ok(CommitId::new(id))
+}
+
+/// keeps the branch info of a stash message like `WIP on main: ...`
+fn renamed_stash_message(old: &str, message: &str) -> String {
+	old.strip_prefix("WIP on ")
+		.or_else(|| old.strip_prefix("On "))
+		.and_then(|rest| rest.split_once(": "))
+		.map_or_else(
+			|| message.to_string(),
+			|(branch, _)| format!("On {branch}: {message}"),
+		)
+}
+
+/// creates branch `name` at the commit the stash is based on, checks it out
+/// and applies the stash there (like `git stash branch`).
+/// the stash is dropped if it applied cleanly.
+pub fn stash_branch(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+	name: &str,
+) -> Result<()> {
+	scope_time!("stash_branch");
+
+	let mut repo = repo(repo_path)?;
+
+	let index = get_stash_index(&mut repo, stash_id.get_oid())?;
+
+	{
+		let base = repo.find_commit(stash_id.get_oid())?.parent(0)?;
+
+		let mut branch = repo.branch(name, &base, false)?;
+
+		if let Err(e) = repo.checkout_tree(
+			base.as_object(),
+			Some(&mut CheckoutBuilder::new()),
+		) {
+			branch.delete()?;
+			return Err(e.into());
+		}
+
+		let branch_ref = branch.into_reference();
+		repo.set_head(
+			bytes2string(branch_ref.name_bytes())?.as_str(),
+		)?;
+	}
+
+	let mut options = StashApplyOptions::new();
+	options.reinstantiate_index();
+	repo.stash_apply(index, Some(&mut options))?;
+
+	repo.stash_drop(index)?;
+
+	Ok(())
+}
+
+/// the commits a stash consists of
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StashParts {
+	/// commit that was `HEAD` when stashing
+	pub base: CommitId,
+	/// the stash itself, its tree has the tracked worktree changes
+	pub worktree: CommitId,
+	/// state of the index
+	pub index: CommitId,
+	/// untracked files (only if they were stashed)
+	pub untracked: Option<CommitId>,
+}
+
+///
+pub fn get_stash_parts(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+) -> Result<StashParts> {
+	scope_time!("get_stash_parts");
+
+	let repo = repo(repo_path)?;
+	let stash = repo.find_commit(stash_id.get_oid())?;
+
+	Ok(StashParts {
+		base: stash.parent_id(0)?.into(),
+		worktree: stash_id,
+		index: stash.parent_id(1)?.into(),
+		untracked: stash.parent_id(2).ok().map(Into::into),
+	})
+}
+
 fn get_stash_index(
 	repo: &mut Repository,
 	stash_id: Oid,
@@ -124,6 +283,364 @@ pub fn stash_save(
 	Ok(CommitId::new(id))
 }
 
+/// like [`stash_save`] but only stashes changes of `paths`
+/// (pathspecs relative to the workdir), everything else stays untouched
+pub fn stash_save_paths(
+	repo_path: &RepoPath,
+	message: Option<&str>,
+	paths: &[String],
+	include_untracked: bool,
+	keep_index: bool,
+) -> Result<CommitId> {
+	scope_time!("stash_save_paths");
+
+	if paths.is_empty() {
+		return Err(Error::Generic(String::from(
+			"no paths to stash",
+		)));
+	}
+
+	// libgit2 does not reliably limit a stash to a pathspec,
+	// so the stash commits are created the same way git does it
+	let repo = repo(repo_path)?;
+	let workdir = work_dir(&repo)?.to_path_buf();
+
+	let mut options = StatusOptions::new();
+	options
+		.include_untracked(include_untracked)
+		.recurse_untracked_dirs(true);
+	for path in paths {
+		options.pathspec(path.as_str());
+	}
+
+	let changes = repo
+		.statuses(Some(&mut options))?
+		.iter()
+		.filter_map(|e| Some((e.path()?.to_string(), e.status())))
+		.collect::<Vec<_>>();
+
+	if changes.is_empty() {
+		return Err(Error::Generic(String::from(
+			"no local changes to stash",
+		)));
+	}
+
+	let head = repo.head()?.peel_to_commit()?;
+	let repo_index = repo.index()?;
+
+	// index and worktree state of the selected paths on top of `HEAD`
+	let mut index = Index::new()?;
+	index.read_tree(&head.tree()?)?;
+
+	for (path, status) in &changes {
+		if status.is_index_deleted() {
+			index.remove_path(Path::new(path))?;
+		} else if status.intersects(
+			Status::INDEX_NEW
+				| Status::INDEX_MODIFIED
+				| Status::INDEX_TYPECHANGE,
+		) {
+			if let Some(entry) =
+				repo_index.get_path(Path::new(path), 0)
+			{
+				index.add(&entry)?;
+			}
+		}
+	}
+
+	let index_tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+
+	let mut untracked = Index::new()?;
+
+	for (path, status) in &changes {
+		if status.is_wt_new() {
+			add_workdir_file(&repo, &mut untracked, &workdir, path)?;
+		} else if status.is_wt_deleted() {
+			index.remove_path(Path::new(path))?;
+		} else if status
+			.intersects(Status::WT_MODIFIED | Status::WT_TYPECHANGE)
+		{
+			add_workdir_file(&repo, &mut index, &workdir, path)?;
+		}
+	}
+
+	let worktree_tree =
+		repo.find_tree(index.write_tree_to(&repo)?)?;
+
+	let sig = repo.signature()?;
+	let branch = stash_branch_name(&repo);
+	let oneline = commit_oneline(&head);
+
+	let index_commit = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&format!("index on {branch}: {oneline}"),
+		&index_tree,
+		&[&head],
+	)?;
+
+	let mut parents =
+		vec![head.clone(), repo.find_commit(index_commit)?];
+
+	if !untracked.is_empty() {
+		let untracked_commit = repo.commit(
+			None,
+			&sig,
+			&sig,
+			&format!("untracked files on {branch}: {oneline}"),
+			&repo.find_tree(untracked.write_tree_to(&repo)?)?,
+			&[],
+		)?;
+		parents.push(repo.find_commit(untracked_commit)?);
+	}
+
+	let message = stash_message(&repo, message)?;
+	let id = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&message,
+		&worktree_tree,
+		parents.iter().collect::<Vec<_>>().as_slice(),
+	)?;
+
+	store_stash(&repo, id, &message)?;
+
+	discard_changes(&repo, &head, &changes, keep_index)?;
+
+	Ok(CommitId::new(id))
+}
+
+/// resets `changes` (as reported by `statuses`) back to `HEAD`,
+/// files that are not tracked afterwards get deleted
+fn discard_changes(
+	repo: &Repository,
+	head: &Commit,
+	changes: &[(String, Status)],
+	keep_index: bool,
+) -> Result<()> {
+	let workdir = work_dir(repo)?;
+
+	let (removed, restored): (Vec<_>, Vec<_>) =
+		changes.iter().partition(|(_, status)| {
+			status.is_wt_new()
+				|| (status.is_index_new() && !keep_index)
+		});
+
+	if !keep_index {
+		repo.reset_default(
+			Some(head.as_object()),
+			changes.iter().map(|(path, _)| path.as_str()),
+		)?;
+	}
+
+	if !restored.is_empty() {
+		let mut checkout = CheckoutBuilder::new();
+		checkout.force().disable_pathspec_match(true);
+		for (path, _) in &restored {
+			checkout.path(path.as_str());
+		}
+
+		repo.checkout_index(None, Some(&mut checkout))?;
+	}
+
+	for (path, _) in removed {
+		remove_workdir_file(workdir, path)?;
+	}
+
+	Ok(())
+}
+
+/// adds the current content of a workdir file to `index`
+fn add_workdir_file(
+	repo: &Repository,
+	index: &mut Index,
+	workdir: &Path,
+	path: &str,
+) -> Result<()> {
+	let file = workdir.join(path);
+	let metadata = std::fs::symlink_metadata(&file)?;
+
+	let (mode, id) = if metadata.is_symlink() {
+		let target = std::fs::read_link(&file)?;
+		(0o120_000, repo.blob(target.to_string_lossy().as_bytes())?)
+	} else {
+		(file_mode(&metadata), repo.blob_path(&file)?)
+	};
+
+	index.add(&IndexEntry {
+		ctime: IndexTime::new(0, 0),
+		mtime: IndexTime::new(0, 0),
+		dev: 0,
+		ino: 0,
+		mode,
+		uid: 0,
+		gid: 0,
+		file_size: 0,
+		id,
+		flags: 0,
+		flags_extended: 0,
+		path: path.as_bytes().to_vec(),
+	})?;
+
+	Ok(())
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+	use std::os::unix::fs::PermissionsExt;
+
+	if metadata.permissions().mode() & 0o111 == 0 {
+		0o100_644
+	} else {
+		0o100_755
+	}
+}
+
+#[cfg(not(unix))]
+const fn file_mode(_metadata: &std::fs::Metadata) -> u32 {
+	0o100_644
+}
+
+/// deletes a file and the directories that became empty because of it
+fn remove_workdir_file(workdir: &Path, path: &str) -> Result<()> {
+	let file = workdir.join(path);
+
+	if std::fs::symlink_metadata(&file).is_ok() {
+		std::fs::remove_file(&file)?;
+	}
+
+	let mut dir = file.parent();
+	while let Some(d) = dir.filter(|d| *d != workdir) {
+		if std::fs::remove_dir(d).is_err() {
+			break;
+		}
+		dir = d.parent();
+	}
+
+	Ok(())
+}
+
+/// stashes only what is staged and removes it from the index and workdir
+/// while leaving unstaged changes alone (like `git stash --staged`)
+pub fn stash_save_staged(
+	repo_path: &RepoPath,
+	message: Option<&str>,
+) -> Result<CommitId> {
+	scope_time!("stash_save_staged");
+
+	let repo = repo(repo_path)?;
+
+	let head = repo.head()?.peel_to_commit()?;
+	let head_tree = head.tree()?;
+
+	let mut index = repo.index()?;
+	let index_tree = repo.find_tree(index.write_tree()?)?;
+
+	if index_tree.id() == head_tree.id() {
+		return Err(Error::Generic(String::from(
+			"no staged changes to stash",
+		)));
+	}
+
+	// the patch that removes the staged changes again
+	let revert = repo.diff_tree_to_tree(
+		Some(&index_tree),
+		Some(&head_tree),
+		None,
+	)?;
+
+	for location in [ApplyLocation::Index, ApplyLocation::WorkDir] {
+		repo.apply(
+			&revert,
+			location,
+			Some(ApplyOptions::new().check(true)),
+		)
+		.map_err(|_| {
+			Error::Generic(String::from(
+				"staged changes overlap with unstaged changes",
+			))
+		})?;
+	}
+
+	let sig = repo.signature()?;
+	let branch = stash_branch_name(&repo);
+
+	let index_commit = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&format!("index on {branch}: {}", commit_oneline(&head)),
+		&index_tree,
+		&[&head],
+	)?;
+	let index_commit = repo.find_commit(index_commit)?;
+
+	let message = stash_message(&repo, message)?;
+	let id = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&message,
+		&index_tree,
+		&[&head, &index_commit],
+	)?;
+
+	store_stash(&repo, id, &message)?;
+
+	repo.apply(&revert, ApplyLocation::Index, None)?;
+	repo.apply(&revert, ApplyLocation::WorkDir, None)?;
+
+	Ok(CommitId::new(id))
+}
+
+/// message of a new stash in the format used by git
+fn stash_message(
+	repo: &Repository,
+	message: Option<&str>,
+) -> Result<String> {
+	let branch = stash_branch_name(repo);
+
+	if let Some(message) = message {
+		return Ok(format!("On {branch}: {message}"));
+	}
+
+	let head = repo.head()?.peel_to_commit()?;
+	Ok(format!("WIP on {branch}: {}", commit_oneline(&head)))
+}
+
+fn stash_branch_name(repo: &Repository) -> String {
+	repo.head()
+		.ok()
+		.filter(git2::Reference::is_branch)
+		.and_then(|head| head.shorthand().map(String::from))
+		.unwrap_or_else(|| String::from("(no branch)"))
+}
+
+fn commit_oneline(commit: &Commit) -> String {
+	let id = commit.id().to_string();
+
+	format!(
+		"{} {}",
+		id.get(..7).unwrap_or_default(),
+		commit.summary().unwrap_or_default()
+	)
+}
+
+/// makes `id` the newest stash
+fn store_stash(
+	repo: &Repository,
+	id: Oid,
+	message: &str,
+) -> Result<()> {
+	// the stash list is read from the reflog so it must be written
+	repo.reference_ensure_log(STASH_REF)?;
+	repo.reference(STASH_REF, id, true, message)?;
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -373,4 +890,261 @@ mod tests {
 			"test3"
 		);
 	}
+
+	#[test]
+	fn test_stash_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+		repo_write_file(&repo, "c.txt", "c").unwrap();
+		repo_write_file(&repo, "d.txt", "d").unwrap();
+
+		assert!(stash_save_paths(repo_path, None, &[], true, false)
+			.is_err());
+
+		let id = stash_save_paths(
+			repo_path,
+			Some("only a"),
+			&[String::from("a.txt"), String::from("c.txt")],
+			true,
+			false,
+		)
+		.unwrap();
+
+		assert_eq!(get_stashes(repo_path).unwrap(), vec![id]);
+
+		let files = get_commit_files(repo_path, id, None).unwrap();
+		assert_eq!(
+			files.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(),
+			vec!["a.txt", "c.txt"]
+		);
+
+		assert_eq!(get_statuses(repo_path), (2, 0));
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a");
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+		assert!(!root.join("c.txt").exists());
+		assert!(root.join("d.txt").exists());
+
+		let infos = get_commits_info(repo_path, &[id], 100).unwrap();
+		assert_eq!(infos[0].message, "On master: only a");
+
+		stash_pop(repo_path, id).unwrap();
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a2");
+		assert_eq!(repo_read_file(&repo, "c.txt").unwrap(), "c");
+	}
+
+	#[test]
+	fn test_stash_staged() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		assert!(stash_save_staged(repo_path, None).is_err());
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		repo_write_file(&repo, "new.txt", "new").unwrap();
+		stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+
+		let id =
+			stash_save_staged(repo_path, Some("staged")).unwrap();
+
+		assert_eq!(get_stashes(repo_path).unwrap(), vec![id]);
+		assert_eq!(get_statuses(repo_path), (1, 0));
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a");
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+		assert!(!root.join("new.txt").exists());
+
+		let files = get_commit_files(repo_path, id, None).unwrap();
+		assert_eq!(files.len(), 2);
+
+		stash_pop(repo_path, id).unwrap();
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a2");
+		assert_eq!(repo_read_file(&repo, "new.txt").unwrap(), "new");
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+	}
+
+	#[test]
+	fn test_stash_staged_overlapping() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		repo_write_file(&repo, "a.txt", "a3").unwrap();
+
+		assert!(stash_save_staged(repo_path, None).is_err());
+		assert!(get_stashes(repo_path).unwrap().is_empty());
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a3");
+	}
+
+	#[test]
+	fn test_stash_parts() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		let base = write_commit_file(&repo, "b.txt", "b", "c2");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+
+		let id = stash_save(repo_path, None, false, false).unwrap();
+		let parts = get_stash_parts(repo_path, id).unwrap();
+
+		assert_eq!(parts.base, base);
+		assert_eq!(parts.worktree, id);
+		assert_eq!(parts.untracked, None);
+
+		let index =
+			get_commit_files(repo_path, parts.index, None).unwrap();
+		assert_eq!(index.len(), 1);
+		assert_eq!(index[0].path, "a.txt");
+
+		repo_write_file(&repo, "c.txt", "c").unwrap();
+
+		let id = stash_save(repo_path, None, true, false).unwrap();
+		let parts = get_stash_parts(repo_path, id).unwrap();
+
+		let untracked = get_commit_files(
+			repo_path,
+			parts.untracked.unwrap(),
+			None,
+		)
+		.unwrap();
+		assert_eq!(untracked.len(), 1);
+		assert_eq!(untracked[0].path, "c.txt");
+	}
+
+	#[test]
+	fn test_stash_branch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+		let id = stash_save(repo_path, None, false, false).unwrap();
+
+		// stash would not apply cleanly on master anymore
+		write_commit_file(&repo, "a.txt", "a3", "c2");
+
+		stash_branch(repo_path, id, "from-stash").unwrap();
+
+		assert!(get_stashes(repo_path).unwrap().is_empty());
+		assert_eq!(
+			repo.head().unwrap().shorthand().unwrap(),
+			"from-stash"
+		);
+		assert_eq!(
+			repo.head().unwrap().target().unwrap(),
+			base.get_oid()
+		);
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a2");
+		assert_eq!(get_statuses(repo_path), (0, 1));
+	}
+
+	#[test]
+	fn test_stash_branch_existing() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+
+		let id = stash_save(repo_path, None, false, false).unwrap();
+
+		assert!(stash_branch(repo_path, id, "master").is_err());
+		assert_eq!(get_stashes(repo_path).unwrap(), vec![id]);
+	}
+
+	#[test]
+	fn test_stash_rename() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		let first =
+			stash_save(repo_path, None, false, false).unwrap();
+
+		repo_write_file(&repo, "a.txt", "a3").unwrap();
+		let second =
+			stash_save(repo_path, Some("foo"), false, false).unwrap();
+
+		let renamed_first =
+			stash_rename(repo_path, first, "first").unwrap();
+		let renamed_second =
+			stash_rename(repo_path, second, "second").unwrap();
+
+		assert_eq!(
+			get_stashes(repo_path).unwrap(),
+			vec![renamed_second, renamed_first]
+		);
+
+		let infos = get_commits_info(
+			repo_path,
+			&[renamed_second, renamed_first],
+			100,
+		)
+		.unwrap();
+		assert_eq!(infos[0].message, "On master: second");
+		assert_eq!(infos[1].message, "On master: first");
+
+		let reflog = repo.reflog(STASH_REF).unwrap();
+		assert_eq!(reflog.len(), 2);
+		assert_eq!(
+			reflog.get(1).unwrap().message().unwrap(),
+			"On master: first"
+		);
+
+		stash_pop(repo_path, renamed_first).unwrap();
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a2");
+		assert_eq!(
+			get_stashes(repo_path).unwrap(),
+			vec![renamed_second]
+		);
+	}
+
+	#[test]
+	fn test_renamed_stash_message() {
+		assert_eq!(
+			renamed_stash_message("WIP on main: 1234567 foo", "bar"),
+			"On main: bar"
+		);
+		assert_eq!(
+			renamed_stash_message("On feat/x: foo", "bar"),
+			"On feat/x: bar"
+		);
+		assert_eq!(renamed_stash_message("foo", "bar"), "bar");
+	}
 }
@@ -175,6 +175,91 @@ pub fn get_status(
 ) -> Result<Vec<StatusItem>> {
 	scope_time!("get_status");
 
+	get_status_pathspec(
+		repo_path,
+		status_type,
+		show_untracked,
+		Vec::new(),
+	)
+}
+
+/// recomputes the status of `paths` only and merges it into the
+/// `previous` result of [`get_status`] (using the same type and config).
+///
+/// guarantees sorting
+pub fn get_status_of_paths(
+	repo_path: &RepoPath,
+	status_type: StatusType,
+	show_untracked: Option<ShowUntrackedFilesConfig>,
+	previous: &[StatusItem],
+	paths: &[String],
+) -> Result<Vec<StatusItem>> {
+	scope_time!("get_status_of_paths");
+
+	let paths: Vec<&str> = paths
+		.iter()
+		.map(|path| path.trim_end_matches('/'))
+		.filter(|path| !path.is_empty())
+		.map(|path| {
+			// a change inside of a collapsed untracked folder needs the whole folder re-evaluated
+			previous
+				.iter()
+				.map(|item| item.path.trim_end_matches('/'))
+				.find(|item| is_path_affected(path, item))
+				.unwrap_or(path)
+		})
+		.collect();
+
+	if paths.is_empty() {
+		return Ok(previous.to_vec());
+	}
+
+	let patterns = paths
+		.iter()
+		.map(|path| format!(":(literal){path}").into())
+		.collect();
+
+	let fresh = get_status_pathspec(
+		repo_path,
+		status_type,
+		show_untracked,
+		patterns,
+	)?;
+
+	let mut res: Vec<StatusItem> = previous
+		.iter()
+		.filter(|item| {
+			!paths
+				.iter()
+				.any(|path| is_path_affected(&item.path, path))
+		})
+		.cloned()
+		.chain(fresh)
+		.collect();
+
+	sort_status(&mut res);
+
+	Ok(res)
+}
+
+/// is `item` (a status entry) equal to or contained in `changed`
+fn is_path_affected(item: &str, changed: &str) -> bool {
+	item.strip_prefix(changed)
+		.is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+fn sort_status(items: &mut [StatusItem]) {
+	items.sort_by(|a, b| {
+		Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
+	});
+}
+
+fn get_status_pathspec(
+	repo_path: &RepoPath,
+	status_type: StatusType,
+	show_untracked: Option<ShowUntrackedFilesConfig>,
+	patterns: Vec<gix::bstr::BString>,
+) -> Result<Vec<StatusItem>> {
 	let repo: gix::Repository = gix_repo(repo_path)?;
 
 	let show_untracked = if let Some(config) = show_untracked {
@@ -199,7 +284,7 @@ pub fn get_status(
 
 	match status_type {
 		StatusType::WorkingDir => {
-			let iter = status.into_index_worktree_iter(Vec::new())?;
+			let iter = status.into_index_worktree_iter(patterns)?;
 
 			for item in iter {
 				let item = item?;
@@ -223,7 +308,7 @@ pub fn get_status(
 
 			let mut pathspec = repo.pathspec(
 				false, /* empty patterns match prefix */
-				None::<&str>,
+				patterns,
 				true, /* inherit ignore case */
 				&gix::index::State::new(repo.object_hash()),
 				gix::worktree::stack::state::attributes::Source::WorktreeThenIdMapping
@@ -251,7 +336,7 @@ pub fn get_status(
 			)?;
 		}
 		StatusType::Both => {
-			let iter = status.into_iter(Vec::new())?;
+			let iter = status.into_iter(patterns)?;
 
 			for item in iter {
 				let item = item?;
@@ -274,9 +359,112 @@ pub fn get_status(
 		}
 	}
 
-	res.sort_by(|a, b| {
-		Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
-	});
+	sort_status(&mut res);
 
 	Ok(res)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+
+	#[test]
+	fn test_status_of_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+
+		let show = Some(ShowUntrackedFilesConfig::All);
+
+		let previous =
+			get_status(repo_path, StatusType::WorkingDir, show)
+				.unwrap();
+		assert_eq!(previous.len(), 1);
+
+		// `b.txt` changed and a new file showed up,
+		// `a.txt` is kept as it was not reported
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+		std::fs::create_dir(root.join("dir")).unwrap();
+		repo_write_file(&repo, "dir/c.txt", "c").unwrap();
+
+		let res = get_status_of_paths(
+			repo_path,
+			StatusType::WorkingDir,
+			show,
+			&previous,
+			&[String::from("b.txt"), String::from("dir/")],
+		)
+		.unwrap();
+
+		assert_eq!(
+			res,
+			get_status(repo_path, StatusType::WorkingDir, show)
+				.unwrap()
+		);
+		assert_eq!(res.len(), 3);
+
+		// reverting `a.txt` removes it
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+
+		let res = get_status_of_paths(
+			repo_path,
+			StatusType::WorkingDir,
+			show,
+			&res,
+			&[String::from("a.txt")],
+		)
+		.unwrap();
+
+		assert_eq!(
+			res.iter().map(|i| i.path.as_str()).collect::<Vec<_>>(),
+			vec!["b.txt", "dir/c.txt"]
+		);
+	}
+
+	#[test]
+	fn test_status_of_paths_collapsed_untracked() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::create_dir(root.join("dir")).unwrap();
+		repo_write_file(&repo, "dir/a.txt", "a").unwrap();
+
+		let show = Some(ShowUntrackedFilesConfig::Normal);
+
+		let previous =
+			get_status(repo_path, StatusType::WorkingDir, show)
+				.unwrap();
+		assert_eq!(previous.len(), 1);
+
+		stage_add_file(repo_path, Path::new("dir/a.txt")).unwrap();
+		repo_write_file(&repo, "dir/b.txt", "b").unwrap();
+
+		let res = get_status_of_paths(
+			repo_path,
+			StatusType::WorkingDir,
+			show,
+			&previous,
+			&[String::from("dir/b.txt")],
+		)
+		.unwrap();
+
+		assert_eq!(
+			res,
+			get_status(repo_path, StatusType::WorkingDir, show)
+				.unwrap()
+		);
+	}
+}
@@ -1,7 +1,11 @@
 use super::{get_commits_info, CommitId, RepoPath};
 use crate::{error::Result, sync::repository::repo};
+use git2::{DescribeFormatOptions, DescribeOptions, ErrorClass};
 use scopetime::scope_time;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{
+	cmp::Ordering,
+	collections::{BTreeMap, HashMap, HashSet},
+};
 
 ///
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
@@ -28,6 +32,7 @@ pub type CommitTags = Vec<Tag>;
 pub type Tags = BTreeMap<CommitId, CommitTags>;
 
 ///
+#[derive(Clone)]
 pub struct TagWithMetadata {
 	///
 	pub name: String,
@@ -158,10 +163,105 @@ pub fn delete_tag(
 	Ok(())
 }
 
+/// `git describe --tags` of a commit (`v1.4.2-17-gabc1234`),
+/// `None` if no tag is reachable from it
+pub fn describe_commit(
+	repo_path: &RepoPath,
+	commit_id: CommitId,
+) -> Result<Option<String>> {
+	scope_time!("describe_commit");
+
+	let repo = repo(repo_path)?;
+	let commit = repo.find_commit(commit_id.into())?;
+
+	let describe = match commit
+		.as_object()
+		.describe(DescribeOptions::new().describe_tags())
+	{
+		Ok(describe) => describe,
+		// no tag reachable
+		Err(e) if e.class() == ErrorClass::Describe => {
+			return Ok(None)
+		}
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(Some(describe.format(Some(&DescribeFormatOptions::new()))?))
+}
+
+/// compares tag names like versions (`git tag --sort=version:refname`):
+/// numbers are compared by value and a pre-release suffix
+/// (`v1.0.0-rc1`) sorts before the release
+pub fn cmp_tag_versions(a: &str, b: &str) -> Ordering {
+	let mut a_chunks = version_chunks(a);
+	let mut b_chunks = version_chunks(b);
+
+	loop {
+		match (a_chunks.next(), b_chunks.next()) {
+			(None, None) => return a.cmp(b),
+			(Some(rest), None) => {
+				return if rest.starts_with('-') {
+					Ordering::Less
+				} else {
+					Ordering::Greater
+				};
+			}
+			(None, Some(rest)) => {
+				return if rest.starts_with('-') {
+					Ordering::Greater
+				} else {
+					Ordering::Less
+				};
+			}
+			(Some(a_chunk), Some(b_chunk)) => {
+				let ordering =
+					if is_number(a_chunk) && is_number(b_chunk) {
+						let a_num = a_chunk.trim_start_matches('0');
+						let b_num = b_chunk.trim_start_matches('0');
+
+						a_num
+							.len()
+							.cmp(&b_num.len())
+							.then_with(|| a_num.cmp(b_num))
+					} else {
+						a_chunk.cmp(b_chunk)
+					};
+
+				if ordering != Ordering::Equal {
+					return ordering;
+				}
+			}
+		}
+	}
+}
+
+fn is_number(chunk: &str) -> bool {
+	chunk.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// splits into alternating runs of digits and non-digits
+fn version_chunks(version: &str) -> impl Iterator<Item = &str> {
+	let mut rest = version;
+
+	std::iter::from_fn(move || {
+		let first = rest.chars().next()?;
+		let end = rest
+			.find(|c: char| {
+				c.is_ascii_digit() != first.is_ascii_digit()
+			})
+			.unwrap_or(rest.len());
+
+		let (chunk, tail) = rest.split_at(end);
+		rest = tail;
+
+		Some(chunk)
+	})
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::sync::tests::repo_init;
+	use crate::sync::tests::{repo_init, write_commit_file};
 	use git2::ObjectType;
 
 	#[test]
@@ -222,4 +322,67 @@ mod tests {
 
 		assert_eq!(tags.len(), 0);
 	}
+
+	#[test]
+	fn test_describe() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head_id =
+			CommitId::new(repo.head().unwrap().target().unwrap());
+
+		assert_eq!(
+			describe_commit(repo_path, head_id).unwrap(),
+			None
+		);
+
+		let target = repo
+			.find_object(head_id.into(), Some(ObjectType::Commit))
+			.unwrap();
+		repo.tag_lightweight("v1.0", &target, false).unwrap();
+
+		assert_eq!(
+			describe_commit(repo_path, head_id).unwrap().as_deref(),
+			Some("v1.0")
+		);
+
+		let id = write_commit_file(&repo, "a.txt", "a", "c1");
+		let describe =
+			describe_commit(repo_path, id).unwrap().unwrap();
+
+		assert!(describe.starts_with("v1.0-1-g"));
+		assert!(id.to_string().starts_with(&describe[8..]));
+	}
+
+	#[test]
+	fn test_cmp_tag_versions() {
+		let mut tags = vec![
+			"v1.10.0",
+			"v1.2.0",
+			"v1.2.0-rc.10",
+			"v1.2.0-rc.2",
+			"v1.2.1",
+			"v0.9",
+			"v1.2.0.1",
+			"latest",
+		];
+
+		tags.sort_by(|a, b| cmp_tag_versions(a, b));
+
+		assert_eq!(
+			tags,
+			vec![
+				"latest",
+				"v0.9",
+				"v1.2.0-rc.2",
+				"v1.2.0-rc.10",
+				"v1.2.0",
+				"v1.2.0.1",
+				"v1.2.1",
+				"v1.10.0",
+			]
+		);
+	}
 }
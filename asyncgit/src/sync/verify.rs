@@ -0,0 +1,523 @@
+//! Verify signatures of tags and commits.
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::{Error, Result};
+use git2::{ErrorCode, ObjectType, Oid, Repository};
+use scopetime::scope_time;
+use std::{
+	fs::{self, OpenOptions},
+	io::Write,
+	path::{Path, PathBuf},
+	process::{Command, Output, Stdio},
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Outcome of verifying the signature of a git object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureState {
+	/// the object is not signed
+	Unsigned,
+	/// valid signature made by the contained signer
+	Good(String),
+	/// the signature does not match the signed data
+	Bad(String),
+	/// the signature can not be checked because the contained key
+	/// is not known (or not allowed to sign)
+	UnknownKey(String),
+}
+
+/// Signature formats git supports, see `gpg.format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SignatureFormat {
+	OpenPgp,
+	X509,
+	Ssh,
+}
+
+const SIGNATURE_HEADERS: [(&str, SignatureFormat); 4] = [
+	("-----BEGIN PGP SIGNATURE-----", SignatureFormat::OpenPgp),
+	("-----BEGIN PGP MESSAGE-----", SignatureFormat::OpenPgp),
+	("-----BEGIN SIGNED MESSAGE-----", SignatureFormat::X509),
+	("-----BEGIN SSH SIGNATURE-----", SignatureFormat::Ssh),
+];
+
+/// verifies the signature of the annotated tag `name`,
+/// lightweight tags are reported as [`SignatureState::Unsigned`]
+pub fn verify_tag_signature(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<SignatureState> {
+	scope_time!("verify_tag_signature");
+
+	let repo = repo(repo_path)?;
+	let reference =
+		repo.find_reference(&format!("refs/tags/{name}"))?;
+
+	reference
+		.target()
+		.map_or(Ok(SignatureState::Unsigned), |id| {
+			verify_tag_object(&repo, id)
+		})
+}
+
+/// verifies the signature of commit `id`
+pub fn verify_commit_signature(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<SignatureState> {
+	scope_time!("verify_commit_signature");
+
+	let repo = repo(repo_path)?;
+
+	let (signature, payload) =
+		match repo.extract_signature(&id.get_oid(), None) {
+			Ok(signed) => signed,
+			Err(e) if e.code() == ErrorCode::NotFound => {
+				return Ok(SignatureState::Unsigned);
+			}
+			Err(e) => return Err(e.into()),
+		};
+
+	let format = signature_format(&signature).ok_or_else(|| {
+		Error::Generic(format!("unknown signature format on {id}"))
+	})?;
+
+	verify_signature(&repo.config()?, format, &payload, &signature)
+}
+
+fn signature_format(signature: &[u8]) -> Option<SignatureFormat> {
+	SIGNATURE_HEADERS
+		.iter()
+		.find(|(header, _)| signature.starts_with(header.as_bytes()))
+		.map(|(_, format)| *format)
+}
+
+fn verify_tag_object(
+	repo: &Repository,
+	id: Oid,
+) -> Result<SignatureState> {
+	let odb = repo.odb()?;
+	let object = odb.read(id)?;
+
+	if object.kind() != ObjectType::Tag {
+		return Ok(SignatureState::Unsigned);
+	}
+
+	split_signature(object.data()).map_or(
+		Ok(SignatureState::Unsigned),
+		|(payload, signature, format)| {
+			verify_signature(
+				&repo.config()?,
+				format,
+				payload,
+				signature,
+			)
+		},
+	)
+}
+
+/// splits a tag object into the signed payload and the signature
+/// appended to its message, just like git we use the last signature
+fn split_signature(
+	buffer: &[u8],
+) -> Option<(&[u8], &[u8], SignatureFormat)> {
+	let mut found = None;
+	let mut start = 0;
+
+	for line in buffer.split_inclusive(|c| *c == b'\n') {
+		if let Some(format) = signature_format(line) {
+			found = Some((start, format));
+		}
+		start += line.len();
+	}
+
+	found.map(|(start, format)| {
+		(&buffer[..start], &buffer[start..], format)
+	})
+}
+
+fn verify_signature(
+	config: &git2::Config,
+	format: SignatureFormat,
+	payload: &[u8],
+	signature: &[u8],
+) -> Result<SignatureState> {
+	let signature = TempFile::new(signature)?;
+
+	match format {
+		SignatureFormat::OpenPgp => {
+			let program = config
+				.get_string("gpg.openpgp.program")
+				.or_else(|_| config.get_string("gpg.program"))
+				.unwrap_or_else(|_| "gpg".to_string());
+
+			verify_gpg(&program, payload, &signature)
+		}
+		SignatureFormat::X509 => {
+			let program = config
+				.get_string("gpg.x509.program")
+				.unwrap_or_else(|_| "gpgsm".to_string());
+
+			verify_gpg(&program, payload, &signature)
+		}
+		SignatureFormat::Ssh => {
+			let program = config
+				.get_string("gpg.ssh.program")
+				.unwrap_or_else(|_| "ssh-keygen".to_string());
+			let allowed_signers =
+				config.get_path("gpg.ssh.allowedSignersFile").ok();
+
+			verify_ssh(
+				&program,
+				allowed_signers.as_ref(),
+				payload,
+				&signature,
+			)
+		}
+	}
+}
+
+/// uses the status lines gpg (and gpgsm) print to `--status-fd`
+fn verify_gpg(
+	program: &str,
+	payload: &[u8],
+	signature: &TempFile,
+) -> Result<SignatureState> {
+	let mut cmd = Command::new(program);
+	cmd.arg("--status-fd=1")
+		.arg("--verify")
+		.arg(&signature.0)
+		.arg("-");
+
+	let output = run_with_input(cmd, payload)?;
+	let status = String::from_utf8_lossy(&output.stdout);
+
+	for line in status.lines() {
+		let Some(line) = line.strip_prefix("[GNUPG:] ") else {
+			continue;
+		};
+		let mut parts = line.splitn(3, ' ');
+		let (Some(keyword), Some(key)) = (parts.next(), parts.next())
+		else {
+			continue;
+		};
+		let signer = parts.next().unwrap_or(key).to_string();
+
+		match keyword {
+			"GOODSIG" => return Ok(SignatureState::Good(signer)),
+			"BADSIG" | "EXPSIG" | "EXPKEYSIG" | "REVKEYSIG" => {
+				return Ok(SignatureState::Bad(signer))
+			}
+			"ERRSIG" | "NO_PUBKEY" => {
+				return Ok(SignatureState::UnknownKey(
+					key.to_string(),
+				))
+			}
+			_ => (),
+		}
+	}
+
+	Err(Error::Generic(format!(
+		"'{program}' could not verify the signature: {}",
+		String::from_utf8_lossy(&output.stderr).trim()
+	)))
+}
+
+/// follows what git does: the principal is looked up in
+/// `gpg.ssh.allowedSignersFile` and the signature verified for it
+fn verify_ssh(
+	program: &str,
+	allowed_signers: Option<&PathBuf>,
+	payload: &[u8],
+	signature: &TempFile,
+) -> Result<SignatureState> {
+	let principal = allowed_signers
+		.map(|allowed_signers| -> Result<Option<String>> {
+			let output = Command::new(program)
+				.args(["-Y", "find-principals", "-f"])
+				.arg(allowed_signers)
+				.arg("-s")
+				.arg(&signature.0)
+				.output()?;
+
+			Ok(output
+				.status
+				.success()
+				.then(|| {
+					String::from_utf8_lossy(&output.stdout)
+						.lines()
+						.next()
+						.map(str::to_string)
+				})
+				.flatten())
+		})
+		.transpose()?
+		.flatten();
+
+	let (Some(allowed_signers), Some(principal)) =
+		(allowed_signers, principal)
+	else {
+		// the key is not allowed, at least tell whether the data matches
+		let mut cmd = Command::new(program);
+		cmd.args(["-Y", "check-novalidate", "-n", "git", "-s"])
+			.arg(&signature.0);
+
+		let output = run_with_input(cmd, payload)?;
+		let key = String::from_utf8_lossy(&output.stdout)
+			.split(" key ")
+			.nth(1)
+			.map(|key| key.trim().to_string())
+			.unwrap_or_default();
+
+		return Ok(if output.status.success() {
+			SignatureState::UnknownKey(key)
+		} else {
+			SignatureState::Bad(key)
+		});
+	};
+
+	let mut cmd = Command::new(program);
+	cmd.args(["-Y", "verify", "-n", "git", "-f"])
+		.arg(allowed_signers)
+		.arg("-I")
+		.arg(&principal)
+		.arg("-s")
+		.arg(&signature.0);
+
+	let output = run_with_input(cmd, payload)?;
+
+	Ok(if output.status.success() {
+		SignatureState::Good(principal)
+	} else {
+		SignatureState::Bad(principal)
+	})
+}
+
+fn run_with_input(mut cmd: Command, input: &[u8]) -> Result<Output> {
+	log::trace!("verify command: {cmd:?}");
+
+	let mut child = cmd
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.map_err(|e| {
+			Error::Generic(format!(
+				"failed to run '{}': {e}",
+				cmd.get_program().to_string_lossy()
+			))
+		})?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		// the program might not read all of it before failing
+		stdin.write_all(input).ok();
+	}
+
+	Ok(child.wait_with_output()?)
+}
+
+/// the signing and verifying programs expect keys and signatures in files
+pub(super) struct TempFile(PathBuf);
+
+impl TempFile {
+	pub(super) fn new(content: &[u8]) -> Result<Self> {
+		static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+		let path = std::env::temp_dir().join(format!(
+			"gitui-{}-{}",
+			std::process::id(),
+			COUNTER.fetch_add(1, Ordering::Relaxed)
+		));
+
+		OpenOptions::new()
+			.write(true)
+			.create_new(true)
+			.open(&path)?
+			.write_all(content)?;
+
+		Ok(Self(path))
+	}
+
+	pub(super) fn path(&self) -> &Path {
+		&self.0
+	}
+}
+
+impl Drop for TempFile {
+	fn drop(&mut self) {
+		fs::remove_file(&self.0).ok();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		tag_commit,
+		tests::{repo_init, write_commit_file},
+	};
+	#[cfg(unix)]
+	use std::os::unix::fs::PermissionsExt;
+
+	#[cfg(unix)]
+	fn create_program(
+		dir: &Path,
+		name: &str,
+		script: &str,
+	) -> String {
+		let path = dir.join(name);
+		fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+		fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+			.unwrap();
+		path.to_string_lossy().to_string()
+	}
+
+	#[test]
+	fn test_split_signature() {
+		let tag = b"object 1\ntype commit\ntag v1\n\nmsg\n-----BEGIN SSH SIGNATURE-----\nabc\n-----END SSH SIGNATURE-----\n";
+
+		let (payload, signature, format) =
+			split_signature(tag).unwrap();
+
+		assert_eq!(
+			payload,
+			b"object 1\ntype commit\ntag v1\n\nmsg\n"
+		);
+		assert_eq!(
+			signature,
+			b"-----BEGIN SSH SIGNATURE-----\nabc\n-----END SSH SIGNATURE-----\n"
+		);
+		assert_eq!(format, SignatureFormat::Ssh);
+
+		assert!(split_signature(b"tag v1\n\nmsg\n").is_none());
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_signed_tag() {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_path_buf().into();
+
+		let id = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let gpg = create_program(
+			td.path(),
+			"fake-gpg",
+			"if [ \"$2\" = \"--verify\" ]; then
+	grep -q 'tag v1' && echo '[GNUPG:] GOODSIG ABCD name <email>'
+	exit 0
+fi
+cat > /dev/null
+printf '[GNUPG:] BEGIN_SIGNING\n[GNUPG:] SIG_CREATED \n' >&2
+printf -- '-----BEGIN PGP SIGNATURE-----\nsig\n-----END PGP SIGNATURE-----\n'",
+		);
+
+		{
+			let mut config = repo.config().unwrap();
+			config.set_bool("tag.gpgSign", true).unwrap();
+			config.set_str("gpg.program", &gpg).unwrap();
+		}
+
+		tag_commit(repo_path, &id, "v1", Some("msg")).unwrap();
+		tag_commit(repo_path, &id, "v2", None).unwrap();
+
+		let tag = repo.revparse_single("v1").unwrap();
+		let tag = tag.as_tag().unwrap();
+		assert_eq!(tag.target_id(), id.get_oid());
+		assert_eq!(
+			tag.message(),
+			Some("msg\n-----BEGIN PGP SIGNATURE-----\nsig\n-----END PGP SIGNATURE-----\n")
+		);
+
+		assert_eq!(
+			verify_tag_signature(repo_path, "v1").unwrap(),
+			SignatureState::Good(String::from("name <email>"))
+		);
+		assert_eq!(
+			verify_tag_signature(repo_path, "v2").unwrap(),
+			SignatureState::Unsigned
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_unknown_gpg_key() {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_path_buf().into();
+
+		let id = write_commit_file(&repo, "a.txt", "a", "c1");
+		let tag = format!(
+			"object {id}\ntype commit\ntag v1\ntagger name <email> 0 +0000\n\nmsg\n-----BEGIN PGP SIGNATURE-----\nsig\n-----END PGP SIGNATURE-----\n"
+		);
+		let tag_id = repo
+			.odb()
+			.unwrap()
+			.write(ObjectType::Tag, tag.as_bytes());
+		repo.reference("refs/tags/v1", tag_id.unwrap(), false, "")
+			.unwrap();
+
+		let gpg = create_program(
+			td.path(),
+			"fake-gpg",
+			"echo '[GNUPG:] ERRSIG ABCD 1 8 00 0 9'
+echo '[GNUPG:] NO_PUBKEY ABCD'
+exit 2",
+		);
+		repo.config().unwrap().set_str("gpg.program", &gpg).unwrap();
+
+		assert_eq!(
+			verify_tag_signature(repo_path, "v1").unwrap(),
+			SignatureState::UnknownKey(String::from("ABCD"))
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_signed_commit() {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_path_buf().into();
+
+		let unsigned = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let gpg = create_program(
+			td.path(),
+			"fake-gpg",
+			"if [ \"$2\" = \"--verify\" ]; then
+	if grep -q 'good'; then
+		echo '[GNUPG:] GOODSIG ABCD name <email>'
+	else
+		echo '[GNUPG:] BADSIG ABCD name <email>'
+	fi
+	exit 0
+fi
+cat > /dev/null
+printf '[GNUPG:] BEGIN_SIGNING\\n[GNUPG:] SIG_CREATED \\n' >&2
+printf -- '-----BEGIN PGP SIGNATURE-----\\nsig\\n-----END PGP SIGNATURE-----\\n'",
+		);
+
+		{
+			let mut config = repo.config().unwrap();
+			config.set_bool("commit.gpgsign", true).unwrap();
+			config.set_str("gpg.program", &gpg).unwrap();
+		}
+
+		let good = write_commit_file(&repo, "a.txt", "b", "good");
+		let bad = write_commit_file(&repo, "a.txt", "c", "bad");
+
+		assert_eq!(
+			verify_commit_signature(repo_path, unsigned).unwrap(),
+			SignatureState::Unsigned
+		);
+		assert_eq!(
+			verify_commit_signature(repo_path, good).unwrap(),
+			SignatureState::Good(String::from("name <email>"))
+		);
+		assert_eq!(
+			verify_commit_signature(repo_path, bad).unwrap(),
+			SignatureState::Bad(String::from("name <email>"))
+		);
+	}
+}
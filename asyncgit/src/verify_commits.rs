@@ -0,0 +1,95 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		verify_commit_signature, CommitId, RepoPath, SignatureState,
+	},
+	AsyncGitNotification,
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+/// signature state by commit, `None` if the signature could not be
+/// verified (the error gets logged)
+pub type CommitSignatures = HashMap<CommitId, Option<SignatureState>>;
+
+enum JobState {
+	Request(Vec<CommitId>),
+	Response(CommitSignatures),
+}
+
+/// verifies the signatures of a list of commits
+#[derive(Clone)]
+pub struct AsyncVerifyCommitsJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+///
+impl AsyncVerifyCommitsJob {
+	///
+	pub fn new(repo: RepoPath, commits: Vec<CommitId>) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				commits,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<CommitSignatures> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncVerifyCommitsJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(commits) => JobState::Response(
+					commits
+						.into_iter()
+						.map(|id| {
+							let state = verify_commit_signature(
+								&self.repo, id,
+							)
+							.map_err(|e| {
+								log::error!(
+									"verify commit {id}: {e}"
+								);
+							})
+							.ok();
+
+							(id, state)
+						})
+						.collect(),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::VerifyCommits)
+	}
+}
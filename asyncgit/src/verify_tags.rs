@@ -0,0 +1,90 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{verify_tag_signature, RepoPath, SignatureState},
+	AsyncGitNotification,
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+/// signature state by tag name, `None` if the signature could not be
+/// verified (the error gets logged)
+pub type TagSignatures = HashMap<String, Option<SignatureState>>;
+
+enum JobState {
+	Request(Vec<String>),
+	Response(TagSignatures),
+}
+
+/// verifies the signatures of a list of tags
+#[derive(Clone)]
+pub struct AsyncVerifyTagsJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+///
+impl AsyncVerifyTagsJob {
+	///
+	pub fn new(repo: RepoPath, tags: Vec<String>) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				tags,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<TagSignatures> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncVerifyTagsJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(tags) => JobState::Response(
+					tags.into_iter()
+						.map(|tag| {
+							let state = verify_tag_signature(
+								&self.repo, &tag,
+							)
+							.map_err(|e| {
+								log::error!("verify tag {tag}: {e}");
+							})
+							.ok();
+
+							(tag, state)
+						})
+						.collect(),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::VerifyTags)
+	}
+}
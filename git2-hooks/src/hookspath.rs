@@ -1,12 +1,15 @@
 use git2::Repository;
 
-use crate::{error::Result, HookResult, HooksError};
+use crate::{error::Result, HookControl, HookResult, HooksError};
 
 use std::{
 	ffi::{OsStr, OsString},
+	io::{BufRead, BufReader, Read, Write},
 	path::{Path, PathBuf},
-	process::Command,
+	process::{Child, ChildStdin, Command, Output, Stdio},
 	str::FromStr,
+	sync::atomic::Ordering,
+	time::{Duration, Instant},
 };
 
 pub struct HookPaths {
@@ -18,6 +21,13 @@ pub struct HookPaths {
 const CONFIG_HOOKS_PATH: &str = "core.hooksPath";
 const DEFAULT_HOOKS_PATH: &str = "hooks";
 const ENOEXEC: i32 = 8;
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// why a hook was killed before it exited on its own
+enum Stopped {
+	Cancelled,
+	TimedOut(Duration),
+}
 
 impl HookPaths {
 	/// `core.hooksPath` always takes precedence.
@@ -134,13 +144,40 @@ impl HookPaths {
 
 	/// this function calls hook scripts based on conventions documented here
 	/// see <https://git-scm.com/docs/githooks>
-	pub fn run_hook(&self, args: &[&str]) -> Result<HookResult> {
-		self.run_hook_os_str(args)
+	///
+	/// `stdin` is written to the hook's standard input if provided
+	pub fn run_hook(
+		&self,
+		args: &[&str],
+		stdin: Option<&[u8]>,
+	) -> Result<HookResult> {
+		self.run_hook_os_str(args, stdin)
 	}
 
 	/// this function calls hook scripts based on conventions documented here
 	/// see <https://git-scm.com/docs/githooks>
-	pub fn run_hook_os_str<I, S>(&self, args: I) -> Result<HookResult>
+	///
+	/// `stdin` is written to the hook's standard input if provided
+	pub fn run_hook_os_str<I, S>(
+		&self,
+		args: I,
+		stdin: Option<&[u8]>,
+	) -> Result<HookResult>
+	where
+		I: IntoIterator<Item = S> + Copy,
+		S: AsRef<OsStr>,
+	{
+		self.run_hook_controlled(args, stdin, &HookControl::default())
+	}
+
+	/// like [`Self::run_hook_os_str`] but streams the output of the
+	/// hook and stops it on request or timeout, see [`HookControl`]
+	pub fn run_hook_controlled<I, S>(
+		&self,
+		args: I,
+		stdin: Option<&[u8]>,
+		control: &HookControl,
+	) -> Result<HookResult>
 	where
 		I: IntoIterator<Item = S> + Copy,
 		S: AsRef<OsStr>,
@@ -157,7 +194,15 @@ impl HookPaths {
 				.args(args)
 				.current_dir(&self.pwd)
 				.with_no_window()
-				.output()
+				.stdin(if stdin.is_some() {
+					Stdio::piped()
+				} else {
+					Stdio::null()
+				})
+				.stdout(Stdio::piped())
+				.stderr(Stdio::piped());
+
+			run_controlled(command, stdin, control)
 		};
 
 		let output = if cfg!(windows) {
@@ -209,6 +254,28 @@ impl HookPaths {
 				result => result,
 			}
 		}?;
+		let (output, stopped) = output;
+
+		if let Some(stopped) = stopped {
+			let reason = match stopped {
+				Stopped::Cancelled => String::from("hook cancelled"),
+				Stopped::TimedOut(timeout) => {
+					format!("hook timed out after {timeout:?}")
+				}
+			};
+			log::info!("{reason}: '{}'", hook.display());
+
+			return Ok(HookResult::RunNotSuccessful {
+				code: None,
+				stdout: String::from_utf8_lossy(&output.stdout)
+					.to_string(),
+				stderr: format!(
+					"{}{reason}\n",
+					String::from_utf8_lossy(&output.stderr)
+				),
+				hook,
+			});
+		}
 
 		if output.status.success() {
 			Ok(HookResult::Ok { hook })
@@ -228,6 +295,141 @@ impl HookPaths {
 	}
 }
 
+/// runs `command` feeding it `stdin` while following its output and
+/// watching `control` for a cancel request or timeout
+fn run_controlled(
+	command: &mut Command,
+	stdin: Option<&[u8]>,
+	control: &HookControl,
+) -> std::io::Result<(Output, Option<Stopped>)> {
+	#[cfg(unix)]
+	{
+		use std::os::unix::process::CommandExt as _;
+		// own process group so stopping the hook also stops
+		// whatever it spawned (linters, test runners, ...)
+		command.process_group(0);
+	}
+
+	let mut child = command.spawn()?;
+	let started = Instant::now();
+
+	let child_stdin = child.stdin.take();
+	let child_stdout = child.stdout.take();
+	let child_stderr = child.stderr.take();
+
+	std::thread::scope(|scope| {
+		let writer = scope.spawn(|| write_stdin(child_stdin, stdin));
+		let stdout =
+			scope.spawn(|| read_output(child_stdout, control));
+		let stderr =
+			scope.spawn(|| read_output(child_stderr, control));
+
+		let mut stopped = None;
+		let status = loop {
+			if let Some(status) = child.try_wait()? {
+				break status;
+			}
+
+			if stopped.is_none() {
+				if control.cancel.load(Ordering::Relaxed) {
+					stopped = Some(Stopped::Cancelled);
+				} else if let Some(timeout) = control
+					.timeout
+					.filter(|timeout| started.elapsed() >= *timeout)
+				{
+					stopped = Some(Stopped::TimedOut(timeout));
+				}
+
+				if stopped.is_some() {
+					kill_process_group(&mut child);
+				}
+			}
+
+			std::thread::sleep(POLL_INTERVAL);
+		};
+
+		writer.join().unwrap_or(Ok(()))?;
+
+		Ok((
+			Output {
+				status,
+				stdout: stdout.join().unwrap_or_default(),
+				stderr: stderr.join().unwrap_or_default(),
+			},
+			stopped,
+		))
+	})
+}
+
+fn write_stdin(
+	pipe: Option<ChildStdin>,
+	stdin: Option<&[u8]>,
+) -> std::io::Result<()> {
+	if let (Some(data), Some(mut pipe)) = (stdin, pipe) {
+		// hooks are free to exit without reading their input
+		if let Err(e) = pipe.write_all(data) {
+			if e.kind() != std::io::ErrorKind::BrokenPipe {
+				return Err(e);
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// collects everything read from `pipe`, passing each line on to
+/// `control.output` as soon as it is complete
+fn read_output<R: Read>(
+	pipe: Option<R>,
+	control: &HookControl,
+) -> Vec<u8> {
+	let mut all = Vec::new();
+
+	let Some(pipe) = pipe else {
+		return all;
+	};
+
+	let mut reader = BufReader::new(pipe);
+	let mut line = Vec::new();
+
+	loop {
+		line.clear();
+
+		match reader.read_until(b'\n', &mut line) {
+			Ok(0) | Err(_) => break,
+			Ok(_) => {
+				if let Some(output) = &control.output {
+					output(
+						String::from_utf8_lossy(&line)
+							.trim_end_matches(['\r', '\n']),
+					);
+				}
+				all.extend_from_slice(&line);
+			}
+		}
+	}
+
+	all
+}
+
+fn kill_process_group(child: &mut Child) {
+	#[cfg(unix)]
+	{
+		use rustix::process::{kill_process_group, Pid, Signal};
+
+		// the hook leads its own process group (see `run_controlled`)
+		if let Err(e) =
+			kill_process_group(Pid::from_child(child), Signal::KILL)
+		{
+			log::error!("kill hook process group: {e}");
+		}
+	}
+
+	if let Err(e) = child.kill() {
+		log::error!("kill hook: {e}");
+	}
+}
+
 fn sh_command() -> Command {
 	let mut command = Command::new(gix_path::env::shell());
 
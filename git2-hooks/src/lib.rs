@@ -32,6 +32,8 @@ use std::{
 	fs::File,
 	io::{Read, Write},
 	path::{Path, PathBuf},
+	sync::{atomic::AtomicBool, Arc},
+	time::Duration,
 };
 
 pub use error::HooksError;
@@ -45,6 +47,11 @@ pub const HOOK_PRE_COMMIT: &str = "pre-commit";
 pub const HOOK_COMMIT_MSG: &str = "commit-msg";
 pub const HOOK_PREPARE_COMMIT_MSG: &str = "prepare-commit-msg";
 pub const HOOK_PRE_PUSH: &str = "pre-push";
+pub const HOOK_POST_CHECKOUT: &str = "post-checkout";
+pub const HOOK_POST_MERGE: &str = "post-merge";
+pub const HOOK_PRE_MERGE_COMMIT: &str = "pre-merge-commit";
+pub const HOOK_PRE_REBASE: &str = "pre-rebase";
+pub const HOOK_POST_REWRITE: &str = "post-rewrite";
 
 const HOOK_COMMIT_MSG_TEMP_FILE: &str = "COMMIT_EDITMSG";
 
@@ -70,6 +77,20 @@ pub enum HookResult {
 	},
 }
 
+/// receives every line a hook writes to stdout or stderr
+pub type HookOutput = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// lets the caller follow and stop a (long running) hook
+#[derive(Default, Clone)]
+pub struct HookControl {
+	/// called with every line the hook writes to stdout or stderr
+	pub output: Option<HookOutput>,
+	/// set to kill the hook including the processes it spawned
+	pub cancel: Arc<AtomicBool>,
+	/// kill the hook if it is still running after this long
+	pub timeout: Option<Duration>,
+}
+
 impl HookResult {
 	/// helper to check if result is ok
 	pub const fn is_ok(&self) -> bool {
@@ -82,6 +103,15 @@ impl HookResult {
 	}
 }
 
+/// whether the hook `hook` exists and can be run
+pub fn hook_found(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	hook: &str,
+) -> Result<bool> {
+	Ok(HookPaths::new(repo, other_paths, hook)?.found())
+}
+
 /// helper method to create git hooks programmatically (heavy used in unittests)
 ///
 /// # Panics
@@ -134,7 +164,7 @@ pub fn hooks_commit_msg(
 	let temp_file = hook.git.join(HOOK_COMMIT_MSG_TEMP_FILE);
 	File::create(&temp_file)?.write_all(msg.as_bytes())?;
 
-	let res = hook.run_hook_os_str([&temp_file])?;
+	let res = hook.run_hook_os_str([&temp_file], None)?;
 
 	// load possibly altered msg
 	msg.clear();
@@ -154,7 +184,23 @@ pub fn hooks_pre_commit(
 		return Ok(HookResult::NoHookFound);
 	}
 
-	hook.run_hook(&[])
+	hook.run_hook(&[], None)
+}
+
+/// like [`hooks_pre_commit`] but the hook can be followed and
+/// stopped using `control`
+pub fn hooks_pre_commit_controlled(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	control: &HookControl,
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_PRE_COMMIT)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	hook.run_hook_controlled::<_, &str>([], None, control)
 }
 
 /// this hook is documented here <https://git-scm.com/docs/githooks#_post_commit>
@@ -168,13 +214,49 @@ pub fn hooks_post_commit(
 		return Ok(HookResult::NoHookFound);
 	}
 
-	hook.run_hook(&[])
+	hook.run_hook(&[], None)
+}
+
+/// a ref about to be pushed, passed to the pre-push hook as one
+/// `<local ref> <local oid> <remote ref> <remote oid>` line on stdin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrePushRef {
+	/// `(delete)` if the remote ref gets deleted
+	pub local_ref: String,
+	/// zero if the remote ref gets deleted
+	pub local_oid: git2::Oid,
+	/// ref on the remote to update
+	pub remote_ref: String,
+	/// zero if the remote ref does not exist yet
+	pub remote_oid: git2::Oid,
+}
+
+impl std::fmt::Display for PrePushRef {
+	fn fmt(
+		&self,
+		f: &mut std::fmt::Formatter<'_>,
+	) -> std::fmt::Result {
+		write!(
+			f,
+			"{} {} {} {}",
+			self.local_ref,
+			self.local_oid,
+			self.remote_ref,
+			self.remote_oid
+		)
+	}
 }
 
 /// this hook is documented here <https://git-scm.com/docs/githooks#_pre_push>
+///
+/// the hook gets the `remote` name and its `url` as arguments and a
+/// line per entry in `updates` on stdin
 pub fn hooks_pre_push(
 	repo: &Repository,
 	other_paths: Option<&[&str]>,
+	remote: &str,
+	url: &str,
+	updates: &[PrePushRef],
 ) -> Result<HookResult> {
 	let hook = HookPaths::new(repo, other_paths, HOOK_PRE_PUSH)?;
 
@@ -182,7 +264,133 @@ pub fn hooks_pre_push(
 		return Ok(HookResult::NoHookFound);
 	}
 
-	hook.run_hook(&[])
+	let mut stdin = String::new();
+	for update in updates {
+		stdin.push_str(&update.to_string());
+		stdin.push('\n');
+	}
+
+	hook.run_hook(&[remote, url], Some(stdin.as_bytes()))
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_post_checkout>
+///
+/// `branch_checkout` is `false` for checking out files only
+pub fn hooks_post_checkout(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	prev_head: git2::Oid,
+	new_head: git2::Oid,
+	branch_checkout: bool,
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_POST_CHECKOUT)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	hook.run_hook(
+		&[
+			prev_head.to_string().as_str(),
+			new_head.to_string().as_str(),
+			if branch_checkout { "1" } else { "0" },
+		],
+		None,
+	)
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_post_merge>
+pub fn hooks_post_merge(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	squash: bool,
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_POST_MERGE)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	hook.run_hook(&[if squash { "1" } else { "0" }], None)
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_pre_merge_commit>
+pub fn hooks_pre_merge_commit(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+) -> Result<HookResult> {
+	let hook =
+		HookPaths::new(repo, other_paths, HOOK_PRE_MERGE_COMMIT)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	hook.run_hook(&[], None)
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_pre_rebase>
+///
+/// `branch` is `None` when rebasing the current branch
+pub fn hooks_pre_rebase(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	upstream: &str,
+	branch: Option<&str>,
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_PRE_REBASE)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	let mut args = vec![upstream];
+	if let Some(branch) = branch {
+		args.push(branch);
+	}
+
+	hook.run_hook(args.as_slice(), None)
+}
+
+/// the command that rewrote commits, see [`hooks_post_rewrite`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteCommand {
+	Amend,
+	Rebase,
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_post_rewrite>
+///
+/// `rewritten` holds `(old, new)` commit pairs passed as
+/// `<old> <new>` lines on stdin
+pub fn hooks_post_rewrite(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	command: RewriteCommand,
+	rewritten: &[(git2::Oid, git2::Oid)],
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_POST_REWRITE)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	let stdin = rewritten.iter().fold(
+		String::new(),
+		|mut stdin, (old, new)| {
+			use std::fmt::Write as _;
+			let _ = writeln!(stdin, "{old} {new}");
+			stdin
+		},
+	);
+
+	hook.run_hook(
+		&[match command {
+			RewriteCommand::Amend => "amend",
+			RewriteCommand::Rebase => "rebase",
+		}],
+		Some(stdin.as_bytes()),
+	)
 }
 
 pub enum PrepareCommitMsgSource {
@@ -235,7 +443,7 @@ pub fn hooks_prepare_commit_msg(
 		args.push(id);
 	}
 
-	let res = hook.run_hook(args.as_slice())?;
+	let res = hook.run_hook(args.as_slice(), None)?;
 
 	// load possibly altered msg
 	msg.clear();
@@ -337,7 +545,7 @@ exit 0
 
 		assert!(hook.found());
 
-		let result = hook.run_hook(&[TEXT]).unwrap();
+		let result = hook.run_hook(&[TEXT], None).unwrap();
 
 		let HookResult::RunNotSuccessful {
 			code,
@@ -684,7 +892,8 @@ exit 0
 
 		create_hook(&repo, HOOK_PRE_PUSH, hook);
 
-		let res = hooks_pre_push(&repo, None).unwrap();
+		let res = hooks_pre_push(&repo, None, "origin", "url", &[])
+			.unwrap();
 
 		assert!(matches!(res, HookResult::Ok { .. }));
 	}
@@ -698,7 +907,8 @@ echo 'failed'
 exit 3
 	";
 		create_hook(&repo, HOOK_PRE_PUSH, hook);
-		let res = hooks_pre_push(&repo, None).unwrap();
+		let res = hooks_pre_push(&repo, None, "origin", "url", &[])
+			.unwrap();
 		let HookResult::RunNotSuccessful { code, stdout, .. } = res
 		else {
 			unreachable!()
@@ -706,4 +916,298 @@ exit 3
 		assert_eq!(code.unwrap(), 3);
 		assert_eq!(&stdout, "failed\n");
 	}
+
+	#[test]
+	fn test_pre_push_args_and_stdin() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$1 $2\"
+cat
+exit 1
+	";
+		create_hook(&repo, HOOK_PRE_PUSH, hook);
+
+		let local_oid = git2::Oid::from_str(
+			"1111111111111111111111111111111111111111",
+		)
+		.unwrap();
+		let updates = [
+			PrePushRef {
+				local_ref: String::from("refs/heads/main"),
+				local_oid,
+				remote_ref: String::from("refs/heads/main"),
+				remote_oid: git2::Oid::zero(),
+			},
+			PrePushRef {
+				local_ref: String::from("(delete)"),
+				local_oid: git2::Oid::zero(),
+				remote_ref: String::from("refs/heads/old"),
+				remote_oid: local_oid,
+			},
+		];
+
+		let res = hooks_pre_push(
+			&repo,
+			None,
+			"origin",
+			"https://example.com/repo.git",
+			&updates,
+		)
+		.unwrap();
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+
+		assert_eq!(
+			stdout,
+			format!(
+				"origin https://example.com/repo.git\n\
+				refs/heads/main {local_oid} refs/heads/main {zero}\n\
+				(delete) {zero} refs/heads/old {local_oid}\n",
+				zero = git2::Oid::zero()
+			)
+		);
+	}
+
+	#[test]
+	fn test_pre_push_ignoring_stdin() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+exit 0
+	";
+		create_hook(&repo, HOOK_PRE_PUSH, hook);
+
+		let updates = vec![
+			PrePushRef {
+				local_ref: String::from("refs/heads/main"),
+				local_oid: git2::Oid::zero(),
+				remote_ref: String::from("refs/heads/main"),
+				remote_oid: git2::Oid::zero(),
+			};
+			1000
+		];
+
+		let res =
+			hooks_pre_push(&repo, None, "origin", "url", &updates)
+				.unwrap();
+
+		assert!(res.is_ok());
+	}
+
+	#[test]
+	fn test_post_checkout_args() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$1 $2 $3\"
+exit 1
+	";
+		create_hook(&repo, HOOK_POST_CHECKOUT, hook);
+
+		let prev = git2::Oid::zero();
+		let new = git2::Oid::from_str(
+			"1111111111111111111111111111111111111111",
+		)
+		.unwrap();
+		let res = hooks_post_checkout(&repo, None, prev, new, true)
+			.unwrap();
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+
+		assert_eq!(stdout, format!("{prev} {new} 1\n"));
+	}
+
+	#[test]
+	fn test_post_merge_squash_flag() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$1\"
+exit 1
+	";
+		create_hook(&repo, HOOK_POST_MERGE, hook);
+
+		let res = hooks_post_merge(&repo, None, true).unwrap();
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+
+		assert_eq!(stdout, "1\n");
+	}
+
+	#[test]
+	fn test_pre_merge_commit_reject() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo 'rejected'
+exit 1
+	";
+		create_hook(&repo, HOOK_PRE_MERGE_COMMIT, hook);
+
+		let res = hooks_pre_merge_commit(&repo, None).unwrap();
+		let HookResult::RunNotSuccessful { code, stdout, .. } = res
+		else {
+			unreachable!()
+		};
+
+		assert_eq!(code.unwrap(), 1);
+		assert_eq!(&stdout, "rejected\n");
+	}
+
+	#[test]
+	fn test_pre_rebase_args() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$# $1 $2\"
+exit 1
+	";
+		create_hook(&repo, HOOK_PRE_REBASE, hook);
+
+		let res =
+			hooks_pre_rebase(&repo, None, "origin/master", None)
+				.unwrap();
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+		assert_eq!(stdout, "1 origin/master \n");
+
+		let res =
+			hooks_pre_rebase(&repo, None, "master", Some("feature"))
+				.unwrap();
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+		assert_eq!(stdout, "2 master feature\n");
+	}
+
+	#[test]
+	fn test_post_rewrite_stdin() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$1\"
+cat
+exit 1
+	";
+		create_hook(&repo, HOOK_POST_REWRITE, hook);
+
+		let old = git2::Oid::zero();
+		let new = git2::Oid::from_str(
+			"1111111111111111111111111111111111111111",
+		)
+		.unwrap();
+		let res = hooks_post_rewrite(
+			&repo,
+			None,
+			RewriteCommand::Amend,
+			&[(old, new)],
+		)
+		.unwrap();
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+
+		assert_eq!(stdout, format!("amend\n{old} {new}\n"));
+	}
+
+	#[test]
+	fn test_pre_commit_controlled_streams_output() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo 'line 1'
+echo 'line 2' >&2
+exit 0
+	";
+		create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+		let lines = Arc::new(std::sync::Mutex::new(Vec::new()));
+		let control = HookControl {
+			output: Some(Arc::new({
+				let lines = Arc::clone(&lines);
+				move |line: &str| {
+					lines.lock().unwrap().push(line.to_string());
+				}
+			})),
+			..HookControl::default()
+		};
+
+		let res = hooks_pre_commit_controlled(&repo, None, &control)
+			.unwrap();
+		assert!(res.is_ok());
+
+		let mut lines = lines.lock().unwrap().clone();
+		lines.sort();
+		assert_eq!(lines, vec!["line 1", "line 2"]);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_pre_commit_controlled_timeout_kills_children() {
+		let (_td, repo) = repo_init();
+
+		// the backgrounded sleep keeps stdout open unless the whole
+		// process group gets killed
+		let hook = b"#!/bin/sh
+echo 'started'
+sleep 30 &
+sleep 30
+exit 0
+	";
+		create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+		let control = HookControl {
+			timeout: Some(Duration::from_millis(200)),
+			..HookControl::default()
+		};
+
+		let started = std::time::Instant::now();
+		let res = hooks_pre_commit_controlled(&repo, None, &control)
+			.unwrap();
+		assert!(started.elapsed() < Duration::from_secs(10));
+
+		let HookResult::RunNotSuccessful {
+			code,
+			stdout,
+			stderr,
+			..
+		} = res
+		else {
+			unreachable!()
+		};
+		assert_eq!(code, None);
+		assert_eq!(&stdout, "started\n");
+		assert_eq!(&stderr, "hook timed out after 200ms\n");
+	}
+
+	#[test]
+	fn test_pre_commit_controlled_cancel() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+sleep 30
+exit 0
+	";
+		create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+		let control = HookControl::default();
+		control
+			.cancel
+			.store(true, std::sync::atomic::Ordering::Relaxed);
+
+		let started = std::time::Instant::now();
+		let res = hooks_pre_commit_controlled(&repo, None, &control)
+			.unwrap();
+		assert!(started.elapsed() < Duration::from_secs(10));
+
+		let HookResult::RunNotSuccessful { stderr, .. } = res else {
+			unreachable!()
+		};
+		assert_eq!(&stderr, "hook cancelled\n");
+	}
 }
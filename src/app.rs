@@ -14,15 +14,17 @@ use crate::{
 	options::{Options, SharedOptions},
 	popup_stack::PopupStack,
 	popups::{
-		AppOption, BlameFilePopup, BranchListPopup, CommitPopup,
-		CompareCommitsPopup, ConfirmPopup, CreateBranchPopup,
-		CreateRemotePopup, ExternalEditorPopup, FetchPopup,
-		FileRevlogPopup, FuzzyFindPopup, HelpPopup,
-		InspectCommitPopup, MsgPopup, OptionsPopup, PullPopup,
-		PushPopup, PushTagsPopup, RemoteListPopup, RenameBranchPopup,
-		RenameRemotePopup, ResetPopup, RevisionFilesPopup,
-		StashMsgPopup, SubmodulesListPopup, TagCommitPopup,
-		TagListPopup, UpdateRemoteUrlPopup,
+		AppOption, BlameFilePopup, BranchListPopup, ClonePopup,
+		CommitPopup, CompareBranchesPopup, CompareCommitsPopup,
+		ConfirmPopup, CreateBranchPopup, CreateRemotePopup,
+		ExternalEditorPopup, FetchPopup, FileRevlogPopup,
+		FuzzyFindPopup, HelpPopup, InspectCommitPopup, MergePopup,
+		MsgPopup, NotePopup, OptionsPopup, PullPopup, PushPopup,
+		PushTagsPopup, PushTarget, PushToPopup, RemoteListPopup,
+		RenameBranchPopup, RenameRemotePopup, ResetPopup,
+		RevisionFilesPopup, StashMsgPopup, SubmodulesListPopup,
+		TagCommitPopup, TagListPopup, UpdateRemoteUrlPopup,
+		UpstreamPopup,
 	},
 	queue::{
 		Action, AppTabs, InternalEvent, NeedsUpdate, Queue,
@@ -37,12 +39,14 @@ use crate::{
 };
 use anyhow::{bail, Result};
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	describe::AsyncDescribeJob,
 	sync::{
 		self,
 		utils::{repo_work_dir, undo_last_commit},
-		RepoPath, RepoPathRef,
+		CommitId, RepoPath, RepoPathRef,
 	},
-	AsyncGitNotification, PushType,
+	AsyncGitNotification, PushForce, PushType,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
@@ -81,16 +85,22 @@ pub struct App {
 	stashmsg_popup: StashMsgPopup,
 	inspect_commit_popup: InspectCommitPopup,
 	compare_commits_popup: CompareCommitsPopup,
+	compare_branches_popup: CompareBranchesPopup,
 	external_editor_popup: ExternalEditorPopup,
 	revision_files_popup: RevisionFilesPopup,
 	fuzzy_find_popup: FuzzyFindPopup,
 	#[cfg(not(feature = "disable-log-files-tabs"))]
 	log_search_popup: LogSearchPopupPopup,
 	push_popup: PushPopup,
+	push_to_popup: PushToPopup,
+	upstream_popup: UpstreamPopup,
+	merge_popup: MergePopup,
 	push_tags_popup: PushTagsPopup,
 	pull_popup: PullPopup,
 	fetch_popup: FetchPopup,
+	clone_popup: ClonePopup,
 	tag_commit_popup: TagCommitPopup,
+	note_popup: NotePopup,
 	create_branch_popup: CreateBranchPopup,
 	create_remote_popup: CreateRemotePopup,
 	rename_remote_popup: RenameRemotePopup,
@@ -118,6 +128,10 @@ pub struct App {
 	popup_stack: PopupStack,
 	options: SharedOptions,
 	repo_path_text: String,
+	/// `git describe` of HEAD, shown next to the repo path
+	head_describe: Option<String>,
+	described_head: Option<CommitId>,
+	async_describe: AsyncSingleJob<AsyncDescribeJob>,
 
 	// "Flags"
 	requires_redraw: Cell<bool>,
@@ -193,13 +207,19 @@ impl App {
 			stashmsg_popup: StashMsgPopup::new(&env),
 			inspect_commit_popup: InspectCommitPopup::new(&env),
 			compare_commits_popup: CompareCommitsPopup::new(&env),
+			compare_branches_popup: CompareBranchesPopup::new(&env),
 			external_editor_popup: ExternalEditorPopup::new(&env),
 			push_popup: PushPopup::new(&env),
+			push_to_popup: PushToPopup::new(&env),
+			upstream_popup: UpstreamPopup::new(&env),
+			merge_popup: MergePopup::new(&env),
 			push_tags_popup: PushTagsPopup::new(&env),
 			reset_popup: ResetPopup::new(&env),
 			pull_popup: PullPopup::new(&env),
 			fetch_popup: FetchPopup::new(&env),
+			clone_popup: ClonePopup::new(&env),
 			tag_commit_popup: TagCommitPopup::new(&env),
+			note_popup: NotePopup::new(&env),
 			create_branch_popup: CreateBranchPopup::new(&env),
 			create_remote_popup: CreateRemotePopup::new(&env),
 			rename_remote_popup: RenameRemotePopup::new(&env),
@@ -213,6 +233,9 @@ impl App {
 			#[cfg(not(feature = "disable-log-files-tabs"))]
 			log_search_popup: LogSearchPopupPopup::new(&env),
 			fuzzy_find_popup: FuzzyFindPopup::new(&env),
+			async_describe: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
 			do_quit: QuitState::None,
 			cmdbar: RefCell::new(CommandBar::new(
 				env.theme.clone(),
@@ -236,6 +259,8 @@ impl App {
 			file_to_open: None,
 			repo: env.repo,
 			repo_path_text,
+			head_describe: None,
+			described_head: None,
 			popup_stack: PopupStack::default(),
 		};
 
@@ -281,6 +306,7 @@ impl App {
 			self.revision_files_popup.is_visible()
 				|| self.inspect_commit_popup.is_visible()
 				|| self.compare_commits_popup.is_visible()
+				|| self.compare_branches_popup.is_visible()
 				|| self.blame_file_popup.is_visible()
 				|| self.file_revlog_popup.is_visible();
 
@@ -375,6 +401,12 @@ impl App {
 				) {
 					self.options_popup.show()?;
 					NeedsUpdate::ALL
+				} else if key_match(
+					k,
+					self.key_config.keys.clone_repo,
+				) {
+					self.clone_popup.open()?;
+					NeedsUpdate::ALL
 				} else {
 					NeedsUpdate::empty()
 				};
@@ -386,17 +418,20 @@ impl App {
 		} else if let InputEvent::State(polling_state) = ev {
 			self.external_editor_popup.hide();
 			if matches!(polling_state, InputState::Paused) {
-				let result =
-					if let Some(path) = self.file_to_open.take() {
-						ExternalEditorPopup::open_file_in_editor(
-							&self.repo.borrow(),
-							Path::new(&path),
-						)
-					} else {
-						let changes =
-							self.status_tab.get_files_changes()?;
-						self.commit_popup.show_editor(changes)
-					};
+				let result = if let Some(path) =
+					self.file_to_open.take()
+				{
+					ExternalEditorPopup::open_file_in_editor(
+						&self.repo.borrow(),
+						Path::new(&path),
+					)
+				} else if self.note_popup.is_editing_externally() {
+					self.note_popup.show_editor()
+				} else {
+					let changes =
+						self.status_tab.get_files_changes()?;
+					self.commit_popup.show_editor(changes)
+				};
 
 				if let Err(e) = result {
 					let msg =
@@ -418,8 +453,58 @@ impl App {
 	pub fn update(&mut self) -> Result<()> {
 		log::trace!("update");
 
-		self.commit_popup.update();
 		self.status_tab.update()?;
+		self.update_head_describe();
+		self.update_tabs()
+	}
+
+	/// update after the watcher reported changes of worktree files only
+	pub fn update_changed_paths(
+		&mut self,
+		paths: &[String],
+	) -> Result<()> {
+		log::trace!("update changed paths: {}", paths.len());
+
+		self.status_tab.update_changed_paths(paths)?;
+		self.update_tabs()
+	}
+
+	/// describe HEAD in the background whenever it moved
+	fn update_head_describe(&mut self) {
+		let head = sync::get_head(&self.repo.borrow()).ok();
+
+		if head != self.described_head {
+			self.described_head = head;
+
+			if let Some(head) = head {
+				self.async_describe.spawn(AsyncDescribeJob::new(
+					self.repo.borrow().clone(),
+					head,
+				));
+			} else {
+				self.head_describe = None;
+			}
+		}
+	}
+
+	fn update_head_describe_result(&mut self) {
+		if let Some(job) = self.async_describe.take_last() {
+			if Some(job.commit()) == self.described_head {
+				match job.result() {
+					Some(Ok(describe)) => {
+						self.head_describe = describe;
+					}
+					Some(Err(e)) => {
+						log::error!("describe error: {e}");
+					}
+					None => (),
+				}
+			}
+		}
+	}
+
+	fn update_tabs(&mut self) -> Result<()> {
+		self.commit_popup.update();
 		#[cfg(not(feature = "disable-log-files-tabs"))]
 		{
 			self.revlog.update()?;
@@ -451,11 +536,18 @@ impl App {
 			self.file_revlog_popup.update_git(ev)?;
 			self.inspect_commit_popup.update_git(ev)?;
 			self.compare_commits_popup.update_git(ev)?;
+			self.compare_branches_popup.update_git(ev)?;
 			self.push_popup.update_git(ev)?;
 			self.push_tags_popup.update_git(ev)?;
 			self.pull_popup.update_git(ev);
 			self.fetch_popup.update_git(ev);
+			self.clone_popup.update_git(ev);
+			self.commit_popup.update_git(ev);
 			self.select_branch_popup.update_git(ev)?;
+
+			if ev == AsyncGitNotification::Describe {
+				self.update_head_describe_result();
+			}
 		}
 
 		#[cfg(not(feature = "disable-log-files-tabs"))]
@@ -490,13 +582,17 @@ impl App {
 			|| self.file_revlog_popup.any_work_pending()
 			|| self.inspect_commit_popup.any_work_pending()
 			|| self.compare_commits_popup.any_work_pending()
+			|| self.compare_branches_popup.any_work_pending()
 			|| self.input.is_state_changing()
 			|| self.push_popup.any_work_pending()
 			|| self.push_tags_popup.any_work_pending()
 			|| self.pull_popup.any_work_pending()
 			|| self.fetch_popup.any_work_pending()
+			|| self.clone_popup.any_work_pending()
+			|| self.commit_popup.any_work_pending()
 			|| self.revision_files_popup.any_work_pending()
-			|| self.tags_popup.any_work_pending();
+			|| self.tags_popup.any_work_pending()
+			|| self.async_describe.is_pending();
 
 		#[cfg(not(feature = "disable-log-files-tabs"))]
 		let pending = base_pending
@@ -536,18 +632,24 @@ impl App {
 			stashmsg_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
+			compare_branches_popup,
 			external_editor_popup,
 			push_popup,
+			push_to_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
+			clone_popup,
 			tag_commit_popup,
+			note_popup,
 			reset_popup,
 			create_branch_popup,
 			create_remote_popup,
 			rename_remote_popup,
 			update_remote_url_popup,
 			remotes_popup,
+			upstream_popup,
+			merge_popup,
 			rename_branch_popup,
 			select_branch_popup,
 			revision_files_popup,
@@ -576,18 +678,24 @@ impl App {
 			stashmsg_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
+			compare_branches_popup,
 			external_editor_popup,
 			push_popup,
+			push_to_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
+			clone_popup,
 			tag_commit_popup,
+			note_popup,
 			reset_popup,
 			create_branch_popup,
 			create_remote_popup,
 			rename_remote_popup,
 			update_remote_url_popup,
 			remotes_popup,
+			upstream_popup,
+			merge_popup,
 			rename_branch_popup,
 			select_branch_popup,
 			revision_files_popup,
@@ -610,12 +718,16 @@ impl App {
 			help_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
+			compare_branches_popup,
 			blame_file_popup,
 			file_revlog_popup,
 			external_editor_popup,
 			tag_commit_popup,
+			note_popup,
 			select_branch_popup,
 			remotes_popup,
+			upstream_popup,
+			merge_popup,
 			create_remote_popup,
 			rename_remote_popup,
 			update_remote_url_popup,
@@ -628,9 +740,11 @@ impl App {
 			fuzzy_find_popup,
 			log_search_popup,
 			push_popup,
+			push_to_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
+			clone_popup,
 			options_popup,
 			confirm_popup,
 			msg_popup
@@ -646,12 +760,16 @@ impl App {
 			help_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
+			compare_branches_popup,
 			blame_file_popup,
 			file_revlog_popup,
 			external_editor_popup,
 			tag_commit_popup,
+			note_popup,
 			select_branch_popup,
 			remotes_popup,
+			upstream_popup,
+			merge_popup,
 			create_remote_popup,
 			rename_remote_popup,
 			update_remote_url_popup,
@@ -663,9 +781,11 @@ impl App {
 			revision_files_popup,
 			fuzzy_find_popup,
 			push_popup,
+			push_to_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
+			clone_popup,
 			options_popup,
 			confirm_popup,
 			msg_popup
@@ -817,6 +937,8 @@ impl App {
 		flags.insert(new_flags);
 
 		if flags.contains(NeedsUpdate::ALL) {
+			// tags might have been added or removed
+			self.described_head = None;
 			self.update()?;
 		}
 		//TODO: make this a queue event?
@@ -825,6 +947,7 @@ impl App {
 			self.status_tab.update_diff()?;
 			self.inspect_commit_popup.update_diff()?;
 			self.compare_commits_popup.update_diff()?;
+			self.compare_branches_popup.update_diff()?;
 			self.file_revlog_popup.update_diff()?;
 		}
 		if flags.contains(NeedsUpdate::COMMANDS) {
@@ -910,10 +1033,16 @@ impl App {
 			InternalEvent::RewordCommit(id) => {
 				self.commit_popup.open(Some(id))?;
 			}
+			InternalEvent::EditNote(id) => {
+				self.note_popup.open(id)?;
+			}
 			InternalEvent::PopupStashing(opts) => {
 				self.stashmsg_popup.options(opts);
 				self.stashmsg_popup.show()?;
 			}
+			InternalEvent::RenameStash(id, msg) => {
+				self.stashmsg_popup.open_rename(id, &msg)?;
+			}
 			InternalEvent::TagCommit(id) => {
 				self.tag_commit_popup.open(id)?;
 			}
@@ -933,6 +1062,9 @@ impl App {
 			InternalEvent::CreateBranch => {
 				self.create_branch_popup.open()?;
 			}
+			InternalEvent::StashBranch(id) => {
+				self.create_branch_popup.open_from_stash(id)?;
+			}
 			InternalEvent::RenameBranch(branch_ref, cur_name) => {
 				self.rename_branch_popup
 					.open(branch_ref, cur_name)?;
@@ -975,6 +1107,30 @@ impl App {
 					.push(branch, push_type, force, delete)?;
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::OpenPushTo(branch) => {
+				self.push_to_popup.open(branch)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::PushTo(branch, target) => {
+				self.push_popup.push_to(branch, target)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenCompareBranches(a, b) => {
+				self.compare_branches_popup.open(&a, &b)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenMerge(branch, branch_type) => {
+				self.merge_popup.open(branch, branch_type)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenUpstream(branch, upstream) => {
+				self.upstream_popup.open(branch, upstream)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::Pull(branch) => {
 				if let Err(error) = self.pull_popup.fetch(branch) {
 					self.queue.push(InternalEvent::ShowErrorMsg(
@@ -1127,20 +1283,23 @@ impl App {
 					&lines,
 				)?;
 			}
-			Action::DeleteLocalBranch(branch_ref) => {
-				if let Err(e) = sync::delete_branch(
-					&self.repo.borrow(),
-					&branch_ref,
-				) {
-					self.queue.push(InternalEvent::ShowErrorMsg(
-						e.to_string(),
-					));
+			Action::DeleteLocalBranch(branch_refs) => {
+				for branch_ref in branch_refs {
+					if let Err(e) = sync::delete_branch(
+						&self.repo.borrow(),
+						&branch_ref,
+					) {
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							e.to_string(),
+						));
+						break;
+					}
 				}
 
 				self.select_branch_popup.update_branches()?;
 			}
-			Action::DeleteRemoteBranch(branch_ref) => {
-				self.delete_remote_branch(&branch_ref)?;
+			Action::DeleteRemoteBranch(branch_refs) => {
+				self.delete_remote_branches(&branch_refs)?;
 			}
 			Action::DeleteRemote(remote_name) => {
 				self.delete_remote(&remote_name);
@@ -1152,7 +1311,7 @@ impl App {
 				self.queue.push(InternalEvent::Push(
 					tag_name,
 					PushType::Tag,
-					false,
+					PushForce::None,
 					true,
 				));
 			}
@@ -1206,28 +1365,40 @@ impl App {
 		Ok(())
 	}
 
-	fn delete_remote_branch(
+	fn delete_remote_branches(
 		&mut self,
-		branch_ref: &str,
+		branch_refs: &[String],
 	) -> Result<()> {
-		self.queue.push(
-			//TODO: check if this is correct based on the fix in `c6abbaf`
-			branch_ref.rsplit('/').next().map_or_else(
-				|| {
-					InternalEvent::ShowErrorMsg(format!(
-						    "Failed to find the branch name in {branch_ref}"
-					    ))
-				},
-				|name| {
-					InternalEvent::Push(
-						name.to_string(),
-						PushType::Branch,
-						false,
-						true,
-					)
-				},
-			),
-		);
+		let targets = branch_refs
+			.iter()
+			.map(|branch_ref| {
+				let (remote, merge) =
+					sync::get_remote_branch_upstream(
+						&self.repo.borrow(),
+						branch_ref,
+					)?;
+				let dst_branch = merge
+					.strip_prefix("refs/heads/")
+					.map_or_else(|| merge.clone(), String::from);
+
+				Ok(PushTarget {
+					remote,
+					dst_branch,
+					set_upstream: false,
+				})
+			})
+			.collect::<asyncgit::Result<Vec<_>>>();
+
+		match targets {
+			Ok(targets) => {
+				self.push_popup.delete_remote_branches(targets)?;
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("delete remote branch error:\n{e}"),
+				));
+			}
+		}
 
 		self.select_branch_popup.update_branches()?;
 
@@ -1302,6 +1473,14 @@ impl App {
 			)
 			.order(order::NAV),
 		);
+		res.push(
+			CommandInfo::new(
+				strings::commands::clone_repo(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
 		res.push(
 			CommandInfo::new(
 				strings::commands::toggle_key_help(&self.key_config),
@@ -1396,14 +1575,26 @@ impl App {
 
 		f.render_widget(tabs_widget, table_area);
 
+		let describe = self
+			.head_describe
+			.as_ref()
+			.map_or_else(String::new, |describe| {
+				format!("{describe} ")
+			});
+		let path_width = (text_area.width as usize)
+			.saturating_sub(describe.width());
+
 		f.render_widget(
-			Paragraph::new(Line::from(vec![Span::styled(
-				ellipsis_trim_start(
-					&self.repo_path_text,
-					text_area.width as usize,
+			Paragraph::new(Line::from(vec![
+				Span::styled(describe, self.theme.commit_hash(false)),
+				Span::styled(
+					ellipsis_trim_start(
+						&self.repo_path_text,
+						path_width,
+					),
+					self.theme.title(false),
 				),
-				self.theme.title(false),
-			)]))
+			]))
 			.alignment(Alignment::Right),
 			text_area,
 		);
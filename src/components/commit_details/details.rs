@@ -12,8 +12,15 @@ use crate::{
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::sync::{
-	self, CommitDetails, CommitId, CommitMessage, RepoPathRef, Tag,
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	describe::AsyncDescribeJob,
+	sync::{
+		self, CommitDetails, CommitId, CommitMessage, RepoPathRef,
+		SignatureState, Tag,
+	},
+	verify_commits::AsyncVerifyCommitsJob,
+	AsyncGitNotification,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -27,10 +34,26 @@ use sync::CommitTags;
 
 use super::style::Detail;
 
+/// signature of the shown commit
+enum Signature {
+	Verifying,
+	/// verifying failed, the error got logged
+	Failed,
+	Verified(SignatureState),
+}
+
 pub struct DetailsComponent {
 	repo: RepoPathRef,
+	commit: Option<CommitId>,
 	data: Option<CommitDetails>,
 	tags: Vec<Tag>,
+	signature: Signature,
+	async_signature: AsyncSingleJob<AsyncVerifyCommitsJob>,
+	/// closest tag of the shown commit, see `git describe`
+	describe: Option<String>,
+	/// git note of the shown commit
+	note: Option<String>,
+	async_describe: AsyncSingleJob<AsyncDescribeJob>,
 	theme: SharedTheme,
 	focused: bool,
 	current_width: Cell<u16>,
@@ -47,8 +70,18 @@ impl DetailsComponent {
 	pub fn new(env: &Environment, focused: bool) -> Self {
 		Self {
 			repo: env.repo.clone(),
+			commit: None,
 			data: None,
 			tags: Vec::new(),
+			signature: Signature::Verifying,
+			async_signature: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			describe: None,
+			note: None,
+			async_describe: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
 			theme: env.theme.clone(),
 			focused,
 			scroll_to_bottom_next_draw: Cell::new(false),
@@ -65,9 +98,35 @@ impl DetailsComponent {
 	) {
 		self.tags.clear();
 
+		let changed = self.commit != id;
+		self.commit = id;
+
 		self.data = id.and_then(|id| {
 			sync::get_commit_details(&self.repo.borrow(), id).ok()
 		});
+		self.note = id.and_then(|id| {
+			sync::get_note(&self.repo.borrow(), id, None)
+				.ok()
+				.flatten()
+		});
+
+		if changed {
+			self.signature = Signature::Verifying;
+			self.describe = None;
+
+			if let Some(id) = id {
+				self.async_signature.spawn(
+					AsyncVerifyCommitsJob::new(
+						self.repo.borrow().clone(),
+						vec![id],
+					),
+				);
+				self.async_describe.spawn(AsyncDescribeJob::new(
+					self.repo.borrow().clone(),
+					id,
+				));
+			}
+		}
 
 		self.scroll.reset();
 
@@ -76,6 +135,72 @@ impl DetailsComponent {
 		}
 	}
 
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_signature.is_pending()
+			|| self.async_describe.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		match ev {
+			AsyncGitNotification::VerifyCommits => {
+				if let Some(signatures) = self
+					.async_signature
+					.take_last()
+					.and_then(|job| job.result())
+				{
+					if let Some(signature) =
+						self.commit.and_then(|id| signatures.get(&id))
+					{
+						self.signature = signature.clone().map_or(
+							Signature::Failed,
+							Signature::Verified,
+						);
+					}
+				}
+			}
+			AsyncGitNotification::Describe => {
+				if let Some(job) = self.async_describe.take_last() {
+					if Some(job.commit()) == self.commit {
+						match job.result() {
+							Some(Ok(describe)) => {
+								self.describe = describe;
+							}
+							Some(Err(e)) => {
+								log::error!("describe error: {e}");
+							}
+							None => (),
+						}
+					}
+				}
+			}
+			_ => (),
+		}
+	}
+
+	fn signature_line(&self) -> Line<'_> {
+		let (text, style) = match &self.signature {
+			Signature::Verifying => (
+				strings::commit::details_signature_verifying(),
+				self.theme.text(false, false),
+			),
+			Signature::Failed => (
+				strings::commit::details_signature_failed(),
+				self.theme.text_danger(),
+			),
+			Signature::Verified(state) => (
+				strings::signature_state(state),
+				self.theme.signature(state, false),
+			),
+		};
+
+		Line::from(vec![
+			style_detail(&self.theme, &Detail::Signature),
+			Span::styled(Cow::from(text), style),
+		])
+	}
+
 	fn wrap_commit_details(
 		message: &CommitMessage,
 		width: usize,
@@ -101,25 +226,47 @@ impl DetailsComponent {
 		}
 	}
 
-	fn get_wrapped_lines(
-		data: Option<&CommitDetails>,
+	/// indented below the message, like `git log` does
+	fn wrap_note(note: &str, width: usize) -> Vec<Cow<'_, str>> {
+		const INDENT: &str = "    ";
+
+		let width = width.saturating_sub(INDENT.len()).max(1);
+
+		[Cow::from(""), Cow::from(strings::commit::details_notes())]
+			.into_iter()
+			.chain(
+				bwrap::wrap!(note.trim_end(), width)
+					.lines()
+					.map(|line| Cow::from(format!("{INDENT}{line}"))),
+			)
+			.collect()
+	}
+
+	fn get_wrapped_lines<'a>(
+		data: Option<&'a CommitDetails>,
+		note: Option<&'a str>,
 		width: usize,
-	) -> WrappedCommitMessage<'_> {
-		if let Some(data) = data {
-			if let Some(message) = &data.message {
-				return Self::wrap_commit_details(message, width);
-			}
+	) -> WrappedCommitMessage<'a> {
+		let (wrapped_title, mut wrapped_message) =
+			data.and_then(|data| data.message.as_ref()).map_or_else(
+				|| (vec![], vec![]),
+				|message| Self::wrap_commit_details(message, width),
+			);
+
+		if let Some(note) = note {
+			wrapped_message.extend(Self::wrap_note(note, width));
 		}
 
-		(vec![], vec![])
+		(wrapped_title, wrapped_message)
 	}
 
 	fn get_number_of_lines(
 		details: Option<&CommitDetails>,
+		note: Option<&str>,
 		width: usize,
 	) -> usize {
 		let (wrapped_title, wrapped_message) =
-			Self::get_wrapped_lines(details, width);
+			Self::get_wrapped_lines(details, note, width);
 
 		wrapped_title.len() + wrapped_message.len()
 	}
@@ -138,7 +285,11 @@ impl DetailsComponent {
 		height: usize,
 	) -> Vec<Line<'_>> {
 		let (wrapped_title, wrapped_message) =
-			Self::get_wrapped_lines(self.data.as_ref(), width);
+			Self::get_wrapped_lines(
+				self.data.as_ref(),
+				self.note.as_deref(),
+				width,
+			);
 
 		[&wrapped_title[..], &wrapped_message[..]]
 			.concat()
@@ -215,8 +366,20 @@ impl DetailsComponent {
 					Cow::from(data.hash.clone()),
 					self.theme.text(true, false),
 				),
+				Span::styled(
+					Cow::from(
+						self.describe
+							.as_ref()
+							.map_or_else(String::new, |describe| {
+								format!(" ({describe})")
+							}),
+					),
+					self.theme.text(false, false),
+				),
 			]));
 
+			res.push(self.signature_line());
+
 			if !self.tags.is_empty() {
 				res.push(Line::from(style_detail(
 					&self.theme,
@@ -261,7 +424,7 @@ impl DrawableComponent for DetailsComponent {
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
-				[Constraint::Length(8), Constraint::Min(10)].as_ref(),
+				[Constraint::Length(9), Constraint::Min(10)].as_ref(),
 			)
 			.split(rect);
 
@@ -288,6 +451,7 @@ impl DrawableComponent for DetailsComponent {
 
 		let number_of_lines = Self::get_number_of_lines(
 			self.data.as_ref(),
+			self.note.as_deref(),
 			usize::from(width),
 		);
 
@@ -341,8 +505,11 @@ impl Component for DetailsComponent {
 		force_all: bool,
 	) -> CommandBlocking {
 		let width = usize::from(self.current_width.get());
-		let number_of_lines =
-			Self::get_number_of_lines(self.data.as_ref(), width);
+		let number_of_lines = Self::get_number_of_lines(
+			self.data.as_ref(),
+			self.note.as_deref(),
+			width,
+		);
 
 		out.push(
 			CommandInfo::new(
@@ -503,14 +670,24 @@ mod test_line_count {
 		};
 		let lines = DetailsComponent::get_number_of_lines(
 			Some(commit.clone()).as_ref(),
+			None,
 			50,
 		);
 		assert_eq!(lines, 2);
 
 		let lines = DetailsComponent::get_number_of_lines(
-			Some(commit).as_ref(),
+			Some(commit.clone()).as_ref(),
+			None,
 			8,
 		);
 		assert_eq!(lines, 4);
+
+		// empty line, "Notes:" and the indented note
+		let lines = DetailsComponent::get_number_of_lines(
+			Some(commit).as_ref(),
+			Some("reviewed\n"),
+			50,
+		);
+		assert_eq!(lines, 5);
 	}
 }
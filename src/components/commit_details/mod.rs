@@ -15,7 +15,7 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	sync::{commit_files::OldNew, CommitTags},
-	AsyncCommitFiles, CommitFilesParams,
+	AsyncCommitFiles, AsyncGitNotification, CommitFilesParams,
 };
 use compare_details::CompareDetailsComponent;
 use crossterm::event::Event;
@@ -113,6 +113,12 @@ impl CommitDetailsComponent {
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.git_commit_files.is_pending()
+			|| self.single_details.any_work_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		self.single_details.update_git(ev);
 	}
 
 	///
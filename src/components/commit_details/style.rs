@@ -8,6 +8,7 @@ pub enum Detail {
 	Committer,
 	Sha,
 	Message,
+	Signature,
 }
 
 pub fn style_detail<'a>(
@@ -35,5 +36,9 @@ pub fn style_detail<'a>(
 			Cow::from(strings::commit::details_message()),
 			theme.text(false, false),
 		),
+		Detail::Signature => Span::styled(
+			Cow::from(strings::commit::details_signature()),
+			theme.text(false, false),
+		),
 	}
 }
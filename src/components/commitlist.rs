@@ -9,15 +9,18 @@ use crate::{
 	},
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, Queue},
+	report_hook,
 	strings::{self, symbol},
-	try_or_popup,
 	ui::style::{SharedTheme, Theme},
 	ui::{calc_scroll_top, draw_scrollbar, Orientation},
 };
 use anyhow::Result;
-use asyncgit::sync::{
-	self, checkout_commit, BranchDetails, BranchInfo, CommitId,
-	RepoPathRef, Tags,
+use asyncgit::{
+	sync::{
+		self, checkout_commit, BranchDetails, BranchInfo, CommitId,
+		RepoPathRef, Tags,
+	},
+	verify_commits::CommitSignatures,
 };
 use chrono::{DateTime, Local};
 use crossterm::event::Event;
@@ -31,7 +34,11 @@ use ratatui::{
 	Frame,
 };
 use std::{
-	borrow::Cow, cell::Cell, cmp, collections::BTreeMap, rc::Rc,
+	borrow::Cow,
+	cell::Cell,
+	cmp,
+	collections::{BTreeMap, HashSet},
+	rc::Rc,
 	time::Instant,
 };
 
@@ -55,6 +62,10 @@ pub struct CommitList {
 	tags: Option<Tags>,
 	local_branches: BTreeMap<CommitId, Vec<BranchInfo>>,
 	remote_branches: BTreeMap<CommitId, Vec<BranchInfo>>,
+	/// commits whose parents are cut off by a shallow clone
+	shallow_boundary: HashSet<CommitId>,
+	/// verified signatures, `None` if they are not shown
+	signatures: Option<CommitSignatures>,
 	current_size: Cell<Option<(u16, u16)>>,
 	scroll_top: Cell<usize>,
 	theme: SharedTheme,
@@ -77,6 +88,8 @@ impl CommitList {
 			tags: None,
 			local_branches: BTreeMap::default(),
 			remote_branches: BTreeMap::default(),
+			shallow_boundary: HashSet::new(),
+			signatures: None,
 			current_size: Cell::new(None),
 			scroll_top: Cell::new(0),
 			theme: env.theme.clone(),
@@ -86,6 +99,44 @@ impl CommitList {
 		}
 	}
 
+	/// show the signature state of each commit,
+	/// see [`Self::set_signatures`]
+	#[must_use]
+	pub fn with_signatures(mut self) -> Self {
+		self.signatures = Some(CommitSignatures::new());
+		self
+	}
+
+	///
+	pub fn set_signatures(&mut self, signatures: CommitSignatures) {
+		if let Some(known) = &mut self.signatures {
+			known.extend(signatures);
+		}
+	}
+
+	/// commits on screen whose signature was not verified yet
+	pub fn unverified_visible_commits(&self) -> Vec<CommitId> {
+		let Some(signatures) = &self.signatures else {
+			return Vec::new();
+		};
+
+		let height =
+			self.current_size().map_or(0, |size| usize::from(size.1));
+		let top = calc_scroll_top(
+			self.scroll_top.get(),
+			height,
+			self.relative_selection(),
+		);
+
+		self.items
+			.iter()
+			.skip(top)
+			.take(height)
+			.map(|e| e.id)
+			.filter(|id| !signatures.contains_key(id))
+			.collect()
+	}
+
 	///
 	pub const fn tags(&self) -> Option<&Tags> {
 		self.tags.as_ref()
@@ -107,6 +158,19 @@ impl CommitList {
 		self.tags = Some(tags);
 	}
 
+	/// returns `true` if the boundary changed
+	pub fn set_shallow_boundary(
+		&mut self,
+		shallow_boundary: HashSet<CommitId>,
+	) -> bool {
+		if self.shallow_boundary == shallow_boundary {
+			return false;
+		}
+
+		self.shallow_boundary = shallow_boundary;
+		true
+	}
+
 	///
 	pub fn selected_entry(&self) -> Option<&LogEntry> {
 		self.items.iter().nth(
@@ -169,10 +233,25 @@ impl CommitList {
 		if let Some(commit_hash) =
 			self.selected_entry().map(|entry| entry.id)
 		{
-			try_or_popup!(
-				self,
-				"failed to checkout commit:",
+			let prev_head = sync::get_head(&self.repo.borrow()).ok();
+
+			if let Err(e) =
 				checkout_commit(&self.repo.borrow(), commit_hash)
+			{
+				log::error!("failed to checkout commit: {e}");
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("failed to checkout commit:\n{e}"),
+				));
+				return;
+			}
+
+			report_hook!(
+				self,
+				"post-checkout",
+				sync::hooks_post_checkout(
+					&self.repo.borrow(),
+					prev_head
+				)
 			);
 		}
 	}
@@ -520,6 +599,9 @@ impl CommitList {
 
 		txt.push(splitter.clone());
 
+		// signature
+		self.push_signature(&mut txt, &splitter, e, selected);
+
 		// commit timestamp
 		txt.push(Span::styled(
 			Cow::from(e.time_to_string(now)),
@@ -552,6 +634,14 @@ impl CommitList {
 			txt.push(Span::styled(remote_branches, style_branches));
 		}
 
+		if self.shallow_boundary.contains(&e.id) {
+			txt.push(splitter.clone());
+			txt.push(Span::styled(
+				Cow::from(strings::shallow_boundary()),
+				theme.commit_unhighlighted(),
+			));
+		}
+
 		txt.push(splitter);
 
 		let message_width = width.saturating_sub(
@@ -567,6 +657,28 @@ impl CommitList {
 		Line::from(txt)
 	}
 
+	fn push_signature<'a>(
+		&self,
+		txt: &mut Vec<Span<'a>>,
+		splitter: &Span<'a>,
+		e: &LogEntry,
+		selected: bool,
+	) {
+		if let Some(signatures) = &self.signatures {
+			let state =
+				signatures.get(&e.id).and_then(Option::as_ref);
+
+			txt.push(Span::styled(
+				Cow::from(strings::signature_symbol(state)),
+				state.map_or_else(
+					|| self.theme.text(true, selected),
+					|state| self.theme.signature(state, selected),
+				),
+			));
+			txt.push(splitter.clone());
+		}
+	}
+
 	fn get_text(&self, height: usize, width: usize) -> Vec<Line<'_>> {
 		let selection = self.relative_selection();
 
@@ -917,6 +1029,8 @@ mod tests {
 				scroll_top: Cell::default(),
 				local_branches: BTreeMap::default(),
 				remote_branches: BTreeMap::default(),
+				shallow_boundary: HashSet::new(),
+				signatures: None,
 				theme: SharedTheme::default(),
 				key_config: SharedKeyConfig::default(),
 				scroll_state: (Instant::now(), 0.0),
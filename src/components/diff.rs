@@ -151,6 +151,19 @@ impl DiffComponent {
 	pub fn current(&self) -> (String, bool) {
 		(self.current.path.clone(), self.current.is_stage)
 	}
+	/// does a change of worktree `paths` affect the shown diff
+	pub fn is_affected_by(&self, paths: &[String]) -> bool {
+		// a staged diff only changes with the index
+		!self.current.is_stage
+			&& paths.iter().any(|path| {
+				self.current
+					.path
+					.strip_prefix(path.as_str())
+					.is_some_and(|rest| {
+						rest.is_empty() || rest.starts_with('/')
+					})
+			})
+	}
 	///
 	pub fn clear(&mut self, pending: bool) {
 		self.current = Current::default();
@@ -26,6 +26,37 @@ macro_rules! try_or_popup {
 	};
 }
 
+/// macro to run a hook after the action it belongs to is done.
+/// It will show a popup if the hook failed (or could not be run)
+#[macro_export]
+macro_rules! report_hook {
+	($self:ident, $hook:expr, $e:expr) => {
+		match $e {
+			::std::result::Result::Ok(
+				::asyncgit::sync::HookResult::Ok,
+			) => (),
+			::std::result::Result::Ok(
+				::asyncgit::sync::HookResult::NotOk(err),
+			) => {
+				::log::error!("{} hook error: {}", $hook, err);
+				$self.queue.push(
+					$crate::queue::InternalEvent::ShowErrorMsg(
+						format!("{} hook error:\n{}", $hook, err),
+					),
+				);
+			}
+			::std::result::Result::Err(err) => {
+				::log::error!("{} hook error: {}", $hook, err);
+				$self.queue.push(
+					$crate::queue::InternalEvent::ShowErrorMsg(
+						format!("{} hook error:\n{}", $hook, err),
+					),
+				);
+			}
+		}
+	};
+}
+
 /// helper func to convert unix time since epoch to formatted time string in local timezone
 pub fn time_to_string(secs: i64, short: bool) -> String {
 	let time = DateTime::<Local>::from(
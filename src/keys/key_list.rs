@@ -52,6 +52,7 @@ pub struct KeysList {
 	pub open_commit_editor: GituiKeyEvent,
 	pub open_help: GituiKeyEvent,
 	pub open_options: GituiKeyEvent,
+	pub clone_repo: GituiKeyEvent,
 	pub move_left: GituiKeyEvent,
 	pub move_right: GituiKeyEvent,
 	pub move_up: GituiKeyEvent,
@@ -78,20 +79,27 @@ pub struct KeysList {
 	pub stashing_save: GituiKeyEvent,
 	pub stashing_toggle_untracked: GituiKeyEvent,
 	pub stashing_toggle_index: GituiKeyEvent,
+	pub stashing_toggle_staged_only: GituiKeyEvent,
+	pub stashing_toggle_selected_only: GituiKeyEvent,
 	pub stash_apply: GituiKeyEvent,
 	pub stash_open: GituiKeyEvent,
 	pub stash_drop: GituiKeyEvent,
+	pub stash_branch: GituiKeyEvent,
+	pub stash_rename: GituiKeyEvent,
+	pub stash_next_part: GituiKeyEvent,
 	pub cmd_bar_toggle: GituiKeyEvent,
 	pub log_tag_commit: GituiKeyEvent,
 	pub log_mark_commit: GituiKeyEvent,
 	pub log_checkout_commit: GituiKeyEvent,
 	pub log_reset_commit: GituiKeyEvent,
 	pub log_reword_commit: GituiKeyEvent,
+	pub log_edit_note: GituiKeyEvent,
 	pub log_find: GituiKeyEvent,
 	pub find_commit_sha: GituiKeyEvent,
 	pub commit_amend: GituiKeyEvent,
 	pub toggle_signoff: GituiKeyEvent,
 	pub toggle_verify: GituiKeyEvent,
+	pub cancel_hook: GituiKeyEvent,
 	pub copy: GituiKeyEvent,
 	pub create_branch: GituiKeyEvent,
 	pub rename_branch: GituiKeyEvent,
@@ -104,11 +112,20 @@ pub struct KeysList {
 	pub tags: GituiKeyEvent,
 	pub delete_tag: GituiKeyEvent,
 	pub select_tag: GituiKeyEvent,
+	pub verify_tags: GituiKeyEvent,
+	pub tag_sort: GituiKeyEvent,
+	pub tag_filter: GituiKeyEvent,
 	pub push: GituiKeyEvent,
 	pub open_file_tree: GituiKeyEvent,
 	pub file_find: GituiKeyEvent,
 	pub branch_find: GituiKeyEvent,
+	pub branch_set_upstream: GituiKeyEvent,
+	pub branch_unset_upstream: GituiKeyEvent,
+	pub branch_sort: GituiKeyEvent,
+	pub branch_filter: GituiKeyEvent,
+	pub branch_merged: GituiKeyEvent,
 	pub force_push: GituiKeyEvent,
+	pub push_to: GituiKeyEvent,
 	pub fetch: GituiKeyEvent,
 	pub pull: GituiKeyEvent,
 	pub abort_merge: GituiKeyEvent,
@@ -132,6 +149,7 @@ pub struct KeysList {
 
 #[rustfmt::skip]
 impl Default for KeysList {
+	#[allow(clippy::too_many_lines)]
 	fn default() -> Self {
 		Self {
 			tab_status: GituiKeyEvent::new(KeyCode::Char('1'), KeyModifiers::empty()),
@@ -149,6 +167,7 @@ impl Default for KeysList {
 			open_commit_editor: GituiKeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
 			open_help: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::empty()),
 			open_options: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			clone_repo: GituiKeyEvent::new(KeyCode::Char('O'),  KeyModifiers::SHIFT),
 			move_left: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::empty()),
 			move_right: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			tree_collapse_recursive: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::SHIFT),
@@ -175,20 +194,27 @@ impl Default for KeysList {
 			stashing_save: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			stashing_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
 			stashing_toggle_index: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			stashing_toggle_staged_only: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			stashing_toggle_selected_only: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
 			stash_apply: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			stash_open: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			stash_drop: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			stash_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
+			stash_rename: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			stash_next_part: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
 			cmd_bar_toggle: GituiKeyEvent::new(KeyCode::Char('.'),  KeyModifiers::empty()),
 			log_tag_commit: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
 			log_mark_commit: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
 			log_checkout_commit: GituiKeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT },
 			log_reset_commit: GituiKeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT },
 			log_reword_commit: GituiKeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty() },
+			log_edit_note: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
 			log_find: GituiKeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty() },
 			find_commit_sha: GituiKeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
 			commit_amend: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
 			toggle_signoff: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
 			toggle_verify: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::CONTROL),
+			cancel_hook: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::CONTROL),
 			copy: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::empty()),
 			create_branch: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			rename_branch: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
@@ -201,8 +227,12 @@ impl Default for KeysList {
 			tags: GituiKeyEvent::new(KeyCode::Char('T'),  KeyModifiers::SHIFT),
 			delete_tag: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			select_tag: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
+			verify_tags: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
+			tag_sort: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			tag_filter: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::empty()),
 			push: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			force_push: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
+			push_to: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::CONTROL),
 			undo_commit: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
 			fetch: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
 			pull: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
@@ -210,6 +240,11 @@ impl Default for KeysList {
 			open_file_tree: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
 			file_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			branch_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			branch_set_upstream: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
+			branch_unset_upstream: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
+			branch_sort: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			branch_filter: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::empty()),
+			branch_merged: GituiKeyEvent::new(KeyCode::Char('M'),  KeyModifiers::SHIFT),
 			diff_hunk_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
 			diff_hunk_prev: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			stage_unstage_item: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
@@ -114,7 +114,7 @@ use std::{
 	time::{Duration, Instant},
 };
 use ui::style::Theme;
-use watcher::RepoWatcher;
+use watcher::{ChangedPaths, RepoWatcher};
 
 type Terminal = ratatui::Terminal<CrosstermBackend<io::Stdout>>;
 
@@ -126,6 +126,7 @@ static SPINNER_INTERVAL: Duration = Duration::from_millis(80);
 pub enum QueueEvent {
 	Tick,
 	Notify,
+	FilesChanged(Vec<String>),
 	SpinnerUpdate,
 	AsyncEvent(AsyncNotification),
 	InputEvent(InputEvent),
@@ -295,6 +296,9 @@ fn run_app(
 				QueueEvent::Tick | QueueEvent::Notify => {
 					app.update()?;
 				}
+				QueueEvent::FilesChanged(paths) => {
+					app.update_changed_paths(&paths)?;
+				}
 				QueueEvent::AsyncEvent(ev) => {
 					if !matches!(
 						ev,
@@ -372,7 +376,7 @@ fn select_event(
 	rx_git: &Receiver<AsyncGitNotification>,
 	rx_app: &Receiver<AsyncAppNotification>,
 	rx_ticker: &Receiver<Instant>,
-	rx_notify: &Receiver<()>,
+	rx_notify: &Receiver<ChangedPaths>,
 	rx_spinner: &Receiver<Instant>,
 ) -> Result<QueueEvent> {
 	let mut sel = Select::new();
@@ -396,7 +400,9 @@ fn select_event(
 			QueueEvent::AsyncEvent(AsyncNotification::App(e))
 		}),
 		3 => oper.recv(rx_ticker).map(|_| QueueEvent::Notify),
-		4 => oper.recv(rx_notify).map(|()| QueueEvent::Notify),
+		4 => oper.recv(rx_notify).map(|paths| {
+			paths.map_or(QueueEvent::Notify, QueueEvent::FilesChanged)
+		}),
 		5 => oper.recv(rx_spinner).map(|_| QueueEvent::SpinnerUpdate),
 		_ => bail!("unknown select source"),
 	}?;
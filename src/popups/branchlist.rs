@@ -1,6 +1,7 @@
 use crate::components::{
 	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState, FuzzyFinderTarget, VerticalScroll,
+	DrawableComponent, EventState, FuzzyFinderTarget, InputType,
+	TextInputComponent, VerticalScroll,
 };
 use crate::{
 	app::Environment,
@@ -9,7 +10,9 @@ use crate::{
 	queue::{
 		Action, InternalEvent, NeedsUpdate, Queue, StackablePopupOpen,
 	},
-	strings, try_or_popup,
+	report_hook,
+	strings::{self, symbol},
+	try_or_popup,
 	ui::{self, Size},
 };
 use anyhow::Result;
@@ -20,8 +23,10 @@ use asyncgit::{
 			checkout_remote_branch, BranchDetails, LocalBranch,
 			RemoteBranch,
 		},
-		checkout_branch, get_branches_info, BranchInfo, BranchType,
-		CommitId, RepoPathRef, RepoState,
+		branch_compare_upstream, checkout_branch, get_branches_info,
+		get_merged_branches_info, BranchCompare, BranchInfo,
+		BranchType, CommitId, HookResult, RebaseState, RepoPathRef,
+		RepoState,
 	},
 	AsyncGitNotification,
 };
@@ -34,22 +39,74 @@ use ratatui::{
 	widgets::{Block, BorderType, Borders, Clear, Paragraph, Tabs},
 	Frame,
 };
-use std::cell::Cell;
+use std::{
+	cell::{Cell, RefCell},
+	collections::HashMap,
+};
 use ui::style::SharedTheme;
 use unicode_truncate::UnicodeTruncateStr;
 
 use super::InspectCommitOpen;
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BranchSort {
+	Name,
+	Date,
+	Author,
+}
+
+impl BranchSort {
+	const fn next(self) -> Self {
+		match self {
+			Self::Name => Self::Date,
+			Self::Date => Self::Author,
+			Self::Author => Self::Name,
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Name => "name",
+			Self::Date => "date",
+			Self::Author => "author",
+		}
+	}
+
+	fn sort(self, branches: &mut [BranchInfo]) {
+		branches.sort_by(|a, b| match self {
+			Self::Name => a.name.cmp(&b.name),
+			// newest first
+			Self::Date => b.top_commit_time.cmp(&a.top_commit_time),
+			Self::Author => a
+				.top_commit_author
+				.cmp(&b.top_commit_author)
+				.then_with(|| a.name.cmp(&b.name)),
+		});
+	}
+}
+
 ///
+#[allow(clippy::struct_excessive_bools)]
 pub struct BranchListPopup {
 	repo: RepoPathRef,
+	/// all branches of the current view, `branches` is the filtered subset
+	all_branches: Vec<BranchInfo>,
 	branches: Vec<BranchInfo>,
+	sort: BranchSort,
+	filter: TextInputComponent,
+	filter_focused: bool,
+	/// only list branches merged into this reference
+	merged_base: Option<String>,
+	/// references of the branches marked for deletion
+	marked: Vec<String>,
 	local: bool,
 	has_remotes: bool,
 	visible: bool,
 	selection: u16,
 	scroll: VerticalScroll,
 	current_height: Cell<u16>,
+	/// ahead/behind of the rows drawn so far, keyed by reference
+	compares: RefCell<HashMap<String, BranchCompare>>,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
@@ -72,9 +129,22 @@ impl DrawableComponent for BranchListPopup {
 
 			f.render_widget(Clear, area);
 
+			let title = self.merged_base.as_ref().map_or_else(
+				strings::title_branches,
+				|base| {
+					strings::title_branches_merged(
+						base.strip_prefix("refs/heads/")
+							.or_else(|| {
+								base.strip_prefix("refs/remotes/")
+							})
+							.unwrap_or(base),
+					)
+				},
+			);
+
 			f.render_widget(
 				Block::default()
-					.title(strings::title_branches())
+					.title(title)
 					.border_type(BorderType::Thick)
 					.borders(Borders::ALL),
 				area,
@@ -88,13 +158,20 @@ impl DrawableComponent for BranchListPopup {
 			let chunks = Layout::default()
 				.direction(Direction::Vertical)
 				.constraints(
-					[Constraint::Length(2), Constraint::Min(1)]
-						.as_ref(),
+					[
+						Constraint::Length(2),
+						Constraint::Min(1),
+						Constraint::Length(u16::from(
+							self.filter.is_visible(),
+						)),
+					]
+					.as_ref(),
 				)
 				.split(area);
 
 			self.draw_tabs(f, chunks[0]);
 			self.draw_list(f, chunks[1])?;
+			self.filter.draw(f, chunks[2])?;
 		}
 
 		Ok(())
@@ -118,13 +195,17 @@ impl Component for BranchListPopup {
 	}
 
 	//TODO: cleanup
-	#[allow(clippy::cognitive_complexity)]
+	#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if !self.visible {
 			return Ok(EventState::NotConsumed);
 		}
 
 		if let Event::Key(e) = ev {
+			if self.filter_focused {
+				return self.filter_event(ev, e);
+			}
+
 			if self.move_event(e)?.is_consumed() {
 				return Ok(EventState::Consumed);
 			}
@@ -147,19 +228,36 @@ impl Component for BranchListPopup {
 			{
 				self.rename_branch();
 			} else if key_match(e, self.key_config.keys.delete_branch)
-				&& !selection_is_cur_branch
-				&& self.valid_selection()
+				&& (!self.visible_marked().is_empty()
+					|| (!selection_is_cur_branch
+						&& self.valid_selection()))
 			{
 				self.delete_branch();
+			} else if key_match(
+				e,
+				self.key_config.keys.log_mark_commit,
+			) && !selection_is_cur_branch
+				&& self.valid_selection()
+			{
+				self.toggle_mark();
+			} else if key_match(e, self.key_config.keys.branch_sort) {
+				self.sort = self.sort.next();
+				self.apply_filter()?;
+			} else if key_match(e, self.key_config.keys.branch_filter)
+			{
+				self.filter_focused = true;
+				self.filter.show()?;
+				self.filter.enabled(true);
+			} else if key_match(e, self.key_config.keys.branch_merged)
+				&& (self.merged_base.is_some()
+					|| self.valid_selection())
+			{
+				self.toggle_merged()?;
 			} else if key_match(e, self.key_config.keys.merge_branch)
 				&& !selection_is_cur_branch
 				&& self.valid_selection()
 			{
-				try_or_popup!(
-					self,
-					"merge branch error:",
-					self.merge_branch()
-				);
+				self.merge_branch();
 			} else if key_match(e, self.key_config.keys.rebase_branch)
 				&& !selection_is_cur_branch
 				&& self.valid_selection()
@@ -176,6 +274,12 @@ impl Component for BranchListPopup {
 			} else if key_match(
 				e,
 				self.key_config.keys.compare_commits,
+			) && self.marked.len() == 2
+			{
+				self.compare_marked();
+			} else if key_match(
+				e,
+				self.key_config.keys.compare_commits,
 			) && self.valid_selection()
 			{
 				self.hide();
@@ -206,6 +310,23 @@ impl Component for BranchListPopup {
 			) {
 				//do not consume if its the more key
 				return Ok(EventState::NotConsumed);
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_set_upstream,
+			) && self.local
+				&& self.valid_selection()
+			{
+				self.open_upstream();
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_unset_upstream,
+			) && self.selection_has_upstream()
+			{
+				try_or_popup!(
+					self,
+					"unset upstream error:",
+					self.unset_upstream()
+				);
 			} else if key_match(e, self.key_config.keys.branch_find) {
 				let branches = self
 					.branches
@@ -228,6 +349,11 @@ impl Component for BranchListPopup {
 
 	fn hide(&mut self) {
 		self.visible = false;
+		self.filter_focused = false;
+		self.filter.clear();
+		self.filter.hide();
+		self.merged_base = None;
+		self.marked.clear();
 	}
 
 	fn show(&mut self) -> Result<()> {
@@ -239,8 +365,23 @@ impl Component for BranchListPopup {
 
 impl BranchListPopup {
 	pub fn new(env: &Environment) -> Self {
+		let mut filter = TextInputComponent::new(
+			env,
+			"",
+			"filter branches",
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		filter.embed();
+
 		Self {
+			all_branches: Vec::new(),
 			branches: Vec::new(),
+			sort: BranchSort::Name,
+			filter,
+			filter_focused: false,
+			merged_base: None,
+			marked: Vec::new(),
 			local: true,
 			has_remotes: false,
 			visible: false,
@@ -250,6 +391,7 @@ impl BranchListPopup {
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			current_height: Cell::new(0),
+			compares: RefCell::default(),
 			repo: env.repo.clone(),
 		}
 	}
@@ -283,6 +425,7 @@ impl BranchListPopup {
 				.map(Into::into);
 		} else if key_match(e, self.key_config.keys.tab_toggle) {
 			self.local = !self.local;
+			self.marked.clear();
 			self.check_remotes();
 			self.update_branches()?;
 		}
@@ -315,20 +458,132 @@ impl BranchListPopup {
 	pub fn update_branches(&mut self) -> Result<()> {
 		if self.is_visible() {
 			self.check_remotes();
-			self.branches =
-				get_branches_info(&self.repo.borrow(), self.local)?;
+			self.all_branches = if let Some(base) = &self.merged_base
+			{
+				get_merged_branches_info(
+					&self.repo.borrow(),
+					self.local,
+					base,
+				)?
+			} else {
+				get_branches_info(&self.repo.borrow(), self.local)?
+			};
 			//remove remote branch called `HEAD`
 			if !self.local {
-				self.branches
+				self.all_branches
 					.iter()
 					.position(|b| b.name.ends_with("/HEAD"))
-					.map(|idx| self.branches.remove(idx));
+					.map(|idx| self.all_branches.remove(idx));
 			}
-			self.set_selection(self.selection)?;
+			self.compares.borrow_mut().clear();
+			self.marked.retain(|reference| {
+				self.all_branches
+					.iter()
+					.any(|b| &b.reference == reference)
+			});
+			self.apply_filter()?;
 		}
 		Ok(())
 	}
 
+	/// sorts `all_branches` and shows the ones matching the filter
+	fn apply_filter(&mut self) -> Result<()> {
+		self.sort.sort(&mut self.all_branches);
+
+		let filter = self.filter.get_text().to_lowercase();
+		self.branches = self
+			.all_branches
+			.iter()
+			.filter(|b| b.name.to_lowercase().contains(&filter))
+			.cloned()
+			.collect();
+
+		self.set_selection(self.selection)
+	}
+
+	fn filter_event(
+		&mut self,
+		ev: &Event,
+		e: &KeyEvent,
+	) -> Result<EventState> {
+		if key_match(e, self.key_config.keys.exit_popup) {
+			self.filter_focused = false;
+			self.filter.clear();
+			self.filter.hide();
+			self.apply_filter()?;
+		} else if key_match(e, self.key_config.keys.enter) {
+			self.filter_focused = false;
+			self.filter.enabled(false);
+			if self.filter.get_text().is_empty() {
+				self.filter.hide();
+			}
+		} else if key_match(e, self.key_config.keys.move_down) {
+			self.move_selection(ScrollType::Up)?;
+		} else if key_match(e, self.key_config.keys.move_up) {
+			self.move_selection(ScrollType::Down)?;
+		} else if self.filter.event(ev)?.is_consumed() {
+			self.apply_filter()?;
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn toggle_mark(&mut self) {
+		if let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		{
+			if let Some(idx) = self
+				.marked
+				.iter()
+				.position(|r| r == &branch.reference)
+			{
+				self.marked.remove(idx);
+			} else {
+				self.marked.push(branch.reference.clone());
+			}
+		}
+	}
+
+	/// marked branches that are not hidden by the filter
+	fn visible_marked(&self) -> Vec<String> {
+		self.branches
+			.iter()
+			.filter(|b| self.marked.contains(&b.reference))
+			.map(|b| b.reference.clone())
+			.collect()
+	}
+
+	fn selection_is_marked(&self) -> bool {
+		self.branches
+			.get(usize::from(self.selection))
+			.is_some_and(|b| self.marked.contains(&b.reference))
+	}
+
+	fn compare_marked(&mut self) {
+		if let [a, b] = self.marked.as_slice() {
+			let event = InternalEvent::OpenCompareBranches(
+				a.clone(),
+				b.clone(),
+			);
+			self.hide();
+			self.queue.push(event);
+		}
+	}
+
+	fn toggle_merged(&mut self) -> Result<()> {
+		self.merged_base = if self.merged_base.is_some() {
+			None
+		} else {
+			self.branches
+				.get(usize::from(self.selection))
+				.map(|b| b.reference.clone())
+		};
+		self.marked.clear();
+		self.selection = 0;
+
+		self.update_branches()
+	}
+
 	///
 	pub fn update_git(
 		&mut self,
@@ -345,31 +600,43 @@ impl BranchListPopup {
 		!self.branches.is_empty()
 	}
 
-	fn merge_branch(&mut self) -> Result<()> {
+	fn merge_branch(&mut self) {
 		if let Some(branch) =
 			self.branches.get(usize::from(self.selection))
 		{
-			sync::merge_branch(
-				&self.repo.borrow(),
-				&branch.name,
+			let event = InternalEvent::OpenMerge(
+				branch.name.clone(),
 				self.get_branch_type(),
-			)?;
-
-			self.hide_and_switch_tab()?;
+			);
+			self.hide();
+			self.queue.push(event);
 		}
-
-		Ok(())
 	}
 
 	fn rebase_branch(&mut self) -> Result<()> {
 		if let Some(branch) =
 			self.branches.get(usize::from(self.selection))
 		{
-			sync::rebase_branch(
+			// run pre rebase hook - can reject rebase
+			if let HookResult::NotOk(e) = sync::hooks_pre_rebase(
+				&self.repo.borrow(),
+				&branch.name,
+				None,
+			)? {
+				log::error!("pre-rebase hook error: {e}");
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("pre-rebase hook error:\n{e}"),
+				));
+				return Ok(());
+			}
+
+			if let RebaseState::Finished(hook) = sync::rebase_branch(
 				&self.repo.borrow(),
 				&branch.name,
 				self.get_branch_type(),
-			)?;
+			)? {
+				report_hook!(self, "post-rewrite", anyhow::Ok(hook));
+			}
 
 			self.hide_and_switch_tab()?;
 		}
@@ -421,6 +688,45 @@ impl BranchListPopup {
 			.count() > 0
 	}
 
+	fn selection_has_upstream(&self) -> bool {
+		self.branches
+			.get(usize::from(self.selection))
+			.and_then(BranchInfo::local_details)
+			.is_some_and(|details| details.has_upstream)
+	}
+
+	fn open_upstream(&self) {
+		if let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		{
+			let upstream = branch
+				.local_details()
+				.and_then(|details| details.upstream.as_ref())
+				.map(|upstream| upstream.reference.clone());
+
+			self.queue.push(InternalEvent::OpenUpstream(
+				branch.name.clone(),
+				upstream,
+			));
+		}
+	}
+
+	fn unset_upstream(&mut self) -> Result<()> {
+		if let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		{
+			sync::unset_branch_upstream(
+				&self.repo.borrow(),
+				&branch.name,
+			)?;
+
+			self.update_branches()?;
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
 	// top commit of selected branch
 	fn get_selected_commit(&self) -> Option<CommitId> {
 		self.branches
@@ -467,7 +773,32 @@ impl BranchListPopup {
 		Ok(())
 	}
 
+	/// compares a local branch with its upstream,
+	/// only done for drawn rows since it walks the history
+	fn upstream_compare(
+		&self,
+		branch: &BranchInfo,
+	) -> Option<BranchCompare> {
+		branch.local_details()?.upstream.as_ref()?;
+
+		let compare = *self
+			.compares
+			.borrow_mut()
+			.entry(branch.reference.clone())
+			.or_insert_with(|| {
+				branch_compare_upstream(
+					&self.repo.borrow(),
+					&branch.name,
+				)
+				.inspect_err(|e| log::error!("branch compare: {e}"))
+				.unwrap_or_default()
+			});
+
+		Some(compare)
+	}
+
 	/// Get branches to display
+	#[allow(clippy::too_many_lines)]
 	fn get_text(
 		&self,
 		theme: &SharedTheme,
@@ -482,13 +813,22 @@ impl BranchListPopup {
 		const THREE_DOTS_LENGTH: usize = THREE_DOTS.len(); // "..."
 		const COMMIT_HASH_LENGTH: usize = 8;
 		const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
+		const AHEAD_BEHIND_LENGTH: usize = 10; // "↑12 ↓3    "
 
 		let branch_name_length: usize =
 			width_available as usize * 40 / 100;
+		let ahead_behind_length =
+			if self.local { AHEAD_BEHIND_LENGTH } else { 0 };
 		// commit message takes up the remaining width
 		let commit_message_length: usize = (width_available as usize)
 			.saturating_sub(COMMIT_HASH_LENGTH)
 			.saturating_sub(branch_name_length)
+			.saturating_sub(ahead_behind_length)
+			.saturating_sub(if self.marked.is_empty() {
+				0
+			} else {
+				2
+			})
 			.saturating_sub(IS_HEAD_STAR_LENGTH)
 			.saturating_sub(THREE_DOTS_LENGTH);
 		let mut txt = Vec::new();
@@ -549,6 +889,18 @@ impl BranchListPopup {
 				format!("{is_head_str}{upstream_tracking_str} "),
 				theme.commit_author(selected),
 			);
+			let ahead_behind = self
+				.upstream_compare(displaybranch)
+				.map_or_else(String::new, |compare| {
+					format!(
+						"\u{2191}{} \u{2193}{}",
+						compare.ahead, compare.behind,
+					)
+				});
+			let span_ahead_behind = Span::styled(
+				format!("{ahead_behind:ahead_behind_length$}"),
+				theme.text(true, selected),
+			);
 			let span_hash = Span::styled(
 				format!(
 					"{} ",
@@ -565,12 +917,32 @@ impl BranchListPopup {
 				theme.branch(selected, is_head),
 			);
 
-			txt.push(Line::from(vec![
+			let mut spans = Vec::with_capacity(6);
+			if !self.marked.is_empty() {
+				spans.push(Span::styled(
+					format!(
+						"{} ",
+						if self
+							.marked
+							.contains(&displaybranch.reference)
+						{
+							symbol::CHECKMARK
+						} else {
+							symbol::EMPTY_SPACE
+						}
+					),
+					theme.log_marker(selected),
+				));
+			}
+
+			spans.extend([
 				span_prefix,
 				span_name,
+				span_ahead_behind,
 				span_hash,
 				span_msg,
-			]));
+			]);
+			txt.push(Line::from(spans));
 		}
 
 		Text::from(txt)
@@ -582,6 +954,8 @@ impl BranchListPopup {
 			anyhow::bail!("no valid branch selected");
 		}
 
+		let prev_head = sync::get_head(&self.repo.borrow()).ok();
+
 		if self.local {
 			checkout_branch(
 				&self.repo.borrow(),
@@ -597,6 +971,12 @@ impl BranchListPopup {
 			self.update_branches()?;
 		}
 
+		report_hook!(
+			self,
+			"post-checkout",
+			sync::hooks_post_checkout(&self.repo.borrow(), prev_head)
+		);
+
 		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 
 		Ok(())
@@ -663,15 +1043,22 @@ impl BranchListPopup {
 		));
 	}
 
+	/// deletes the visible marked branches or the selected one if none are marked
 	fn delete_branch(&self) {
-		let reference =
-			self.branches[self.selection as usize].reference.clone();
+		let mut references = self.visible_marked();
+		if references.is_empty() {
+			references.push(
+				self.branches[self.selection as usize]
+					.reference
+					.clone(),
+			);
+		}
 
 		self.queue.push(InternalEvent::ConfirmAction(
 			if self.local {
-				Action::DeleteLocalBranch(reference)
+				Action::DeleteLocalBranch(references)
 			} else {
-				Action::DeleteRemoteBranch(reference)
+				Action::DeleteRemoteBranch(references)
 			},
 		));
 	}
@@ -697,12 +1084,6 @@ impl BranchListPopup {
 			true,
 		));
 
-		out.push(CommandInfo::new(
-			strings::commands::compare_with_head(&self.key_config),
-			!selection_is_cur_branch,
-			true,
-		));
-
 		out.push(CommandInfo::new(
 			strings::commands::toggle_branch_popup(
 				&self.key_config,
@@ -728,7 +1109,8 @@ impl BranchListPopup {
 
 		out.push(CommandInfo::new(
 			strings::commands::delete_branch_popup(&self.key_config),
-			!selection_is_cur_branch,
+			!selection_is_cur_branch
+				|| !self.visible_marked().is_empty(),
 			true,
 		));
 
@@ -750,6 +1132,20 @@ impl BranchListPopup {
 			self.local,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::branch_set_upstream(&self.key_config),
+			self.valid_selection(),
+			self.local,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_unset_upstream(
+				&self.key_config,
+			),
+			self.selection_has_upstream(),
+			self.local,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::fetch_remotes(&self.key_config),
 			self.has_remotes,
@@ -773,5 +1169,60 @@ impl BranchListPopup {
 			true,
 			self.has_remotes,
 		));
+
+		self.add_list_commands(out);
+	}
+	/// commands changing which branches are listed or using the marked ones
+	fn add_list_commands(&self, out: &mut Vec<CommandInfo>) {
+		let selection_is_cur_branch = self.selection_is_cur_branch();
+
+		if self.marked.len() == 2 {
+			out.push(CommandInfo::new(
+				strings::commands::compare_branches(&self.key_config),
+				true,
+				true,
+			));
+		} else {
+			out.push(CommandInfo::new(
+				strings::commands::compare_with_head(
+					&self.key_config,
+				),
+				!selection_is_cur_branch,
+				true,
+			));
+		}
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_list_mark(
+				&self.key_config,
+				self.selection_is_marked(),
+			),
+			!selection_is_cur_branch && self.valid_selection(),
+			true,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_sort(
+				&self.key_config,
+				self.sort.name(),
+			),
+			true,
+			true,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_filter(&self.key_config),
+			true,
+			true,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_merged(
+				&self.key_config,
+				self.merged_base.is_some(),
+			),
+			self.merged_base.is_some() || self.valid_selection(),
+			true,
+		));
 	}
 }
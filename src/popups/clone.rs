@@ -0,0 +1,520 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		CredComponent, DrawableComponent, EventState, InputType,
+		TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::{anyhow, Result};
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{
+		cred::{
+			extract_username_password_for_url,
+			need_username_password_for_url, BasicAuthCredential,
+		},
+		CloneOptions,
+	},
+	AsyncCloneJob, AsyncGitNotification, ProgressPercent,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::Span,
+	widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph},
+	Frame,
+};
+use std::path::PathBuf;
+
+const LABEL_WIDTH: u16 = 12;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Selection {
+	Url,
+	Path,
+	Branch,
+	Depth,
+	Submodules,
+}
+
+impl Selection {
+	const fn next(self) -> Self {
+		match self {
+			Self::Url => Self::Path,
+			Self::Path => Self::Branch,
+			Self::Branch => Self::Depth,
+			Self::Depth => Self::Submodules,
+			Self::Submodules => Self::Url,
+		}
+	}
+
+	const fn prev(self) -> Self {
+		match self {
+			Self::Url => Self::Submodules,
+			Self::Path => Self::Url,
+			Self::Branch => Self::Path,
+			Self::Depth => Self::Branch,
+			Self::Submodules => Self::Depth,
+		}
+	}
+}
+
+/// clones a remote repository and opens it
+#[allow(clippy::struct_excessive_bools)]
+pub struct ClonePopup {
+	queue: Queue,
+	visible: bool,
+	/// choosing what to clone, before the clone starts
+	options_visible: bool,
+	selection: Selection,
+	url: TextInputComponent,
+	path: TextInputComponent,
+	branch: TextInputComponent,
+	depth: TextInputComponent,
+	recurse_submodules: bool,
+	/// what the running clone was started with
+	request: Option<(String, PathBuf, CloneOptions)>,
+	async_clone: AsyncSingleJob<AsyncCloneJob>,
+	progress: Option<ProgressPercent>,
+	pending: bool,
+	input_cred: CredComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ClonePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let input = |placeholder: &str| {
+			let mut input =
+				TextInputComponent::new(env, "", placeholder, false)
+					.with_input_type(InputType::Singleline);
+			input.embed();
+			input
+		};
+
+		Self {
+			queue: env.queue.clone(),
+			visible: false,
+			options_visible: false,
+			selection: Selection::Url,
+			url: input("https://host/repo.git"),
+			path: input("derived from url"),
+			branch: input("remote HEAD"),
+			depth: input("full history"),
+			recurse_submodules: false,
+			request: None,
+			async_clone: AsyncSingleJob::new(env.sender_git.clone()),
+			progress: None,
+			pending: false,
+			input_cred: CredComponent::new(env),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// opens the clone options, the clone starts once confirmed
+	pub fn open(&mut self) -> Result<()> {
+		self.show()?;
+		self.options_visible = true;
+		self.selection = Selection::Url;
+
+		for input in self.inputs_mut() {
+			input.show()?;
+		}
+		self.update_inputs();
+
+		Ok(())
+	}
+
+	///
+	pub const fn any_work_pending(&self) -> bool {
+		self.pending
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if self.is_visible() && ev == AsyncGitNotification::Clone {
+			self.update();
+		}
+	}
+
+	fn update(&mut self) {
+		self.pending = self.async_clone.is_pending();
+		self.progress = self.async_clone.progress();
+
+		if self.pending {
+			return;
+		}
+
+		self.hide();
+
+		match self
+			.async_clone
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(path)) => {
+				self.queue.push(InternalEvent::OpenRepo { path });
+			}
+			Some(Err(e)) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("clone failed:\n{e}"),
+				));
+			}
+			None => (),
+		}
+	}
+
+	fn inputs_mut(&mut self) -> [&mut TextInputComponent; 4] {
+		[
+			&mut self.url,
+			&mut self.path,
+			&mut self.branch,
+			&mut self.depth,
+		]
+	}
+
+	fn update_inputs(&mut self) {
+		let selection = self.selection;
+		self.url.enabled(selection == Selection::Url);
+		self.path.enabled(selection == Selection::Path);
+		self.branch.enabled(selection == Selection::Branch);
+		self.depth.enabled(selection == Selection::Depth);
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		self.selection = if up {
+			self.selection.prev()
+		} else {
+			self.selection.next()
+		};
+
+		self.update_inputs();
+	}
+
+	fn selected_input(&mut self) -> Option<&mut TextInputComponent> {
+		match self.selection {
+			Selection::Url => Some(&mut self.url),
+			Selection::Path => Some(&mut self.path),
+			Selection::Branch => Some(&mut self.branch),
+			Selection::Depth => Some(&mut self.depth),
+			Selection::Submodules => None,
+		}
+	}
+
+	/// directory to clone into, relative paths are relative to the
+	/// current working directory. defaults to the last part of the url
+	fn target_path(&self, url: &str) -> Result<PathBuf> {
+		let path = self.path.get_text().trim();
+		let path = if path.is_empty() {
+			url.trim_end_matches('/')
+				.rsplit(['/', ':'])
+				.next()
+				.map(|name| name.trim_end_matches(".git"))
+				.filter(|name| !name.is_empty())
+				.ok_or_else(|| anyhow!("no target directory"))?
+		} else {
+			path
+		};
+
+		Ok(std::env::current_dir()?.join(path))
+	}
+
+	fn clone_options(&self) -> Result<CloneOptions> {
+		let branch = self.branch.get_text().trim();
+		let depth = self.depth.get_text().trim();
+
+		Ok(CloneOptions {
+			branch: (!branch.is_empty()).then(|| branch.to_string()),
+			depth: if depth.is_empty() {
+				None
+			} else {
+				Some(depth.parse().map_err(|_| {
+					anyhow!("invalid depth: '{depth}'")
+				})?)
+			},
+			recurse_submodules: self.recurse_submodules,
+		})
+	}
+
+	fn confirm_options(&mut self) -> Result<()> {
+		let url = self.url.get_text().trim().to_string();
+		if url.is_empty() {
+			return Ok(());
+		}
+
+		let path = self.target_path(&url)?;
+		let options = self.clone_options()?;
+
+		self.options_visible = false;
+
+		let cred = need_username_password_for_url(&url)
+			.then(|| extract_username_password_for_url(&url));
+		self.request = Some((url, path, options));
+
+		match cred {
+			Some(cred) if !cred.is_complete() => {
+				self.input_cred.set_cred(cred);
+				self.input_cred.show()?;
+			}
+			cred => self.start_clone(cred),
+		}
+
+		Ok(())
+	}
+
+	fn start_clone(&mut self, cred: Option<BasicAuthCredential>) {
+		if let Some((url, path, options)) = self.request.clone() {
+			self.pending = true;
+			self.progress = Some(ProgressPercent::empty());
+			self.async_clone
+				.spawn(AsyncCloneJob::new(url, path, options, cred));
+		}
+	}
+
+	fn draw_options(&self, f: &mut Frame) -> Result<()> {
+		const SIZE: (u16, u16) = (60, 7);
+		let area =
+			ui::centered_rect_absolute(SIZE.0, SIZE.1, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.borders(Borders::all())
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true))
+				.title(Span::styled(
+					strings::CLONE_POPUP_MSG,
+					self.theme.title(true),
+				)),
+			area,
+		);
+
+		let rows = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints([Constraint::Length(1); 5].as_ref())
+			.split(area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}));
+
+		let inputs = [
+			("url", Selection::Url, &self.url),
+			("directory", Selection::Path, &self.path),
+			("branch", Selection::Branch, &self.branch),
+			("depth", Selection::Depth, &self.depth),
+		];
+
+		for (row, (label, selection, input)) in
+			rows.iter().zip(inputs)
+		{
+			let cols = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[
+						Constraint::Length(LABEL_WIDTH),
+						Constraint::Min(1),
+					]
+					.as_ref(),
+				)
+				.split(*row);
+
+			f.render_widget(
+				Paragraph::new(Span::styled(
+					format!("{label}:"),
+					self.theme
+						.text(self.selection == selection, false),
+				)),
+				cols[0],
+			);
+			input.draw(f, cols[1])?;
+		}
+
+		let x_submodules =
+			if self.recurse_submodules { "X" } else { " " };
+		f.render_widget(
+			Paragraph::new(Span::styled(
+				format!("[{x_submodules}] recurse submodules"),
+				self.theme.text(
+					self.selection == Selection::Submodules,
+					false,
+				),
+			)),
+			rows[4],
+		);
+
+		Ok(())
+	}
+}
+
+impl DrawableComponent for ClonePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			if self.options_visible {
+				return self.draw_options(f);
+			}
+
+			let progress = self.progress.unwrap_or_default().progress;
+
+			let area = ui::centered_rect_absolute(30, 3, f.area());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Gauge::default()
+					.block(
+						Block::default()
+							.title(Span::styled(
+								strings::CLONE_POPUP_MSG,
+								self.theme.title(true),
+							))
+							.borders(Borders::ALL)
+							.border_type(BorderType::Thick)
+							.border_style(self.theme.block(true)),
+					)
+					.gauge_style(self.theme.push_gauge())
+					.percent(u16::from(progress)),
+				area,
+			);
+			self.input_cred.draw(f, rect)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ClonePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			if self.options_visible || force_all {
+				out.push(CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				));
+				out.push(CommandInfo::new(
+					strings::commands::scroll_popup(&self.key_config),
+					true,
+					true,
+				));
+				out.push(CommandInfo::new(
+					strings::commands::toggle_option(
+						&self.key_config,
+					),
+					self.selection == Selection::Submodules,
+					true,
+				));
+				out.push(CommandInfo::new(
+					strings::commands::confirm_action(
+						&self.key_config,
+					),
+					!self.url.get_text().trim().is_empty(),
+					true,
+				));
+
+				if !force_all {
+					return visibility_blocking(self);
+				}
+			}
+
+			if self.input_cred.is_visible() {
+				return self.input_cred.commands(out, force_all);
+			}
+			out.push(CommandInfo::new(
+				strings::commands::close_msg(&self.key_config),
+				!self.pending,
+				self.visible,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = ev {
+				if self.options_visible {
+					if key_match(key, self.key_config.keys.exit_popup)
+					{
+						self.hide();
+					} else if key_match(
+						key,
+						self.key_config.keys.enter,
+					) {
+						try_or_popup!(
+							self,
+							"clone error:",
+							self.confirm_options()
+						);
+					} else if key_match(
+						key,
+						self.key_config.keys.popup_up,
+					) {
+						self.move_selection(true);
+					} else if key_match(
+						key,
+						self.key_config.keys.popup_down,
+					) {
+						self.move_selection(false);
+					} else if let Some(input) = self.selected_input()
+					{
+						input.event(ev)?;
+					} else if key_match(
+						key,
+						self.key_config.keys.log_mark_commit,
+					) {
+						self.recurse_submodules =
+							!self.recurse_submodules;
+					}
+				} else if self.input_cred.is_visible() {
+					self.input_cred.event(ev)?;
+
+					if self.input_cred.get_cred().is_complete()
+						|| !self.input_cred.is_visible()
+					{
+						self.start_clone(Some(
+							self.input_cred.get_cred().clone(),
+						));
+						self.input_cred.hide();
+					}
+				} else if key_match(
+					key,
+					self.key_config.keys.exit_popup,
+				) && !self.pending
+				{
+					self.hide();
+				}
+			}
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.options_visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -1,30 +1,34 @@
 use crate::components::{
 	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState, TextInputComponent,
+	DrawableComponent, EventState, ScrollType, TextInputComponent,
+	VerticalScroll,
 };
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
 	queue::{InternalEvent, NeedsUpdate, Queue},
-	strings, try_or_popup,
-	ui::style::SharedTheme,
+	report_hook, strings, try_or_popup,
+	ui::{self, style::SharedTheme},
 };
 use anyhow::{bail, Ok, Result};
 use asyncgit::sync::commit::commit_message_prettify;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	cached,
 	sync::{
 		self, get_config_string, CommitId, HookResult,
 		PrepareCommitMsgSource, RepoPathRef, RepoState,
+		RewriteCommand,
 	},
-	StatusItem, StatusItemType,
+	AsyncGitNotification, AsyncHookJob, StatusItem, StatusItemType,
 };
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{
 	layout::{Alignment, Rect},
-	widgets::Paragraph,
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
 	Frame,
 };
 
@@ -63,9 +67,17 @@ pub struct CommitPopup {
 	commit_msg_history_idx: usize,
 	options: SharedOptions,
 	verify: bool,
+	async_hook: AsyncSingleJob<AsyncHookJob>,
+	/// the running pre-commit hook and the message to commit once it passed
+	hook: Option<(AsyncHookJob, String)>,
+	hook_output: Vec<String>,
+	hook_scroll: VerticalScroll,
+	/// keep the last line of the hook output visible
+	hook_follow: bool,
 }
 
 const FIRST_LINE_LIMIT: usize = 50;
+const HOOK_OUTPUT_HEIGHT: u16 = 12;
 
 impl CommitPopup {
 	///
@@ -89,6 +101,11 @@ impl CommitPopup {
 			commit_msg_history_idx: 0,
 			options: env.options.clone(),
 			verify: true,
+			async_hook: AsyncSingleJob::new(env.sender_git.clone()),
+			hook: None,
+			hook_output: Vec::new(),
+			hook_scroll: VerticalScroll::new(),
+			hook_follow: true,
 		}
 	}
 
@@ -97,6 +114,136 @@ impl CommitPopup {
 		self.git_branch_name.lookup().ok();
 	}
 
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_hook.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::Hook {
+			return;
+		}
+
+		if let Some((job, _)) = &self.hook {
+			self.hook_output = job.output();
+		}
+
+		if self.async_hook.is_pending() {
+			return;
+		}
+
+		if let Some((job, msg)) = self.hook.take() {
+			match job.result() {
+				Some(std::result::Result::Ok(HookResult::Ok)) => {
+					try_or_popup!(
+						self,
+						"commit error:",
+						self.finish_commit(msg, true)
+					);
+				}
+				Some(std::result::Result::Ok(HookResult::NotOk(
+					e,
+				))) => {
+					log::error!("pre-commit hook error: {e}");
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("pre-commit hook error:\n{e}"),
+					));
+				}
+				Some(Err(e)) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("pre-commit hook error:\n{e}"),
+					));
+				}
+				None => (),
+			}
+		}
+	}
+
+	const fn hook_running(&self) -> bool {
+		self.hook.is_some()
+	}
+
+	fn start_pre_commit_hook(&mut self, msg: String) -> Result<()> {
+		let job = AsyncHookJob::new(
+			self.repo.borrow().clone(),
+			sync::hooks_timeout(&self.repo.borrow())?,
+		);
+
+		self.hook_output.clear();
+		self.hook_scroll.reset();
+		self.hook_follow = true;
+		self.async_hook.spawn(job.clone());
+		self.hook = Some((job, msg));
+
+		Ok(())
+	}
+
+	fn cancel_hook(&self) {
+		if let Some((job, _)) = &self.hook {
+			job.cancel();
+		}
+	}
+
+	/// scrolling down at the end follows new output again
+	fn scroll_hook_output(&mut self, scroll: ScrollType) {
+		let moved = self.hook_scroll.move_top(scroll);
+		self.hook_follow =
+			!moved && matches!(scroll, ScrollType::Down);
+	}
+
+	fn draw_hook_output(&self, f: &mut Frame) {
+		let area = ui::centered_rect_absolute(
+			self.input.get_area().width,
+			HOOK_OUTPUT_HEIGHT,
+			f.area(),
+		);
+		let height = usize::from(area.height.saturating_sub(2));
+		let lines = self.hook_output.len();
+
+		let top = if self.hook_follow {
+			self.hook_scroll.update(
+				lines.saturating_sub(1),
+				lines,
+				height,
+			)
+		} else {
+			self.hook_scroll.update_no_selection(lines, height)
+		};
+
+		let output = self
+			.hook_output
+			.iter()
+			.skip(top)
+			.take(height)
+			.map(|line| {
+				Line::from(Span::styled(
+					line.trim_end(),
+					self.theme.text(true, false),
+				))
+			})
+			.collect::<Vec<_>>();
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Paragraph::new(output).block(
+				Block::default()
+					.title(Span::styled(
+						strings::commit_hook_running_title(
+							"pre-commit",
+						),
+						self.theme.title(true),
+					))
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true)),
+			),
+			area,
+		);
+
+		self.hook_scroll.draw(f, area, &self.theme);
+	}
+
 	fn draw_branch_name(&self, f: &mut Frame) {
 		if let Some(name) = self.git_branch_name.last() {
 			let w = Paragraph::new(format!("{{{name}}}"))
@@ -209,8 +356,27 @@ impl CommitPopup {
 	fn commit(&mut self) -> Result<()> {
 		let msg = self.input.get_text().to_string();
 
+		// on exit verify should always be on
+		let verify = self.verify;
+		self.verify = true;
+
+		// run pre commit hook in the background - can reject commit
+		if verify
+			&& sync::hooks_pre_commit_found(&self.repo.borrow())?
+		{
+			return self.start_pre_commit_hook(msg);
+		}
+
+		self.finish_commit(msg, verify)
+	}
+
+	fn finish_commit(
+		&mut self,
+		msg: String,
+		verify: bool,
+	) -> Result<()> {
 		if matches!(
-			self.commit_with_msg(msg)?,
+			self.commit_with_msg(msg, verify)?,
 			CommitResult::CommitDone
 		) {
 			self.options
@@ -228,26 +394,10 @@ impl CommitPopup {
 	}
 
 	fn commit_with_msg(
-		&mut self,
+		&self,
 		msg: String,
+		verify: bool,
 	) -> Result<CommitResult> {
-		// on exit verify should always be on
-		let verify = self.verify;
-		self.verify = true;
-
-		if verify {
-			// run pre commit hook - can reject commit
-			if let HookResult::NotOk(e) =
-				sync::hooks_pre_commit(&self.repo.borrow())?
-			{
-				log::error!("pre-commit hook error: {e}");
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					format!("pre-commit hook error:\n{e}"),
-				));
-				return Ok(CommitResult::Aborted);
-			}
-		}
-
 		let mut msg =
 			commit_message_prettify(&self.repo.borrow(), msg)?;
 
@@ -263,7 +413,7 @@ impl CommitPopup {
 				return Ok(CommitResult::Aborted);
 			}
 		}
-		self.do_commit(&msg)?;
+		let id = self.do_commit(&msg)?;
 
 		if let HookResult::NotOk(e) =
 			sync::hooks_post_commit(&self.repo.borrow())?
@@ -274,11 +424,23 @@ impl CommitPopup {
 			)));
 		}
 
+		if let Mode::Amend(amended) = &self.mode {
+			report_hook!(
+				self,
+				"post-rewrite",
+				sync::hooks_post_rewrite(
+					&self.repo.borrow(),
+					RewriteCommand::Amend,
+					&[(*amended, id)],
+				)
+			);
+		}
+
 		Ok(CommitResult::CommitDone)
 	}
 
-	fn do_commit(&self, msg: &str) -> Result<()> {
-		match &self.mode {
+	fn do_commit(&self, msg: &str) -> Result<CommitId> {
+		Ok(match &self.mode {
 			Mode::Normal => sync::commit(&self.repo.borrow(), msg)?,
 			Mode::Amend(amend) => {
 				sync::amend(&self.repo.borrow(), *amend, msg)?
@@ -290,14 +452,14 @@ impl CommitPopup {
 				sync::commit_revert(&self.repo.borrow(), msg)?
 			}
 			Mode::Reword(id) => {
-				let commit =
+				let (commit, hook) =
 					sync::reword(&self.repo.borrow(), *id, msg)?;
+				report_hook!(self, "post-rewrite", Ok(hook));
 				self.queue.push(InternalEvent::TabSwitchStatus);
 
 				commit
 			}
-		};
-		Ok(())
+		})
 	}
 
 	fn can_commit(&self) -> bool {
@@ -485,6 +647,10 @@ impl DrawableComponent for CommitPopup {
 			self.input.draw(f, rect)?;
 			self.draw_branch_name(f);
 			self.draw_warnings(f);
+
+			if self.hook_running() {
+				self.draw_hook_output(f);
+			}
 		}
 
 		Ok(())
@@ -497,6 +663,16 @@ impl Component for CommitPopup {
 		out: &mut Vec<CommandInfo>,
 		force_all: bool,
 	) -> CommandBlocking {
+		if self.hook_running() {
+			out.push(CommandInfo::new(
+				strings::commands::cancel_hook(&self.key_config),
+				true,
+				true,
+			));
+
+			return visibility_blocking(self);
+		}
+
 		self.input.commands(out, force_all);
 
 		if self.is_visible() || force_all {
@@ -556,6 +732,26 @@ impl Component for CommitPopup {
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
 			if let Event::Key(e) = ev {
+				if self.hook_running() {
+					if key_match(e, self.key_config.keys.cancel_hook)
+					{
+						self.cancel_hook();
+					} else if key_match(
+						e,
+						self.key_config.keys.popup_down,
+					) {
+						self.scroll_hook_output(ScrollType::Down);
+					} else if key_match(
+						e,
+						self.key_config.keys.popup_up,
+					) {
+						self.scroll_hook_output(ScrollType::Up);
+					}
+
+					// the message is locked until the hook is done
+					return Ok(EventState::Consumed);
+				}
+
 				let input_consumed =
 					if key_match(e, self.key_config.keys.commit)
 						&& self.can_commit()
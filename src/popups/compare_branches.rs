@@ -0,0 +1,567 @@
+use crate::components::{
+	command_pump, event_pump, time_to_string, visibility_blocking,
+	CommandBlocking, CommandInfo, CommitDetailsComponent, Component,
+	DiffComponent, DrawableComponent, EventState, ScrollType,
+};
+use crate::{
+	accessors,
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{
+		self, commit_files::OldNew, BranchesCompare, CommitId,
+		CommitInfo, RepoPathRef,
+	},
+	AsyncDiff, AsyncGitNotification, CommitFilesParams, DiffParams,
+	DiffType,
+};
+use crossterm::event::{Event, KeyEvent};
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::cell::Cell;
+
+const MESSAGE_LENGTH: usize = 100;
+/// commit infos are loaded in pages around the scroll position
+const PAGE_SIZE: usize = 500;
+
+/// shows how two branches differ: the commits unique to each side,
+/// their merge base and the diff of the second branch against the
+/// merge base (`a...b`)
+pub struct CompareBranchesPopup {
+	repo: RepoPathRef,
+	names: (String, String),
+	compare: Option<BranchesCompare>,
+	merge_base: Option<CommitInfo>,
+	/// commits only in `b` followed by the ones only in `a`
+	ids: Vec<CommitId>,
+	/// infos of `ids` starting at `page_start`
+	page: Vec<CommitInfo>,
+	page_start: usize,
+	summary_focused: bool,
+	summary_scroll: u16,
+	summary_height: Cell<u16>,
+	diff: DiffComponent,
+	details: CommitDetailsComponent,
+	git_diff: AsyncDiff,
+	visible: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+	options: SharedOptions,
+}
+
+impl DrawableComponent for CompareBranchesPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			let percentages = if self.diff.focused() {
+				(0, 100)
+			} else {
+				(50, 50)
+			};
+
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[
+						Constraint::Percentage(percentages.0),
+						Constraint::Percentage(percentages.1),
+					]
+					.as_ref(),
+				)
+				.split(rect);
+
+			let left = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Percentage(40),
+						Constraint::Percentage(60),
+					]
+					.as_ref(),
+				)
+				.split(chunks[0]);
+
+			f.render_widget(Clear, rect);
+
+			self.draw_summary(f, left[0]);
+			self.details.draw(f, left[1])?;
+			self.diff.draw(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for CompareBranchesPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			command_pump(
+				out,
+				force_all,
+				self.components().as_slice(),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				self.summary_focused || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_right(&self.key_config),
+				self.summary_focused || self.can_focus_diff(),
+				!self.diff.focused() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_left(&self.key_config),
+				true,
+				!self.summary_focused || force_all,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if !self.summary_focused
+				&& event_pump(
+					ev,
+					self.components_mut().as_mut_slice(),
+				)?
+				.is_consumed()
+			{
+				if !self.details.is_visible() {
+					self.hide();
+				}
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup)
+					|| key_match(e, self.key_config.keys.move_left)
+				{
+					self.focus_left();
+				} else if key_match(
+					e,
+					self.key_config.keys.move_right,
+				) {
+					self.focus_right();
+				} else if self.summary_focused {
+					self.summary_event(e)?;
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.details.show()?;
+		self.details.focus(false);
+		self.diff.focus(false);
+		self.summary_focused = true;
+		self.summary_scroll = 0;
+		self.update()?;
+		Ok(())
+	}
+}
+
+impl CompareBranchesPopup {
+	accessors!(self, [diff, details]);
+
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			names: (String::new(), String::new()),
+			compare: None,
+			merge_base: None,
+			ids: Vec::new(),
+			page: Vec::new(),
+			page_start: 0,
+			summary_focused: true,
+			summary_scroll: 0,
+			summary_height: Cell::new(0),
+			details: CommitDetailsComponent::new(env),
+			diff: DiffComponent::new(env, true),
+			git_diff: AsyncDiff::new(
+				env.repo.borrow().clone(),
+				&env.sender_git,
+			),
+			visible: false,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+			options: env.options.clone(),
+		}
+	}
+
+	/// compares the branch references `a` and `b`
+	pub fn open(&mut self, a: &str, b: &str) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		let compare = sync::compare_branches(&repo, a, b)?;
+
+		self.merge_base = match compare.merge_base {
+			Some(merge_base) => sync::get_commits_info(
+				&repo,
+				&[merge_base],
+				MESSAGE_LENGTH,
+			)?
+			.pop(),
+			None => None,
+		};
+		self.ids = compare
+			.only_b
+			.iter()
+			.chain(&compare.only_a)
+			.copied()
+			.collect();
+		self.page.clear();
+		self.page_start = 0;
+		self.compare = Some(compare);
+		self.names = (short_name(a), short_name(b));
+
+		self.show()?;
+		self.load_page()?;
+
+		Ok(())
+	}
+
+	/// loads the infos of the commits around the scroll position
+	/// unless they are loaded already
+	fn load_page(&mut self) -> Result<()> {
+		// the summary has up to four lines that are no commits
+		let first =
+			usize::from(self.summary_scroll).saturating_sub(4);
+		let last = first
+			.saturating_add(usize::from(self.summary_height.get()))
+			.saturating_add(4)
+			.min(self.ids.len());
+
+		if first >= self.page_start
+			&& last <= self.page_start + self.page.len()
+		{
+			return Ok(());
+		}
+
+		let start = first.saturating_sub(PAGE_SIZE / 4);
+		let end = start.saturating_add(PAGE_SIZE).min(self.ids.len());
+
+		self.page = sync::get_commits_info(
+			&self.repo.borrow(),
+			&self.ids[start..end],
+			MESSAGE_LENGTH,
+		)?;
+		self.page_start = start;
+
+		Ok(())
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_diff.is_pending() || self.details.any_work_pending()
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if self.is_visible() {
+			if ev == AsyncGitNotification::CommitFiles {
+				self.update()?;
+			} else if ev == AsyncGitNotification::Diff {
+				self.update_diff()?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// the three-dot diff compares `b` against the merge base,
+	/// unrelated branches are compared directly
+	fn get_ids(&self) -> Option<OldNew<CommitId>> {
+		self.compare.as_ref().map(|compare| OldNew {
+			old: compare.merge_base.unwrap_or(compare.a),
+			new: compare.b,
+		})
+	}
+
+	/// called when any tree component changed selection
+	pub fn update_diff(&mut self) -> Result<()> {
+		if self.is_visible() {
+			if let Some(ids) = self.get_ids() {
+				if let Some(f) = self.details.files().selection_file()
+				{
+					let diff_params = DiffParams {
+						path: f.path.clone(),
+						diff_type: DiffType::Commits(ids),
+						options: self.options.borrow().diff_options(),
+					};
+
+					if let Some((params, last)) =
+						self.git_diff.last()?
+					{
+						if params == diff_params {
+							self.diff.update(f.path, false, last);
+							return Ok(());
+						}
+					}
+
+					self.git_diff.request(diff_params)?;
+					self.diff.clear(true);
+					return Ok(());
+				}
+			}
+
+			self.diff.clear(false);
+		}
+
+		Ok(())
+	}
+
+	fn update(&mut self) -> Result<()> {
+		self.details.set_commits(
+			self.get_ids().map(CommitFilesParams::from),
+			None,
+		)?;
+		self.update_diff()?;
+
+		Ok(())
+	}
+
+	fn can_focus_diff(&self) -> bool {
+		self.details.files().selection_file().is_some()
+	}
+
+	fn focus_left(&mut self) {
+		if self.diff.focused() {
+			self.diff.focus(false);
+			self.details.focus(true);
+		} else if self.summary_focused {
+			self.hide();
+		} else {
+			self.details.focus(false);
+			self.summary_focused = true;
+		}
+	}
+
+	fn focus_right(&mut self) {
+		if self.summary_focused {
+			self.summary_focused = false;
+			self.details.focus(true);
+		} else if self.can_focus_diff() {
+			self.details.focus(false);
+			self.diff.focus(true);
+		}
+	}
+
+	fn summary_event(&mut self, e: &KeyEvent) -> Result<()> {
+		let scroll = if key_match(e, self.key_config.keys.move_down) {
+			ScrollType::Down
+		} else if key_match(e, self.key_config.keys.move_up) {
+			ScrollType::Up
+		} else if key_match(e, self.key_config.keys.page_down) {
+			ScrollType::PageDown
+		} else if key_match(e, self.key_config.keys.page_up) {
+			ScrollType::PageUp
+		} else if key_match(e, self.key_config.keys.home) {
+			ScrollType::Home
+		} else if key_match(e, self.key_config.keys.end) {
+			ScrollType::End
+		} else {
+			return Ok(());
+		};
+
+		let height = self.summary_height.get();
+		let max = u16::try_from(self.summary_len())
+			.unwrap_or(u16::MAX)
+			.saturating_sub(height);
+
+		self.summary_scroll = match scroll {
+			ScrollType::Down => self.summary_scroll.saturating_add(1),
+			ScrollType::Up => self.summary_scroll.saturating_sub(1),
+			ScrollType::PageDown => {
+				self.summary_scroll.saturating_add(height)
+			}
+			ScrollType::PageUp => {
+				self.summary_scroll.saturating_sub(height)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max,
+		}
+		.min(max);
+
+		self.load_page()
+	}
+
+	fn commit_line<'a>(&self, commit: &'a CommitInfo) -> Line<'a> {
+		Line::from(vec![
+			Span::styled(
+				format!("  {} ", commit.id.get_short_string()),
+				self.theme.commit_hash(false),
+			),
+			Span::styled(
+				format!("{} ", time_to_string(commit.time, true)),
+				self.theme.commit_time(false),
+			),
+			Span::styled(
+				format!("{} ", commit.author),
+				self.theme.commit_author(false),
+			),
+			Span::styled(
+				commit.message.as_str(),
+				self.theme.text(true, false),
+			),
+		])
+	}
+
+	/// line of the commit at `idx` of `ids`, just the hash if its
+	/// info is not loaded (yet)
+	fn id_line(&self, idx: usize) -> Option<Line<'_>> {
+		if let Some(commit) = idx
+			.checked_sub(self.page_start)
+			.and_then(|idx| self.page.get(idx))
+		{
+			return Some(self.commit_line(commit));
+		}
+
+		self.ids.get(idx).map(|id| {
+			Line::from(Span::styled(
+				format!("  {} ", id.get_short_string()),
+				self.theme.commit_hash(false),
+			))
+		})
+	}
+
+	fn header_line(&self, text: String) -> Line<'_> {
+		Line::from(Span::styled(text, self.theme.text(true, false)))
+	}
+
+	fn only_counts(&self) -> (usize, usize) {
+		self.compare.as_ref().map_or((0, 0), |compare| {
+			(compare.only_a.len(), compare.only_b.len())
+		})
+	}
+
+	fn summary_len(&self) -> usize {
+		let merge_base_lines = if self.merge_base.is_some() {
+			2
+		} else {
+			usize::from(self.compare.is_some())
+		};
+
+		merge_base_lines + 2 + self.ids.len()
+	}
+
+	fn summary_line(&self, mut idx: usize) -> Option<Line<'_>> {
+		let (a, b) = &self.names;
+		let (only_a, only_b) = self.only_counts();
+
+		if let Some(merge_base) = &self.merge_base {
+			match idx {
+				0 => {
+					return Some(self.header_line(
+						strings::compare_branches_merge_base(),
+					))
+				}
+				1 => return Some(self.commit_line(merge_base)),
+				_ => idx -= 2,
+			}
+		} else if self.compare.is_some() {
+			if idx == 0 {
+				return Some(self.header_line(
+					strings::compare_branches_no_merge_base(),
+				));
+			}
+			idx -= 1;
+		}
+
+		if idx == 0 {
+			return Some(self.header_line(
+				strings::compare_branches_only_in(b, a, only_b),
+			));
+		}
+		idx -= 1;
+
+		if idx < only_b {
+			return self.id_line(idx);
+		}
+		idx -= only_b;
+
+		if idx == 0 {
+			return Some(self.header_line(
+				strings::compare_branches_only_in(a, b, only_a),
+			));
+		}
+
+		self.id_line(only_b + idx - 1)
+	}
+
+	fn draw_summary(&self, f: &mut Frame, r: Rect) {
+		self.summary_height.set(r.height.saturating_sub(2));
+
+		let (a, b) = &self.names;
+
+		let top = usize::from(self.summary_scroll);
+		let lines = (top..top
+			+ usize::from(self.summary_height.get()))
+			.map_while(|idx| self.summary_line(idx))
+			.collect::<Vec<_>>();
+
+		f.render_widget(
+			Paragraph::new(lines).block(
+				Block::default()
+					.title(Span::styled(
+						strings::compare_branches_title(a, b),
+						self.theme.title(self.summary_focused),
+					))
+					.borders(Borders::ALL)
+					.border_style(
+						self.theme.block(self.summary_focused),
+					),
+			),
+			r,
+		);
+	}
+}
+
+fn short_name(reference: &str) -> String {
+	reference
+		.strip_prefix("refs/heads/")
+		.or_else(|| reference.strip_prefix("refs/remotes/"))
+		.unwrap_or(reference)
+		.to_string()
+}
@@ -191,6 +191,7 @@ impl CompareCommitsPopup {
 			commit_id: open.commit_id,
 			compare_id: Some(compare_id),
 			tags: open.tags,
+			stash: None,
 		});
 		self.show()?;
 
@@ -9,6 +9,7 @@ use crate::{
 	strings, ui,
 };
 use anyhow::Result;
+use asyncgit::PushForce;
 use crossterm::event::Event;
 use ratatui::{layout::Rect, text::Text, widgets::Clear, Frame};
 use std::borrow::Cow;
@@ -63,6 +64,13 @@ impl Component for ConfirmPopup {
 			true,
 			self.visible,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::confirm_force_push_without_lease(
+				&self.key_config,
+			),
+			true,
+			self.visible && self.is_force_push_with_lease(),
+		));
 
 		visibility_blocking(self)
 	}
@@ -74,6 +82,11 @@ impl Component for ConfirmPopup {
 					self.hide();
 				} else if key_match(e, self.key_config.keys.enter) {
 					self.confirm();
+				} else if key_match(
+					e,
+					self.key_config.keys.force_push,
+				) {
+					self.drop_lease();
 				}
 
 				return Ok(EventState::Consumed);
@@ -125,6 +138,20 @@ impl ConfirmPopup {
 		self.hide();
 	}
 
+	const fn is_force_push_with_lease(&self) -> bool {
+		matches!(
+			self.target,
+			Some(Action::ForcePush(_, PushForce::WithLease))
+		)
+	}
+
+	/// explicit opt-in to force push without checking the remote
+	fn drop_lease(&mut self) {
+		if let Some(Action::ForcePush(_, force)) = &mut self.target {
+			*force = PushForce::Force;
+		}
+	}
+
 	fn get_text(&self) -> (String, String) {
 		if let Some(ref a) = self.target {
 			return match a {
@@ -150,22 +177,24 @@ impl ConfirmPopup {
                     strings::confirm_title_reset(),
                     strings::confirm_msg_reset_lines(lines.len()),
                 ),
-                Action::DeleteLocalBranch(branch_ref) => (
+                Action::DeleteLocalBranch(branch_refs) => (
                     strings::confirm_title_delete_branch(
                         &self.key_config,
+                        branch_refs.len() > 1,
                     ),
                     strings::confirm_msg_delete_branch(
                         &self.key_config,
-                        branch_ref,
+                        branch_refs,
                     ),
                 ),
-                Action::DeleteRemoteBranch(branch_ref) => (
+                Action::DeleteRemoteBranch(branch_refs) => (
                     strings::confirm_title_delete_remote_branch(
                         &self.key_config,
+                        branch_refs.len() > 1,
                     ),
                     strings::confirm_msg_delete_remote_branch(
                         &self.key_config,
-                        branch_ref,
+                        branch_refs,
                     ),
                 ),
 		Action::DeleteRemote(remote_name)=>(
@@ -185,13 +214,15 @@ impl ConfirmPopup {
                     strings::confirm_title_delete_tag_remote(),
                     strings::confirm_msg_delete_tag_remote(remote),
                 ),
-                Action::ForcePush(branch, _force) => (
+                Action::ForcePush(branch, force) => (
                     strings::confirm_title_force_push(
                         &self.key_config,
+                        *force == PushForce::WithLease,
                     ),
                     strings::confirm_msg_force_push(
                         &self.key_config,
                         branch.rsplit('/').next().expect("There was no / in the head reference which is impossible in git"),
+                        *force == PushForce::WithLease,
                     ),
                 ),
                 Action::PullMerge{incoming,rebase} => (
@@ -10,7 +10,7 @@ use crate::{
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use asyncgit::sync::{self, CommitId, RepoPathRef};
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{layout::Rect, widgets::Paragraph, Frame};
@@ -18,6 +18,8 @@ use ratatui::{layout::Rect, widgets::Paragraph, Frame};
 pub struct CreateBranchPopup {
 	repo: RepoPathRef,
 	input: TextInputComponent,
+	/// create the branch from this stash instead of `HEAD`
+	stash: Option<CommitId>,
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
@@ -99,6 +101,7 @@ impl CreateBranchPopup {
 				true,
 			)
 			.with_input_type(InputType::Singleline),
+			stash: None,
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
@@ -107,6 +110,21 @@ impl CreateBranchPopup {
 
 	///
 	pub fn open(&mut self) -> Result<()> {
+		self.stash = None;
+		self.input.set_title(strings::create_branch_popup_title(
+			&self.key_config,
+		));
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// like `git stash branch`
+	pub fn open_from_stash(&mut self, stash: CommitId) -> Result<()> {
+		self.stash = Some(stash);
+		self.input.set_title(strings::stash_branch_popup_title(
+			&self.key_config,
+		));
 		self.show()?;
 
 		Ok(())
@@ -114,19 +132,32 @@ impl CreateBranchPopup {
 
 	///
 	pub fn create_branch(&mut self) {
-		let res = sync::create_branch(
-			&self.repo.borrow(),
-			self.input.get_text(),
-		);
+		let res = if let Some(stash) = self.stash {
+			sync::stash_branch(
+				&self.repo.borrow(),
+				stash,
+				self.input.get_text(),
+			)
+		} else {
+			sync::create_branch(
+				&self.repo.borrow(),
+				self.input.get_text(),
+			)
+			.map(|_| ())
+		};
 
 		self.input.clear();
 		self.hide();
 
 		match res {
-			Ok(_) => {
+			Ok(()) => {
 				self.queue.push(InternalEvent::Update(
 					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
 				));
+
+				if self.stash.is_some() {
+					self.queue.push(InternalEvent::TabSwitchStatus);
+				}
 			}
 			Err(e) => {
 				log::error!("create branch: {e}");
@@ -1,15 +1,16 @@
 use crate::{
 	app::Environment,
 	components::{
-		visibility_blocking, CommandBlocking, CommandInfo, Component,
-		CredComponent, DrawableComponent, EventState,
+		string_width_align, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, CredComponent, DrawableComponent,
+		EventState, InputType, TextInputComponent,
 	},
-	keys::SharedKeyConfig,
+	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, NeedsUpdate, Queue},
-	strings,
+	strings, try_or_popup,
 	ui::{self, style::SharedTheme},
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
 	sync::{
@@ -17,22 +18,95 @@ use asyncgit::{
 			extract_username_password, need_username_password,
 			BasicAuthCredential,
 		},
+		get_remotes,
+		remotes::{FetchDeepen, FetchSettings, FetchTags},
 		RepoPathRef,
 	},
 	AsyncFetchJob, AsyncGitNotification, ProgressPercent,
 };
 use crossterm::event::Event;
 use ratatui::{
-	layout::Rect,
-	text::Span,
-	widgets::{Block, BorderType, Borders, Clear, Gauge},
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph},
 	Frame,
 };
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Selection {
+	Remote,
+	Prune,
+	PruneTags,
+	Tags,
+	Deepen,
+	DeepenArg,
+	Refspec,
+}
+
+impl Selection {
+	const fn next(self) -> Self {
+		match self {
+			Self::Remote => Self::Prune,
+			Self::Prune => Self::PruneTags,
+			Self::PruneTags => Self::Tags,
+			Self::Tags => Self::Deepen,
+			Self::Deepen => Self::DeepenArg,
+			Self::DeepenArg => Self::Refspec,
+			Self::Refspec => Self::Remote,
+		}
+	}
+
+	const fn prev(self) -> Self {
+		match self {
+			Self::Remote => Self::Refspec,
+			Self::Prune => Self::Remote,
+			Self::PruneTags => Self::Prune,
+			Self::Tags => Self::PruneTags,
+			Self::Deepen => Self::Tags,
+			Self::DeepenArg => Self::Deepen,
+			Self::Refspec => Self::DeepenArg,
+		}
+	}
+}
+
+/// how to change the depth of a shallow clone
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Deepen {
+	Off,
+	By,
+	Since,
+	Unshallow,
+}
+
+impl Deepen {
+	const fn next(self) -> Self {
+		match self {
+			Self::Off => Self::By,
+			Self::By => Self::Since,
+			Self::Since => Self::Unshallow,
+			Self::Unshallow => Self::Off,
+		}
+	}
+
+	/// whether `deepen_arg` is used
+	const fn takes_arg(self) -> bool {
+		matches!(self, Self::By | Self::Since)
+	}
+}
+
 ///
 pub struct FetchPopup {
 	repo: RepoPathRef,
 	visible: bool,
+	/// choosing what to fetch, before the fetch starts
+	options_visible: bool,
+	selection: Selection,
+	settings: FetchSettings,
+	remotes: Vec<String>,
+	refspec: TextInputComponent,
+	deepen: Deepen,
+	/// commit count or date, depending on `deepen`
+	deepen_arg: TextInputComponent,
 	async_fetch: AsyncSingleJob<AsyncFetchJob>,
 	progress: Option<ProgressPercent>,
 	pending: bool,
@@ -45,10 +119,31 @@ pub struct FetchPopup {
 impl FetchPopup {
 	///
 	pub fn new(env: &Environment) -> Self {
+		let mut refspec = TextInputComponent::new(
+			env,
+			"",
+			"configured refspecs",
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		refspec.embed();
+
+		let mut deepen_arg =
+			TextInputComponent::new(env, "", "commits / date", false)
+				.with_input_type(InputType::Singleline);
+		deepen_arg.embed();
+
 		Self {
 			queue: env.queue.clone(),
 			pending: false,
 			visible: false,
+			options_visible: false,
+			selection: Selection::Remote,
+			settings: FetchSettings::default(),
+			remotes: Vec::new(),
+			refspec,
+			deepen: Deepen::Off,
+			deepen_arg,
 			async_fetch: AsyncSingleJob::new(env.sender_git.clone()),
 			progress: None,
 			input_cred: CredComponent::new(env),
@@ -58,9 +153,41 @@ impl FetchPopup {
 		}
 	}
 
-	///
+	/// opens the fetch options, the fetch starts once confirmed
 	pub fn fetch(&mut self) -> Result<()> {
+		self.remotes = get_remotes(&self.repo.borrow())?;
+
+		if self
+			.settings
+			.remote
+			.as_ref()
+			.is_some_and(|remote| !self.remotes.contains(remote))
+		{
+			self.settings.remote = None;
+		}
+
 		self.show()?;
+		self.options_visible = true;
+		self.refspec.show()?;
+		self.deepen = Deepen::Off;
+		self.deepen_arg.show()?;
+		self.deepen_arg.clear();
+		if self.selection == Selection::DeepenArg {
+			self.selection = Selection::Deepen;
+		}
+		self.update_inputs();
+
+		Ok(())
+	}
+
+	fn confirm_options(&mut self) -> Result<()> {
+		let refspec = self.refspec.get_text().trim();
+		self.settings.refspec =
+			(!refspec.is_empty()).then(|| refspec.to_string());
+		self.settings.deepen = self.fetch_deepen()?;
+
+		self.options_visible = false;
+
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
 				.unwrap_or_else(|_| {
@@ -85,6 +212,7 @@ impl FetchPopup {
 		self.progress = Some(ProgressPercent::empty());
 		self.async_fetch.spawn(AsyncFetchJob::new(
 			self.repo.borrow().clone(),
+			self.settings.clone(),
 			cred,
 		));
 	}
@@ -108,15 +236,275 @@ impl FetchPopup {
 
 		if !self.pending {
 			self.hide();
-			self.queue
-				.push(InternalEvent::Update(NeedsUpdate::BRANCHES));
+
+			let result = self
+				.async_fetch
+				.take_last()
+				.and_then(|job| job.result());
+			if let Some(Err(e)) = result {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("fetch failed:\n{e}"),
+				));
+			}
+
+			self.queue.push(InternalEvent::Update(
+				if self.settings.deepen.is_some() {
+					// the log needs to pick up the deepened history
+					NeedsUpdate::ALL | NeedsUpdate::BRANCHES
+				} else {
+					NeedsUpdate::BRANCHES
+				},
+			));
+		}
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		self.selection = if up {
+			self.selection.prev()
+		} else {
+			self.selection.next()
+		};
+
+		if self.selection == Selection::DeepenArg
+			&& !self.deepen.takes_arg()
+		{
+			self.move_selection(up);
+			return;
+		}
+
+		self.update_inputs();
+	}
+
+	fn update_inputs(&mut self) {
+		self.refspec.enabled(self.selection == Selection::Refspec);
+		self.deepen_arg
+			.enabled(self.selection == Selection::DeepenArg);
+	}
+
+	fn fetch_deepen(&self) -> Result<Option<FetchDeepen>> {
+		let arg = self.deepen_arg.get_text().trim();
+
+		Ok(match self.deepen {
+			Deepen::Off => None,
+			Deepen::By => {
+				Some(FetchDeepen::By(arg.parse().map_err(|_| {
+					anyhow!("invalid depth: '{arg}'")
+				})?))
+			}
+			Deepen::Since => {
+				if arg.is_empty() {
+					return Err(anyhow!("missing date"));
+				}
+				Some(FetchDeepen::Since(arg.to_string()))
+			}
+			Deepen::Unshallow => Some(FetchDeepen::Unshallow),
+		})
+	}
+
+	fn toggle_option(&mut self) {
+		let settings = &mut self.settings;
+
+		match self.selection {
+			Selection::Remote => {
+				let next = settings.remote.as_ref().map_or(0, |r| {
+					self.remotes
+						.iter()
+						.position(|remote| remote == r)
+						.map_or(0, |idx| idx + 1)
+				});
+				settings.remote = self.remotes.get(next).cloned();
+			}
+			Selection::Prune => {
+				settings.prune = Self::next_bool(settings.prune);
+			}
+			Selection::PruneTags => {
+				settings.prune_tags =
+					Self::next_bool(settings.prune_tags);
+			}
+			Selection::Tags => {
+				settings.tags = match settings.tags {
+					FetchTags::Config => FetchTags::Auto,
+					FetchTags::Auto => FetchTags::All,
+					FetchTags::All => FetchTags::None,
+					FetchTags::None => FetchTags::Config,
+				};
+			}
+			Selection::Deepen => {
+				self.deepen = self.deepen.next();
+			}
+			Selection::DeepenArg | Selection::Refspec => (),
+		}
+	}
+
+	const fn next_bool(value: Option<bool>) -> Option<bool> {
+		match value {
+			None => Some(true),
+			Some(true) => Some(false),
+			Some(false) => None,
+		}
+	}
+
+	const fn bool_text(value: Option<bool>) -> &'static str {
+		match value {
+			None => "gitconfig",
+			Some(true) => "on",
+			Some(false) => "off",
 		}
 	}
+
+	fn get_text_options(&self, width: u16) -> Vec<Line<'_>> {
+		let tags = match self.settings.tags {
+			FetchTags::Config => "gitconfig",
+			FetchTags::Auto => "auto",
+			FetchTags::All => "all",
+			FetchTags::None => "none",
+		};
+		let deepen = match self.deepen {
+			Deepen::Off => "off",
+			Deepen::By => "by commits",
+			Deepen::Since => "since date",
+			Deepen::Unshallow => "unshallow",
+		};
+
+		vec![
+			self.option_line(
+				width,
+				"remote",
+				self.settings.remote.as_deref().unwrap_or("all"),
+				Selection::Remote,
+			),
+			self.option_line(
+				width,
+				"prune",
+				Self::bool_text(self.settings.prune),
+				Selection::Prune,
+			),
+			self.option_line(
+				width,
+				"prune tags",
+				Self::bool_text(self.settings.prune_tags),
+				Selection::PruneTags,
+			),
+			self.option_line(width, "tags", tags, Selection::Tags),
+			self.option_line(
+				width,
+				"deepen",
+				deepen,
+				Selection::Deepen,
+			),
+		]
+	}
+
+	fn label(&self, label: &str, selection: Selection) -> Line<'_> {
+		Line::from(Span::styled(
+			label.to_string(),
+			self.theme.text(self.selection == selection, false),
+		))
+	}
+
+	fn option_line(
+		&self,
+		width: u16,
+		name: &str,
+		value: &str,
+		selection: Selection,
+	) -> Line<'_> {
+		let half = usize::from(width / 2);
+		let selected = self.selection == selection;
+
+		Line::from(vec![
+			Span::styled(
+				string_width_align(name, half),
+				self.theme.text(selected, false),
+			),
+			Span::styled(
+				format!("{value:^half$}"),
+				self.theme.text(true, selected),
+			),
+		])
+	}
+
+	fn draw_options(&self, f: &mut Frame) -> Result<()> {
+		const SIZE: (u16, u16) = (50, 10);
+		let area =
+			ui::centered_rect_absolute(SIZE.0, SIZE.1, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.borders(Borders::all())
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true))
+				.title(Span::styled(
+					strings::FETCH_POPUP_MSG,
+					self.theme.title(true),
+				)),
+			area,
+		);
+
+		let chunks = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints(
+				[
+					Constraint::Length(5),
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+				]
+				.as_ref(),
+			)
+			.split(area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}));
+
+		f.render_widget(
+			Paragraph::new(self.get_text_options(chunks[0].width)),
+			chunks[0],
+		);
+
+		if self.deepen.takes_arg() {
+			let cols = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[Constraint::Length(12), Constraint::Min(1)]
+						.as_ref(),
+				)
+				.split(chunks[1]);
+
+			f.render_widget(
+				Paragraph::new(self.label(
+					if self.deepen == Deepen::By {
+						"commits:"
+					} else {
+						"since:"
+					},
+					Selection::DeepenArg,
+				)),
+				cols[0],
+			);
+			self.deepen_arg.draw(f, cols[1])?;
+		}
+
+		f.render_widget(
+			Paragraph::new(
+				self.label("refspec:", Selection::Refspec),
+			),
+			chunks[2],
+		);
+		self.refspec.draw(f, chunks[3])?;
+
+		Ok(())
+	}
 }
 
 impl DrawableComponent for FetchPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.visible {
+			if self.options_visible {
+				return self.draw_options(f);
+			}
+
 			let progress = self.progress.unwrap_or_default().progress;
 
 			let area = ui::centered_rect_absolute(30, 3, f.area());
@@ -156,6 +544,40 @@ impl Component for FetchPopup {
 				out.clear();
 			}
 
+			if self.options_visible || force_all {
+				out.push(CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				));
+				out.push(CommandInfo::new(
+					strings::commands::scroll_popup(&self.key_config),
+					true,
+					true,
+				));
+				out.push(CommandInfo::new(
+					strings::commands::toggle_option(
+						&self.key_config,
+					),
+					!matches!(
+						self.selection,
+						Selection::Refspec | Selection::DeepenArg
+					),
+					true,
+				));
+				out.push(CommandInfo::new(
+					strings::commands::confirm_action(
+						&self.key_config,
+					),
+					true,
+					true,
+				));
+
+				if !force_all {
+					return visibility_blocking(self);
+				}
+			}
+
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
@@ -171,8 +593,41 @@ impl Component for FetchPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(_) = ev {
-				if self.input_cred.is_visible() {
+			if let Event::Key(key) = ev {
+				if self.options_visible {
+					if key_match(key, self.key_config.keys.exit_popup)
+					{
+						self.hide();
+					} else if key_match(
+						key,
+						self.key_config.keys.enter,
+					) {
+						try_or_popup!(
+							self,
+							"fetch error:",
+							self.confirm_options()
+						);
+					} else if key_match(
+						key,
+						self.key_config.keys.popup_up,
+					) {
+						self.move_selection(true);
+					} else if key_match(
+						key,
+						self.key_config.keys.popup_down,
+					) {
+						self.move_selection(false);
+					} else if self.selection == Selection::Refspec {
+						self.refspec.event(ev)?;
+					} else if self.selection == Selection::DeepenArg {
+						self.deepen_arg.event(ev)?;
+					} else if key_match(
+						key,
+						self.key_config.keys.log_mark_commit,
+					) {
+						self.toggle_option();
+					}
+				} else if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
 					if self.input_cred.get_cred().is_complete()
@@ -196,6 +651,7 @@ impl Component for FetchPopup {
 
 	fn hide(&mut self) {
 		self.visible = false;
+		self.options_visible = false;
 	}
 
 	fn show(&mut self) -> Result<()> {
@@ -13,8 +13,9 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{CommitId, CommitTags},
-	AsyncDiff, AsyncGitNotification, DiffParams, DiffType,
+	sync::{commit_files::OldNew, CommitId, CommitTags, StashParts},
+	AsyncDiff, AsyncGitNotification, CommitFilesParams, DiffParams,
+	DiffType,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -31,6 +32,8 @@ pub struct InspectCommitOpen {
 	/// in case we wanna compare
 	pub compare_id: Option<CommitId>,
 	pub tags: Option<CommitTags>,
+	/// allows to look at the parts of a stash separately
+	pub stash: Option<StashParts>,
 }
 
 impl InspectCommitOpen {
@@ -39,6 +42,7 @@ impl InspectCommitOpen {
 			commit_id,
 			compare_id: None,
 			tags: None,
+			stash: None,
 		}
 	}
 
@@ -50,6 +54,46 @@ impl InspectCommitOpen {
 			commit_id,
 			compare_id: None,
 			tags,
+			stash: None,
+		}
+	}
+
+	pub const fn new_stash(parts: StashParts) -> Self {
+		Self {
+			commit_id: parts.worktree,
+			compare_id: None,
+			tags: None,
+			stash: Some(parts),
+		}
+	}
+}
+
+/// part of a stash the inspector shows
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum StashPart {
+	/// changes of tracked files in the worktree
+	#[default]
+	Tracked,
+	Index,
+	Untracked,
+}
+
+impl StashPart {
+	const fn next(self, parts: &StashParts) -> Self {
+		match self {
+			Self::Tracked => Self::Index,
+			Self::Index if parts.untracked.is_some() => {
+				Self::Untracked
+			}
+			Self::Index | Self::Untracked => Self::Tracked,
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Tracked => "Tracked",
+			Self::Index => "Index",
+			Self::Untracked => "Untracked",
 		}
 	}
 }
@@ -57,6 +101,7 @@ impl InspectCommitOpen {
 pub struct InspectCommitPopup {
 	queue: Queue,
 	open_request: Option<InspectCommitOpen>,
+	stash_part: StashPart,
 	diff: DiffComponent,
 	details: CommitDetailsComponent,
 	git_diff: AsyncDiff,
@@ -136,6 +181,21 @@ impl Component for InspectCommitPopup {
 				true,
 				true,
 			));
+
+			if let Some(parts) = self
+				.open_request
+				.as_ref()
+				.and_then(|open| open.stash.as_ref())
+			{
+				out.push(CommandInfo::new(
+					strings::commands::stash_next_part(
+						&self.key_config,
+						self.stash_part.next(parts).name(),
+					),
+					true,
+					true,
+				));
+			}
 		}
 
 		visibility_blocking(self)
@@ -173,12 +233,24 @@ impl Component for InspectCommitPopup {
 					self.hide_stacked(false);
 				} else if key_match(
 					e,
-					self.key_config.keys.open_file_tree,
+					self.key_config.keys.stash_next_part,
 				) {
-					if let Some(commit_id) = self
+					if let Some(parts) = self
 						.open_request
 						.as_ref()
-						.map(|open_commit| open_commit.commit_id)
+						.and_then(|open| open.stash)
+					{
+						self.stash_part =
+							self.stash_part.next(&parts);
+						self.update()?;
+					}
+				} else if key_match(
+					e,
+					self.key_config.keys.open_file_tree,
+				) {
+					if let Some(commit_id) = self
+						.commit_files_params()
+						.map(|params| params.id)
 					{
 						self.hide_stacked(true);
 						self.queue.push(InternalEvent::OpenPopup(
@@ -224,6 +296,7 @@ impl InspectCommitPopup {
 			details: CommitDetailsComponent::new(env),
 			diff: DiffComponent::new(env, true),
 			open_request: None,
+			stash_part: StashPart::default(),
 			git_diff: AsyncDiff::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -237,6 +310,7 @@ impl InspectCommitPopup {
 	///
 	pub fn open(&mut self, open: InspectCommitOpen) -> Result<()> {
 		self.open_request = Some(open);
+		self.stash_part = StashPart::default();
 		self.show()?;
 
 		Ok(())
@@ -257,6 +331,8 @@ impl InspectCommitPopup {
 				self.update()?;
 			} else if ev == AsyncGitNotification::Diff {
 				self.update_diff()?;
+			} else {
+				self.details.update_git(ev);
 			}
 		}
 
@@ -266,14 +342,22 @@ impl InspectCommitPopup {
 	/// called when any tree component changed selection
 	pub fn update_diff(&mut self) -> Result<()> {
 		if self.is_visible() {
-			if let Some(request) = &self.open_request {
+			if let Some(params) = self.commit_files_params() {
 				if let Some(f) = self.details.files().selection_file()
 				{
+					let diff_type = params.other.map_or(
+						DiffType::Commit(params.id),
+						|old| {
+							DiffType::Commits(OldNew {
+								old,
+								new: params.id,
+							})
+						},
+					);
+
 					let diff_params = DiffParams {
 						path: f.path.clone(),
-						diff_type: DiffType::Commit(
-							request.commit_id,
-						),
+						diff_type,
 						options: self.options.borrow().diff_options(),
 					};
 
@@ -301,7 +385,7 @@ impl InspectCommitPopup {
 	fn update(&mut self) -> Result<()> {
 		if let Some(request) = &self.open_request {
 			self.details.set_commits(
-				Some(request.commit_id.into()),
+				self.commit_files_params(),
 				request.tags.as_ref(),
 			)?;
 			self.update_diff()?;
@@ -310,6 +394,23 @@ impl InspectCommitPopup {
 		Ok(())
 	}
 
+	/// files to show, a stash shows one of its parts at a time
+	fn commit_files_params(&self) -> Option<CommitFilesParams> {
+		let request = self.open_request.as_ref()?;
+
+		let Some(parts) = request.stash else {
+			return Some(request.commit_id.into());
+		};
+
+		Some(match (self.stash_part, parts.untracked) {
+			(StashPart::Untracked, Some(untracked)) => {
+				untracked.into()
+			}
+			(StashPart::Index, _) => parts.index.into(),
+			_ => (parts.worktree, parts.base).into(),
+		})
+	}
+
 	fn can_focus_diff(&self) -> bool {
 		self.details.files().selection_file().is_some()
 	}
@@ -0,0 +1,377 @@
+use crate::{
+	app::Environment,
+	components::{
+		string_width_align, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+		InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	report_hook, strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, BranchMergeOptions, BranchType, MergeMode, MergeOutcome,
+	RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+const MAX_CONFLICTS_SHOWN: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Selection {
+	Mode,
+	Message,
+}
+
+const fn next_mode(mode: MergeMode) -> MergeMode {
+	match mode {
+		MergeMode::Default => MergeMode::NoFastForward,
+		MergeMode::NoFastForward => MergeMode::FastForwardOnly,
+		MergeMode::FastForwardOnly => MergeMode::Squash,
+		MergeMode::Squash => MergeMode::Default,
+	}
+}
+
+const fn mode_text(mode: MergeMode) -> &'static str {
+	match mode {
+		MergeMode::Default => "default",
+		MergeMode::NoFastForward => "no-ff",
+		MergeMode::FastForwardOnly => "ff-only",
+		MergeMode::Squash => "squash",
+	}
+}
+
+/// lets the user choose how to merge a branch into HEAD and
+/// previews the files that would conflict
+pub struct MergePopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	selection: Selection,
+	branch: String,
+	branch_type: BranchType,
+	mode: MergeMode,
+	message: TextInputComponent,
+	conflicts: Vec<String>,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl MergePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut message = TextInputComponent::new(
+			env,
+			"",
+			"commit message (optional)",
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		message.embed();
+
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			selection: Selection::Mode,
+			branch: String::new(),
+			branch_type: BranchType::Local,
+			mode: MergeMode::Default,
+			message,
+			conflicts: Vec::new(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(
+		&mut self,
+		branch: String,
+		branch_type: BranchType,
+	) -> Result<()> {
+		self.conflicts = sync::merge_conflicts_preview(
+			&self.repo.borrow(),
+			&branch,
+			branch_type,
+		)?;
+
+		self.branch = branch;
+		self.branch_type = branch_type;
+		self.mode = MergeMode::Default;
+		self.selection = Selection::Mode;
+
+		self.show()?;
+		self.message.show()?;
+		self.message.clear();
+		self.message.enabled(false);
+
+		Ok(())
+	}
+
+	const fn mode_takes_message(&self) -> bool {
+		matches!(
+			self.mode,
+			MergeMode::NoFastForward | MergeMode::Squash
+		)
+	}
+
+	fn merge(&mut self) -> Result<()> {
+		let message = self.message.get_text().trim();
+		let options = BranchMergeOptions {
+			mode: self.mode,
+			message: (self.mode_takes_message()
+				&& !message.is_empty())
+			.then(|| message.to_string()),
+		};
+
+		let outcome = sync::merge_branch_with_options(
+			&self.repo.borrow(),
+			&self.branch,
+			self.branch_type,
+			&options,
+		)?;
+
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		match outcome {
+			MergeOutcome::Pending => {
+				self.queue.push(InternalEvent::TabSwitchStatus);
+			}
+			MergeOutcome::FastForward(_)
+			| MergeOutcome::Committed(_) => {
+				report_hook!(
+					self,
+					"post-merge",
+					sync::hooks_post_merge(
+						&self.repo.borrow(),
+						self.mode == MergeMode::Squash
+					)
+				);
+			}
+			MergeOutcome::UpToDate => (),
+		}
+
+		Ok(())
+	}
+
+	fn move_selection(&mut self) {
+		self.selection = match self.selection {
+			Selection::Mode if self.mode_takes_message() => {
+				Selection::Message
+			}
+			_ => Selection::Mode,
+		};
+
+		self.message.enabled(self.selection == Selection::Message);
+	}
+
+	fn get_text_options(&self, width: u16) -> Vec<Line<'_>> {
+		let half = usize::from(width / 2);
+		let mode = mode_text(self.mode);
+		let mode_selected = self.selection == Selection::Mode;
+
+		vec![
+			Line::from(vec![
+				Span::styled(
+					string_width_align("mode", half),
+					self.theme.text(mode_selected, false),
+				),
+				Span::styled(
+					format!("{mode:^half$}"),
+					self.theme.text(true, mode_selected),
+				),
+			]),
+			Line::from(Span::styled(
+				"message:",
+				self.theme.text(
+					self.selection == Selection::Message,
+					false,
+				),
+			)),
+		]
+	}
+
+	fn get_text_conflicts(&self) -> Vec<Line<'_>> {
+		let mut lines = vec![Line::from(Span::styled(
+			strings::merge_conflicts_preview(self.conflicts.len()),
+			self.theme.text(true, false),
+		))];
+
+		lines.extend(
+			self.conflicts.iter().take(MAX_CONFLICTS_SHOWN).map(
+				|path| {
+					Line::from(Span::styled(
+						format!("  {path}"),
+						self.theme.text(false, false),
+					))
+				},
+			),
+		);
+
+		if self.conflicts.len() > MAX_CONFLICTS_SHOWN {
+			lines.push(Line::from(Span::styled(
+				format!(
+					"  ... {} more",
+					self.conflicts.len() - MAX_CONFLICTS_SHOWN
+				),
+				self.theme.text(false, false),
+			)));
+		}
+
+		lines
+	}
+}
+
+impl DrawableComponent for MergePopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			let conflicts = self.get_text_conflicts();
+			let conflicts_height =
+				u16::try_from(conflicts.len()).unwrap_or(u16::MAX);
+
+			let area = ui::centered_rect_absolute(
+				60,
+				conflicts_height.saturating_add(6),
+				f.area(),
+			);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::all())
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true))
+					.title(Span::styled(
+						strings::merge_popup_title(
+							&self.key_config,
+							&self.branch,
+						),
+						self.theme.title(true),
+					)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Length(2),
+						Constraint::Length(1),
+						Constraint::Length(1),
+						Constraint::Min(1),
+					]
+					.as_ref(),
+				)
+				.split(area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			f.render_widget(
+				Paragraph::new(
+					self.get_text_options(chunks[0].width),
+				),
+				chunks[0],
+			);
+			self.message.draw(f, chunks[1])?;
+			f.render_widget(Paragraph::new(conflicts), chunks[3]);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for MergePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::scroll_popup(&self.key_config),
+				self.mode_takes_message(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::toggle_option(&self.key_config),
+				self.selection == Selection::Mode,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = ev {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.enter) {
+					try_or_popup!(
+						self,
+						"merge branch error:",
+						self.merge()
+					);
+				} else if key_match(
+					key,
+					self.key_config.keys.popup_up,
+				) || key_match(
+					key,
+					self.key_config.keys.popup_down,
+				) {
+					self.move_selection();
+				} else if self.selection == Selection::Message {
+					self.message.event(ev)?;
+				} else if key_match(
+					key,
+					self.key_config.keys.log_mark_commit,
+				) {
+					self.mode = next_mode(self.mode);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -1,6 +1,8 @@
 mod blame_file;
 mod branchlist;
+mod clone;
 mod commit;
+mod compare_branches;
 mod compare_commits;
 mod confirm;
 mod create_branch;
@@ -13,11 +15,14 @@ mod help;
 mod inspect_commit;
 #[cfg(not(feature = "disable-log-files-tabs"))]
 mod log_search;
+mod merge;
 mod msg;
+mod note;
 mod options;
 mod pull;
 mod push;
 mod push_tags;
+mod push_to;
 mod remotelist;
 mod rename_branch;
 mod rename_remote;
@@ -28,10 +33,13 @@ mod submodules;
 mod tag_commit;
 mod taglist;
 mod update_remote_url;
+mod upstream;
 
 pub use blame_file::{BlameFileOpen, BlameFilePopup};
 pub use branchlist::BranchListPopup;
+pub use clone::ClonePopup;
 pub use commit::CommitPopup;
+pub use compare_branches::CompareBranchesPopup;
 pub use compare_commits::CompareCommitsPopup;
 pub use confirm::ConfirmPopup;
 pub use create_branch::CreateBranchPopup;
@@ -44,11 +52,14 @@ pub use help::HelpPopup;
 pub use inspect_commit::{InspectCommitOpen, InspectCommitPopup};
 #[cfg(not(feature = "disable-log-files-tabs"))]
 pub use log_search::LogSearchPopupPopup;
+pub use merge::MergePopup;
 pub use msg::MsgPopup;
+pub use note::NotePopup;
 pub use options::{AppOption, OptionsPopup};
 pub use pull::PullPopup;
-pub use push::PushPopup;
+pub use push::{PushPopup, PushTarget};
 pub use push_tags::PushTagsPopup;
+pub use push_to::PushToPopup;
 pub use remotelist::RemoteListPopup;
 pub use rename_branch::RenameBranchPopup;
 pub use rename_remote::RenameRemotePopup;
@@ -59,6 +70,7 @@ pub use submodules::SubmodulesListPopup;
 pub use tag_commit::TagCommitPopup;
 pub use taglist::TagListPopup;
 pub use update_remote_url::UpdateRemoteUrlPopup;
+pub use upstream::UpstreamPopup;
 
 use crate::ui::style::Theme;
 use ratatui::{
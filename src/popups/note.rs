@@ -0,0 +1,196 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	popups::ExternalEditorPopup,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+};
+use anyhow::Result;
+use asyncgit::{
+	message_prettify,
+	sync::{self, CommitId, RepoPathRef},
+};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+use std::{
+	fs::{self, File},
+	io::Write,
+};
+
+/// edits the git note of a commit, an empty note removes it
+pub struct NotePopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	commit_id: Option<CommitId>,
+	/// set while the note is open in the external editor
+	editing_externally: bool,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for NotePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for NotePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::note_confirm_msg(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::commit_open_editor(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.commit) {
+					try_or_popup!(self, "note error:", self.save());
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.open_commit_editor,
+				) {
+					self.editing_externally = true;
+					self.queue.push(
+						InternalEvent::OpenExternalEditor(None),
+					);
+					self.hide();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.input.event(ev)?;
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl NotePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				&strings::note_popup_title(),
+				&strings::note_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Multiline),
+			commit_id: None,
+			editing_externally: false,
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	/// edit the note of `id` in the notes ref `core.notesRef`
+	pub fn open(&mut self, id: CommitId) -> Result<()> {
+		let note = sync::get_note(&self.repo.borrow(), id, None)?;
+
+		self.commit_id = Some(id);
+		self.input.set_text(
+			note.map(|note| note.trim_end().to_string())
+				.unwrap_or_default(),
+		);
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// whether the external editor was requested for the note
+	pub const fn is_editing_externally(&self) -> bool {
+		self.editing_externally
+	}
+
+	/// edit the note in the external editor and show the result
+	pub fn show_editor(&mut self) -> Result<()> {
+		self.editing_externally = false;
+
+		let file_path = sync::repo_dir(&self.repo.borrow())?
+			.join("NOTES_EDITMSG");
+
+		{
+			let mut file = File::create(&file_path)?;
+			file.write_fmt(format_args!(
+				"{}\n",
+				self.input.get_text()
+			))?;
+			file.write_all(strings::note_editor_msg().as_bytes())?;
+		}
+
+		let result = ExternalEditorPopup::open_file_in_editor(
+			&self.repo.borrow(),
+			&file_path,
+		)
+		.and_then(|()| Ok(fs::read_to_string(&file_path)?));
+
+		fs::remove_file(&file_path)?;
+		self.show()?;
+
+		let note = message_prettify(result?, Some(b'#'))?;
+		self.input.set_text(note.trim_end().to_string());
+
+		Ok(())
+	}
+
+	fn save(&mut self) -> Result<()> {
+		if let Some(id) = self.commit_id {
+			let note = message_prettify(self.input.get_text(), None)?;
+
+			if note.is_empty() {
+				sync::remove_note(&self.repo.borrow(), id, None)?;
+			} else {
+				sync::set_note(&self.repo.borrow(), id, None, &note)?;
+			}
+		}
+
+		self.input.clear();
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+}
@@ -7,7 +7,7 @@ use crate::{
 	keys::SharedKeyConfig,
 	popups::PushPopup,
 	queue::{Action, InternalEvent, Queue},
-	strings, try_or_popup,
+	report_hook, strings,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
@@ -19,7 +19,7 @@ use asyncgit::{
 			need_username_password_for_fetch, BasicAuthCredential,
 		},
 		remotes::get_default_remote_for_fetch,
-		RepoPathRef,
+		HookResult, RepoPathRef,
 	},
 	AsyncGitNotification, AsyncPull, FetchRequest, RemoteProgress,
 };
@@ -155,6 +155,15 @@ impl PullPopup {
 			if let Err(e) = ff_res {
 				log::trace!("ff failed: {e}");
 				self.confirm_merge(branch_compare.behind);
+			} else {
+				report_hook!(
+					self,
+					"post-merge",
+					sync::hooks_post_merge(
+						&self.repo.borrow(),
+						false
+					)
+				);
 			}
 		}
 
@@ -165,23 +174,58 @@ impl PullPopup {
 
 	pub fn try_conflict_free_merge(&self, rebase: bool) {
 		if rebase {
-			try_or_popup!(
-				self,
-				"rebase failed:",
-				sync::merge_upstream_rebase(
-					&self.repo.borrow(),
-					&self.branch
-				)
-			);
+			// run pre rebase hook - can reject rebase
+			match sync::hooks_pre_rebase(
+				&self.repo.borrow(),
+				&format!("{}@{{upstream}}", self.branch),
+				None,
+			) {
+				Ok(HookResult::Ok) => (),
+				res => {
+					report_hook!(self, "pre-rebase", res);
+					return;
+				}
+			}
+
+			match sync::merge_upstream_rebase(
+				&self.repo.borrow(),
+				&self.branch,
+			) {
+				Ok((_, hook)) => {
+					report_hook!(
+						self,
+						"post-rewrite",
+						anyhow::Ok(hook)
+					);
+				}
+				Err(e) => {
+					log::error!("rebase failed: {e}");
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("rebase failed:\n{e}"),
+					));
+				}
+			}
 		} else {
-			try_or_popup!(
-				self,
-				"merge failed:",
-				sync::merge_upstream_commit(
-					&self.repo.borrow(),
-					&self.branch
-				)
-			);
+			match sync::merge_upstream_commit(
+				&self.repo.borrow(),
+				&self.branch,
+			) {
+				Ok(Some(_)) => report_hook!(
+					self,
+					"post-merge",
+					sync::hooks_post_merge(
+						&self.repo.borrow(),
+						false
+					)
+				),
+				Ok(None) => (),
+				Err(e) => {
+					log::error!("merge failed: {e}");
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("merge failed:\n{e}"),
+					));
+				}
+			}
 		}
 	}
 
@@ -16,12 +16,12 @@ use asyncgit::{
 			extract_username_password_for_push,
 			need_username_password_for_push, BasicAuthCredential,
 		},
-		get_branch_remote, hooks_pre_push,
+		get_branch_remote,
 		remotes::get_default_remote_for_push,
-		HookResult, RepoPathRef,
+		RepoPathRef,
 	},
-	AsyncGitNotification, AsyncPush, PushRequest, PushType,
-	RemoteProgress, RemoteProgressState,
+	AsyncGitNotification, AsyncPush, PushForce, PushRequest,
+	PushType, RemoteProgress, RemoteProgressState,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -30,25 +30,40 @@ use ratatui::{
 	widgets::{Block, BorderType, Borders, Clear, Gauge},
 	Frame,
 };
+use std::collections::VecDeque;
 
 ///
 #[derive(PartialEq, Eq)]
 enum PushComponentModifier {
 	None,
 	Force,
+	ForceWithLease,
 	Delete,
 	ForceDelete,
 }
 
 impl PushComponentModifier {
-	pub(crate) fn force(&self) -> bool {
-		self == &Self::Force || self == &Self::ForceDelete
+	pub(crate) const fn force(&self) -> PushForce {
+		match self {
+			Self::Force | Self::ForceDelete => PushForce::Force,
+			Self::ForceWithLease => PushForce::WithLease,
+			Self::None | Self::Delete => PushForce::None,
+		}
 	}
 	pub(crate) fn delete(&self) -> bool {
 		self == &Self::Delete || self == &Self::ForceDelete
 	}
 }
 
+/// explicit remote and branch name to push to
+#[derive(Clone, Debug)]
+pub struct PushTarget {
+	pub remote: String,
+	pub dst_branch: String,
+	/// make the pushed branch the upstream (`-u`)
+	pub set_upstream: bool,
+}
+
 ///
 pub struct PushPopup {
 	repo: RepoPathRef,
@@ -59,6 +74,10 @@ pub struct PushPopup {
 	pending: bool,
 	branch: String,
 	push_type: PushType,
+	target: Option<PushTarget>,
+	/// remote branches still to be deleted after the current push
+	queued_deletes: VecDeque<PushTarget>,
+	cred: Option<BasicAuthCredential>,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
@@ -76,6 +95,9 @@ impl PushPopup {
 			visible: false,
 			branch: String::new(),
 			push_type: PushType::Branch,
+			target: None,
+			queued_deletes: VecDeque::new(),
+			cred: None,
 			git_push: AsyncPush::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -92,18 +114,62 @@ impl PushPopup {
 		&mut self,
 		branch: String,
 		push_type: PushType,
-		force: bool,
+		force: PushForce,
 		delete: bool,
 	) -> Result<()> {
 		self.branch = branch;
 		self.push_type = push_type;
 		self.modifier = match (force, delete) {
-			(true, true) => PushComponentModifier::ForceDelete,
-			(false, true) => PushComponentModifier::Delete,
-			(true, false) => PushComponentModifier::Force,
-			(false, false) => PushComponentModifier::None,
+			(PushForce::None, true) => PushComponentModifier::Delete,
+			(_, true) => PushComponentModifier::ForceDelete,
+			(PushForce::Force, false) => PushComponentModifier::Force,
+			(PushForce::WithLease, false) => {
+				PushComponentModifier::ForceWithLease
+			}
+			(PushForce::None, false) => PushComponentModifier::None,
 		};
+		self.target = None;
+		self.queued_deletes.clear();
+
+		self.start(force)
+	}
+
+	/// push `branch` to a remote and branch name picked by the user
+	pub fn push_to(
+		&mut self,
+		branch: String,
+		target: PushTarget,
+	) -> Result<()> {
+		self.branch = branch;
+		self.push_type = PushType::Branch;
+		self.modifier = PushComponentModifier::None;
+		self.target = Some(target);
+		self.queued_deletes.clear();
+
+		self.start(PushForce::None)
+	}
 
+	/// deletes the `dst_branch` of each target on its remote,
+	/// one push after another
+	pub fn delete_remote_branches(
+		&mut self,
+		targets: Vec<PushTarget>,
+	) -> Result<()> {
+		self.queued_deletes = targets.into();
+
+		if let Some(target) = self.queued_deletes.pop_front() {
+			self.branch.clone_from(&target.dst_branch);
+			self.push_type = PushType::Branch;
+			self.modifier = PushComponentModifier::Delete;
+			self.target = Some(target);
+
+			self.start(PushForce::None)?;
+		}
+
+		Ok(())
+	}
+
+	fn start(&mut self, force: PushForce) -> Result<()> {
 		self.show()?;
 
 		if need_username_password_for_push(&self.repo.borrow())? {
@@ -125,9 +191,11 @@ impl PushPopup {
 	fn push_to_remote(
 		&mut self,
 		cred: Option<BasicAuthCredential>,
-		force: bool,
+		force: PushForce,
 	) -> Result<()> {
-		let remote = if let Ok(Some(remote)) =
+		let remote = if let Some(target) = &self.target {
+			target.remote.clone()
+		} else if let Ok(Some(remote)) =
 			get_branch_remote(&self.repo.borrow(), &self.branch)
 		{
 			log::info!("push: branch '{}' has upstream for remote '{}' - using that",self.branch,remote);
@@ -144,27 +212,23 @@ impl PushPopup {
 			remote
 		};
 
-		// run pre push hook - can reject push
-		if let HookResult::NotOk(e) =
-			hooks_pre_push(&self.repo.borrow())?
-		{
-			log::error!("pre-push hook failed: {e}");
-			self.queue.push(InternalEvent::ShowErrorMsg(format!(
-				"pre-push hook failed:\n{e}"
-			)));
-			self.pending = false;
-			self.visible = false;
-			return Ok(());
-		}
-
 		self.pending = true;
 		self.progress = None;
+		self.cred.clone_from(&cred);
 		self.git_push.request(PushRequest {
 			remote,
 			branch: self.branch.clone(),
+			dst_branch: self
+				.target
+				.as_ref()
+				.map(|target| target.dst_branch.clone()),
 			push_type: self.push_type,
 			force,
 			delete: self.modifier.delete(),
+			set_upstream: self
+				.target
+				.as_ref()
+				.is_some_and(|target| target.set_upstream),
 			basic_credential: cred,
 		})?;
 		Ok(())
@@ -192,6 +256,17 @@ impl PushPopup {
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("push failed:\n{err}"),
 				));
+				self.queued_deletes.clear();
+			} else if let Some(target) =
+				self.queued_deletes.pop_front()
+			{
+				self.branch.clone_from(&target.dst_branch);
+				self.target = Some(target);
+
+				return self.push_to_remote(
+					self.cred.clone(),
+					self.modifier.force(),
+				);
 			}
 			self.hide();
 		}
@@ -256,10 +331,16 @@ impl DrawableComponent for PushPopup {
 					.block(
 						Block::default()
 							.title(Span::styled(
-								if self.modifier.force() {
-									strings::FORCE_PUSH_POPUP_MSG
-								} else {
-									strings::PUSH_POPUP_MSG
+								match self.modifier.force() {
+									PushForce::Force => {
+										strings::FORCE_PUSH_POPUP_MSG
+									}
+									PushForce::WithLease => {
+										strings::FORCE_PUSH_WITH_LEASE_POPUP_MSG
+									}
+									PushForce::None => {
+										strings::PUSH_POPUP_MSG
+									}
 								},
 								self.theme.title(true),
 							))
@@ -0,0 +1,353 @@
+use super::PushTarget;
+use crate::{
+	app::Environment,
+	components::{
+		string_width_align, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+		InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	get_branch_remote, get_branch_upstream_merge,
+	get_default_remote_for_push, get_remotes, validate_branch_name,
+	RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Selection {
+	Remote,
+	Branch,
+	SetUpstream,
+}
+
+impl Selection {
+	const fn next(self) -> Self {
+		match self {
+			Self::Remote => Self::Branch,
+			Self::Branch => Self::SetUpstream,
+			Self::SetUpstream => Self::Remote,
+		}
+	}
+
+	const fn prev(self) -> Self {
+		match self {
+			Self::Remote => Self::SetUpstream,
+			Self::Branch => Self::Remote,
+			Self::SetUpstream => Self::Branch,
+		}
+	}
+}
+
+/// lets the user pick remote and branch name to push to
+pub struct PushToPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	selection: Selection,
+	branch: String,
+	remotes: Vec<String>,
+	remote: usize,
+	set_upstream: bool,
+	dst_branch: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl PushToPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut dst_branch =
+			TextInputComponent::new(env, "", "branch name", false)
+				.with_input_type(InputType::Singleline);
+		dst_branch.embed();
+
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			selection: Selection::Branch,
+			branch: String::new(),
+			remotes: Vec::new(),
+			remote: 0,
+			set_upstream: false,
+			dst_branch,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, branch: String) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		self.remotes = get_remotes(&repo)?;
+
+		let upstream_remote = get_branch_remote(&repo, &branch)?;
+		// an inconclusive default falls back to the first remote
+		let remote = upstream_remote.clone().unwrap_or_else(|| {
+			get_default_remote_for_push(&repo).unwrap_or_default()
+		});
+		self.remote = self
+			.remotes
+			.iter()
+			.position(|r| r == &remote)
+			.unwrap_or_default();
+
+		let dst_branch = get_branch_upstream_merge(&repo, &branch)?
+			.and_then(|merge| {
+				merge.strip_prefix("refs/heads/").map(String::from)
+			})
+			.unwrap_or_else(|| branch.clone());
+
+		self.set_upstream = upstream_remote.is_none();
+		self.branch = branch;
+		self.selection = Selection::Branch;
+
+		self.show()?;
+		self.dst_branch.show()?;
+		self.dst_branch.set_text(dst_branch);
+		self.dst_branch.enabled(true);
+
+		Ok(())
+	}
+
+	fn is_valid(&self) -> bool {
+		!self.remotes.is_empty()
+			&& validate_branch_name(self.dst_branch.get_text().trim())
+				.unwrap_or_default()
+	}
+
+	fn confirm(&mut self) {
+		if !self.is_valid() {
+			return;
+		}
+
+		self.hide();
+
+		self.queue.push(InternalEvent::PushTo(
+			self.branch.clone(),
+			PushTarget {
+				remote: self.remotes[self.remote].clone(),
+				dst_branch: self.dst_branch.get_text().trim().into(),
+				set_upstream: self.set_upstream,
+			},
+		));
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		self.selection = if up {
+			self.selection.prev()
+		} else {
+			self.selection.next()
+		};
+
+		self.dst_branch.enabled(self.selection == Selection::Branch);
+	}
+
+	fn toggle_option(&mut self) {
+		match self.selection {
+			Selection::Remote => {
+				if !self.remotes.is_empty() {
+					self.remote =
+						(self.remote + 1) % self.remotes.len();
+				}
+			}
+			Selection::SetUpstream => {
+				self.set_upstream = !self.set_upstream;
+			}
+			Selection::Branch => (),
+		}
+	}
+
+	fn get_text_options(&self, width: u16) -> Vec<Line<'_>> {
+		let half = usize::from(width / 2);
+		let remote =
+			self.remotes.get(self.remote).map_or("", String::as_str);
+
+		vec![
+			Line::from(vec![
+				Span::styled(
+					string_width_align("remote", half),
+					self.theme.text(
+						self.selection == Selection::Remote,
+						false,
+					),
+				),
+				Span::styled(
+					format!("{remote:^half$}"),
+					self.theme.text(
+						true,
+						self.selection == Selection::Remote,
+					),
+				),
+			]),
+			Line::from(Span::styled(
+				"branch:",
+				self.theme
+					.text(self.selection == Selection::Branch, false),
+			)),
+		]
+	}
+
+	fn get_text_upstream(&self) -> Line<'_> {
+		let x_upstream = if self.set_upstream { "X" } else { " " };
+
+		Line::from(Span::styled(
+			format!("[{x_upstream}] set as upstream"),
+			self.theme.text(
+				self.selection == Selection::SetUpstream,
+				false,
+			),
+		))
+	}
+}
+
+impl DrawableComponent for PushToPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const SIZE: (u16, u16) = (50, 6);
+			let area =
+				ui::centered_rect_absolute(SIZE.0, SIZE.1, f.area());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::all())
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true))
+					.title(Span::styled(
+						strings::push_to_popup_title(
+							&self.key_config,
+							&self.branch,
+						),
+						self.theme.title(true),
+					)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Length(2),
+						Constraint::Length(1),
+						Constraint::Length(1),
+					]
+					.as_ref(),
+				)
+				.split(area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			f.render_widget(
+				Paragraph::new(
+					self.get_text_options(chunks[0].width),
+				),
+				chunks[0],
+			);
+			self.dst_branch.draw(f, chunks[1])?;
+			f.render_widget(
+				Paragraph::new(self.get_text_upstream()),
+				chunks[2],
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for PushToPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::scroll_popup(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::toggle_option(&self.key_config),
+				self.selection != Selection::Branch,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				self.is_valid(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = ev {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.confirm();
+				} else if key_match(
+					key,
+					self.key_config.keys.popup_up,
+				) {
+					self.move_selection(true);
+				} else if key_match(
+					key,
+					self.key_config.keys.popup_down,
+				) {
+					self.move_selection(false);
+				} else if self.selection == Selection::Branch {
+					self.dst_branch.event(ev)?;
+				} else if key_match(
+					key,
+					self.key_config.keys.log_mark_commit,
+				) {
+					self.toggle_option();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -5,18 +5,20 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
-	queue::{AppTabs, InternalEvent, Queue},
+	queue::{AppTabs, InternalEvent, NeedsUpdate, Queue},
 	strings,
-	tabs::StashingOptions,
+	tabs::{StashScope, StashingOptions},
 };
 use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use asyncgit::sync::{self, CommitId, RepoPathRef};
 use crossterm::event::Event;
 use ratatui::{layout::Rect, Frame};
 
 pub struct StashMsgPopup {
 	repo: RepoPathRef,
 	options: StashingOptions,
+	/// rename this stash instead of creating a new one
+	rename: Option<CommitId>,
 	input: TextInputComponent,
 	queue: Queue,
 	key_config: SharedKeyConfig,
@@ -59,17 +61,12 @@ impl Component for StashMsgPopup {
 
 			if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.enter) {
-					let result = sync::stash_save(
-						&self.repo.borrow(),
-						if self.input.get_text().is_empty() {
-							None
-						} else {
-							Some(self.input.get_text())
-						},
-						self.options.stash_untracked,
-						self.options.keep_index,
-					);
-					match result {
+					if let Some(id) = self.rename {
+						self.rename_stash(id);
+						return Ok(EventState::Consumed);
+					}
+
+					match self.stash() {
 						Ok(_) => {
 							self.input.clear();
 							self.hide();
@@ -124,6 +121,7 @@ impl StashMsgPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			options: StashingOptions::default(),
+			rename: None,
 			queue: env.queue.clone(),
 			input: TextInputComponent::new(
 				env,
@@ -140,5 +138,84 @@ impl StashMsgPopup {
 	///
 	pub fn options(&mut self, options: StashingOptions) {
 		self.options = options;
+		self.rename = None;
+		self.input
+			.set_title(strings::stash_popup_title(&self.key_config));
+	}
+
+	///
+	pub fn open_rename(
+		&mut self,
+		id: CommitId,
+		message: &str,
+	) -> Result<()> {
+		self.rename = Some(id);
+		self.input.set_title(strings::stash_rename_popup_title(
+			&self.key_config,
+		));
+
+		// the branch prefix is kept anyway
+		let message = message
+			.split_once(": ")
+			.map_or(message, |(_, message)| message);
+		self.input.set_text(message.to_string());
+
+		self.show()
+	}
+
+	fn rename_stash(&mut self, id: CommitId) {
+		if self.input.get_text().is_empty() {
+			return;
+		}
+
+		let result = sync::stash_rename(
+			&self.repo.borrow(),
+			id,
+			self.input.get_text(),
+		);
+
+		self.input.clear();
+		self.hide();
+
+		match result {
+			Ok(_) => {
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+			Err(e) => {
+				log::error!("stash rename: {e}");
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("stash rename error:\n{e}"),
+				));
+			}
+		}
+	}
+
+	fn stash(&self) -> asyncgit::Result<CommitId> {
+		let repo = self.repo.borrow();
+		let message = if self.input.get_text().is_empty() {
+			None
+		} else {
+			Some(self.input.get_text())
+		};
+
+		match self.options.scope {
+			StashScope::All => sync::stash_save(
+				&repo,
+				message,
+				self.options.stash_untracked,
+				self.options.keep_index,
+			),
+			StashScope::Staged => {
+				sync::stash_save_staged(&repo, message)
+			}
+			StashScope::Selected => sync::stash_save_paths(
+				&repo,
+				message,
+				&self.options.paths,
+				self.options.stash_untracked,
+				self.options.keep_index,
+			),
+		}
 	}
 }
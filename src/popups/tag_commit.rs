@@ -169,10 +169,14 @@ impl TagCommitPopup {
 				.and_then(|val| val.parse::<bool>().ok())
 				.unwrap_or_default();
 
-		anyhow::ensure!(!gpgsign, "config tag.gpgsign=true detected.\ngpg signing not supported.\ndeactivate in your repo/gitconfig to be able to tag without signing.");
-
 		let (tag_name, tag_annotation) = self.tag_info();
 
+		anyhow::ensure!(
+			!gpgsign || tag_annotation.is_some(),
+			"config tag.gpgsign=true detected.\nonly annotated tags can be signed, add an annotation [{}].",
+			self.key_config.get_hint(self.key_config.keys.tag_annotate)
+		);
+
 		if let Some(commit_id) = self.commit_id {
 			let result = sync::tag_commit(
 				&self.repo.borrow(),
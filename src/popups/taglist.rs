@@ -1,6 +1,7 @@
 use crate::components::{
 	time_to_string, visibility_blocking, CommandBlocking,
-	CommandInfo, Component, DrawableComponent, EventState,
+	CommandInfo, Component, DrawableComponent, EventState, InputType,
+	TextInputComponent,
 };
 use crate::{
 	app::Environment,
@@ -20,14 +21,16 @@ use asyncgit::{
 		BasicAuthCredential,
 	},
 	sync::{
-		self, get_tags_with_metadata, RepoPathRef, TagWithMetadata,
+		self, cmp_tag_versions, get_tags_with_metadata, RepoPathRef,
+		SignatureState, TagWithMetadata,
 	},
+	verify_tags::{AsyncVerifyTagsJob, TagSignatures},
 	AsyncGitNotification,
 };
 
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use ratatui::{
-	layout::{Constraint, Margin, Rect},
+	layout::{Constraint, Direction, Layout, Margin, Rect},
 	text::Span,
 	widgets::{
 		Block, BorderType, Borders, Cell, Clear, Row, Table,
@@ -37,12 +40,61 @@ use ratatui::{
 };
 use ui::style::SharedTheme;
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TagSort {
+	Date,
+	Version,
+	Name,
+}
+
+impl TagSort {
+	const fn next(self) -> Self {
+		match self {
+			Self::Date => Self::Version,
+			Self::Version => Self::Name,
+			Self::Name => Self::Date,
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Date => "date",
+			Self::Version => "version",
+			Self::Name => "name",
+		}
+	}
+
+	fn sort(self, tags: &mut [TagWithMetadata]) {
+		tags.sort_by(|a, b| match self {
+			// newest first
+			Self::Date => {
+				b.time.cmp(&a.time).then_with(|| a.name.cmp(&b.name))
+			}
+			// highest version first
+			Self::Version => cmp_tag_versions(&b.name, &a.name),
+			Self::Name => a.name.cmp(&b.name),
+		});
+	}
+}
+
+/// signature of a tag once the tags got verified
+enum TagSignature<'a> {
+	/// verifying failed, the error got logged
+	Failed,
+	Verified(&'a SignatureState),
+}
+
 ///
 pub struct TagListPopup {
 	repo: RepoPathRef,
 	theme: SharedTheme,
 	queue: Queue,
+	/// all tags of the repo, `tags` is the filtered subset
+	all_tags: Vec<TagWithMetadata>,
 	tags: Option<Vec<TagWithMetadata>>,
+	sort: TagSort,
+	filter: TextInputComponent,
+	filter_focused: bool,
 	visible: bool,
 	table_state: std::cell::Cell<TableState>,
 	current_height: std::cell::Cell<usize>,
@@ -50,6 +102,8 @@ pub struct TagListPopup {
 	has_remotes: bool,
 	basic_credential: Option<BasicAuthCredential>,
 	async_remote_tags: AsyncSingleJob<AsyncRemoteTagsJob>,
+	signatures: Option<TagSignatures>,
+	async_verify_tags: AsyncSingleJob<AsyncVerifyTagsJob>,
 	key_config: SharedKeyConfig,
 }
 
@@ -75,7 +129,7 @@ impl DrawableComponent for TagListPopup {
 				});
 
 			let constraints = [
-				// symbol if tag is not yet on remote and can be pushed
+				// symbol if tag is on the remote or not yet and can be pushed
 				Constraint::Length(1),
 				// tag name
 				Constraint::Length(tag_name_width.try_into()?),
@@ -85,6 +139,8 @@ impl DrawableComponent for TagListPopup {
 				Constraint::Length(19),
 				// attachment
 				Constraint::Length(1),
+				// signature, once verified
+				Constraint::Length(1),
 				// commit id
 				Constraint::Percentage(100),
 			];
@@ -92,33 +148,52 @@ impl DrawableComponent for TagListPopup {
 			let rows = self.get_rows();
 			let number_of_rows = rows.len();
 
+			let block = Block::default()
+				.borders(Borders::ALL)
+				.title(Span::styled(
+					strings::title_tags(),
+					self.theme.title(true),
+				))
+				.border_style(self.theme.block(true))
+				.border_type(BorderType::Thick);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints([
+					Constraint::Min(1),
+					Constraint::Length(u16::from(
+						self.filter.is_visible(),
+					)),
+				])
+				.split(block.inner(area));
+
 			let table = Table::new(rows, constraints)
 				.column_spacing(1)
-				.row_highlight_style(self.theme.text(true, true))
-				.block(
-					Block::default()
-						.borders(Borders::ALL)
-						.title(Span::styled(
-							strings::title_tags(),
-							self.theme.title(true),
-						))
-						.border_style(self.theme.block(true))
-						.border_type(BorderType::Thick),
-				);
+				.row_highlight_style(self.theme.text(true, true));
 
 			let mut table_state = self.table_state.take();
 
 			f.render_widget(Clear, area);
-			f.render_stateful_widget(table, area, &mut table_state);
+			f.render_widget(block, area);
+			f.render_stateful_widget(
+				table,
+				chunks[0],
+				&mut table_state,
+			);
+			self.filter.draw(f, chunks[1])?;
 
-			let area = area.inner(Margin {
-				vertical: 1,
-				horizontal: 0,
-			});
+			// on top of the right border
+			let scrollbar_area = Rect {
+				height: chunks[0].height,
+				..area.inner(Margin {
+					vertical: 1,
+					horizontal: 0,
+				})
+			};
 
 			ui::draw_scrollbar(
 				f,
-				area,
+				scrollbar_area,
 				&self.theme,
 				number_of_rows,
 				table_state.selected().unwrap_or(0),
@@ -126,7 +201,7 @@ impl DrawableComponent for TagListPopup {
 			);
 
 			self.table_state.set(table_state);
-			self.current_height.set(area.height.into());
+			self.current_height.set(chunks[0].height.into());
 		}
 
 		Ok(())
@@ -166,6 +241,24 @@ impl Component for TagListPopup {
 				self.valid_selection(),
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::verify_tags(&self.key_config),
+				self.can_verify(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::tag_sort(
+					&self.key_config,
+					self.sort.name(),
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::tag_filter(&self.key_config),
+				true,
+				true,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::push_tags(&self.key_config),
 				self.has_remotes,
@@ -185,40 +278,16 @@ impl Component for TagListPopup {
 	fn event(&mut self, event: &Event) -> Result<EventState> {
 		if self.visible {
 			if let Event::Key(key) = event {
+				if self.filter_focused {
+					return self.filter_event(event, key);
+				}
+
+				if self.move_event(key) {
+					return Ok(EventState::Consumed);
+				}
+
 				if key_match(key, self.key_config.keys.exit_popup) {
 					self.hide();
-				} else if key_match(key, self.key_config.keys.move_up)
-				{
-					self.move_selection(ScrollType::Up);
-				} else if key_match(
-					key,
-					self.key_config.keys.move_down,
-				) {
-					self.move_selection(ScrollType::Down);
-				} else if key_match(
-					key,
-					self.key_config.keys.shift_up,
-				) || key_match(
-					key,
-					self.key_config.keys.home,
-				) {
-					self.move_selection(ScrollType::Home);
-				} else if key_match(
-					key,
-					self.key_config.keys.shift_down,
-				) || key_match(
-					key,
-					self.key_config.keys.end,
-				) {
-					self.move_selection(ScrollType::End);
-				} else if key_match(
-					key,
-					self.key_config.keys.page_down,
-				) {
-					self.move_selection(ScrollType::PageDown);
-				} else if key_match(key, self.key_config.keys.page_up)
-				{
-					self.move_selection(ScrollType::PageUp);
 				} else if key_match(
 					key,
 					self.key_config.keys.move_right,
@@ -257,10 +326,29 @@ impl Component for TagListPopup {
 							Ok(EventState::Consumed)
 						},
 					);
+				} else if key_match(
+					key,
+					self.key_config.keys.verify_tags,
+				) && self.can_verify()
+				{
+					self.verify_tags();
 				} else if key_match(key, self.key_config.keys.push)
 					&& self.has_remotes
 				{
 					self.queue.push(InternalEvent::PushTags);
+				} else if key_match(
+					key,
+					self.key_config.keys.tag_sort,
+				) {
+					self.sort = self.sort.next();
+					self.apply_filter();
+				} else if key_match(
+					key,
+					self.key_config.keys.tag_filter,
+				) {
+					self.filter_focused = true;
+					self.filter.show()?;
+					self.filter.enabled(true);
 				}
 			}
 
@@ -276,6 +364,9 @@ impl Component for TagListPopup {
 
 	fn hide(&mut self) {
 		self.visible = false;
+		self.filter_focused = false;
+		self.filter.clear();
+		self.filter.hide();
 	}
 
 	fn show(&mut self) -> Result<()> {
@@ -287,10 +378,19 @@ impl Component for TagListPopup {
 
 impl TagListPopup {
 	pub fn new(env: &Environment) -> Self {
+		let mut filter =
+			TextInputComponent::new(env, "", "filter tags", false)
+				.with_input_type(InputType::Singleline);
+		filter.embed();
+
 		Self {
 			theme: env.theme.clone(),
 			queue: env.queue.clone(),
+			all_tags: Vec::new(),
 			tags: None,
+			sort: TagSort::Date,
+			filter,
+			filter_focused: false,
 			visible: false,
 			has_remotes: false,
 			table_state: std::cell::Cell::new(TableState::default()),
@@ -300,6 +400,10 @@ impl TagListPopup {
 			async_remote_tags: AsyncSingleJob::new(
 				env.sender_git.clone(),
 			),
+			signatures: None,
+			async_verify_tags: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
 		}
@@ -357,23 +461,134 @@ impl TagListPopup {
 			AsyncNotification::Git(AsyncGitNotification::PushTags)
 		) {
 			self.update_missing_remote_tags();
+		} else if matches!(
+			ev,
+			AsyncNotification::Git(AsyncGitNotification::VerifyTags)
+		) {
+			if let Some(job) = self.async_verify_tags.take_last() {
+				if let Some(signatures) = job.result() {
+					let mut failed = signatures
+						.iter()
+						.filter(|(_, state)| state.is_none())
+						.map(|(tag, _)| tag.as_str())
+						.collect::<Vec<_>>();
+
+					if !failed.is_empty() {
+						failed.sort_unstable();
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							strings::verify_tags_failed(&failed),
+						));
+					}
+
+					self.signatures = Some(signatures);
+				}
+			}
 		}
 	}
 
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.async_remote_tags.is_pending()
+			|| self.async_verify_tags.is_pending()
 	}
 
 	/// fetch list of tags
 	pub fn update_tags(&mut self) -> Result<()> {
-		let tags = get_tags_with_metadata(&self.repo.borrow())?;
+		self.all_tags = get_tags_with_metadata(&self.repo.borrow())?;
+		self.signatures = None;
 
-		self.tags = Some(tags);
+		self.apply_filter();
 
 		Ok(())
 	}
 
+	/// sorts `all_tags` and shows the ones matching the filter
+	fn apply_filter(&mut self) {
+		self.sort.sort(&mut self.all_tags);
+
+		let filter = self.filter.get_text().to_lowercase();
+		let tags: Vec<_> = self
+			.all_tags
+			.iter()
+			.filter(|tag| tag.name.to_lowercase().contains(&filter))
+			.cloned()
+			.collect();
+
+		let table_state = self.table_state.get_mut();
+		let selection = table_state.selected().unwrap_or(0);
+		table_state.select(Some(
+			selection.min(tags.len().saturating_sub(1)),
+		));
+
+		self.tags = Some(tags);
+	}
+
+	fn filter_event(
+		&mut self,
+		ev: &Event,
+		key: &KeyEvent,
+	) -> Result<EventState> {
+		if key_match(key, self.key_config.keys.exit_popup) {
+			self.filter_focused = false;
+			self.filter.clear();
+			self.filter.hide();
+			self.apply_filter();
+		} else if key_match(key, self.key_config.keys.enter) {
+			self.filter_focused = false;
+			self.filter.enabled(false);
+			if self.filter.get_text().is_empty() {
+				self.filter.hide();
+			}
+		} else if key_match(key, self.key_config.keys.move_up) {
+			self.move_selection(ScrollType::Up);
+		} else if key_match(key, self.key_config.keys.move_down) {
+			self.move_selection(ScrollType::Down);
+		} else if self.filter.event(ev)?.is_consumed() {
+			self.apply_filter();
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	/// verify the signatures of all annotated tags in the background
+	fn verify_tags(&self) {
+		let annotated = self
+			.all_tags
+			.iter()
+			.filter(|tag| tag.annotation.is_some())
+			.map(|tag| tag.name.clone())
+			.collect();
+
+		self.async_verify_tags.spawn(AsyncVerifyTagsJob::new(
+			self.repo.borrow().clone(),
+			annotated,
+		));
+	}
+
+	fn can_verify(&self) -> bool {
+		!self.async_verify_tags.is_pending()
+			&& self
+				.all_tags
+				.iter()
+				.any(|tag| tag.annotation.is_some())
+	}
+
+	/// `None` until the tag got verified
+	fn signature(
+		&self,
+		tag: &TagWithMetadata,
+	) -> Option<TagSignature<'_>> {
+		self.signatures
+			.as_ref()
+			.and_then(|signatures| signatures.get(&tag.name))
+			.map(|state| {
+				state.as_ref().map_or(
+					TagSignature::Failed,
+					TagSignature::Verified,
+				)
+			})
+	}
+
 	pub fn update_missing_remote_tags(&self) {
 		if self.has_remotes {
 			self.async_remote_tags.spawn(AsyncRemoteTagsJob::new(
@@ -383,13 +598,43 @@ impl TagListPopup {
 		}
 	}
 
+	/// returns whether the key moved the selection
+	fn move_event(&self, key: &KeyEvent) -> bool {
+		let scroll_type =
+			if key_match(key, self.key_config.keys.move_up) {
+				ScrollType::Up
+			} else if key_match(key, self.key_config.keys.move_down) {
+				ScrollType::Down
+			} else if key_match(key, self.key_config.keys.shift_up)
+				|| key_match(key, self.key_config.keys.home)
+			{
+				ScrollType::Home
+			} else if key_match(key, self.key_config.keys.shift_down)
+				|| key_match(key, self.key_config.keys.end)
+			{
+				ScrollType::End
+			} else if key_match(key, self.key_config.keys.page_down) {
+				ScrollType::PageDown
+			} else if key_match(key, self.key_config.keys.page_up) {
+				ScrollType::PageUp
+			} else {
+				return false;
+			};
+
+		self.move_selection(scroll_type);
+
+		true
+	}
+
 	///
 	fn move_selection(&self, scroll_type: ScrollType) -> bool {
 		let mut table_state = self.table_state.take();
 
 		let old_selection = table_state.selected().unwrap_or(0);
-		let max_selection =
-			self.tags.as_ref().map_or(0, |tags| tags.len() - 1);
+		let max_selection = self
+			.tags
+			.as_ref()
+			.map_or(0, |tags| tags.len().saturating_sub(1));
 
 		let new_selection = match scroll_type {
 			ScrollType::Up => old_selection.saturating_sub(1),
@@ -419,9 +664,19 @@ impl TagListPopup {
 	fn show_annotation(&self) {
 		if let Some(tag) = self.selected_tag() {
 			if let Some(annotation) = &tag.annotation {
-				self.queue.push(InternalEvent::ShowInfoMsg(
-					annotation.clone(),
-				));
+				let msg = match self.signature(tag) {
+					None => annotation.clone(),
+					Some(TagSignature::Failed) => format!(
+						"{annotation}\n\n{}",
+						strings::signature_verify_failed()
+					),
+					Some(TagSignature::Verified(state)) => format!(
+						"{annotation}\n\n{}",
+						strings::signature_state(state)
+					),
+				};
+
+				self.queue.push(InternalEvent::ShowInfoMsg(msg));
 			}
 		}
 	}
@@ -442,30 +697,46 @@ impl TagListPopup {
 	///
 	fn get_row(&self, tag: &TagWithMetadata) -> Row<'_> {
 		const UPSTREAM_SYMBOL: &str = "\u{2191}";
+		const PUSHED_SYMBOL: &str = "=";
 		const ATTACHMENT_SYMBOL: &str = "@";
 		const EMPTY_SYMBOL: &str = " ";
 
-		let is_tag_missing_on_remote = self
+		// unknown until the remote was asked
+		let has_remote_str = self
 			.missing_remote_tags
 			.as_ref()
-			.is_some_and(|missing_remote_tags| {
+			.map_or(EMPTY_SYMBOL, |missing_remote_tags| {
 				let remote_tag = format!("refs/tags/{}", tag.name);
 
-				missing_remote_tags.contains(&remote_tag)
+				if missing_remote_tags.contains(&remote_tag) {
+					UPSTREAM_SYMBOL
+				} else {
+					PUSHED_SYMBOL
+				}
 			});
 
-		let has_remote_str = if is_tag_missing_on_remote {
-			UPSTREAM_SYMBOL
-		} else {
-			EMPTY_SYMBOL
-		};
-
 		let has_attachment_str = if tag.annotation.is_some() {
 			ATTACHMENT_SYMBOL
 		} else {
 			EMPTY_SYMBOL
 		};
 
+		let (signature_str, signature_style) =
+			match self.signature(tag) {
+				None => (
+					strings::signature_symbol(None),
+					self.theme.text(true, false),
+				),
+				Some(TagSignature::Failed) => (
+					strings::symbol::EXCLAMATION_MARK,
+					self.theme.text_danger(),
+				),
+				Some(TagSignature::Verified(state)) => (
+					strings::signature_symbol(Some(state)),
+					self.theme.signature(state, false),
+				),
+			};
+
 		let cells: Vec<Cell> = vec![
 			Cell::from(has_remote_str)
 				.style(self.theme.commit_author(false)),
@@ -477,6 +748,7 @@ impl TagListPopup {
 				.style(self.theme.commit_author(false)),
 			Cell::from(has_attachment_str)
 				.style(self.theme.text_danger()),
+			Cell::from(signature_str).style(signature_style),
 			Cell::from(tag.message.clone())
 				.style(self.theme.text(true, false)),
 		];
@@ -0,0 +1,331 @@
+use std::cell::Cell;
+
+use asyncgit::sync::{
+	self, get_branches_info, get_remote_branch_upstream, BranchInfo,
+	RepoPathRef,
+};
+use ratatui::{
+	layout::{Alignment, Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use unicode_truncate::UnicodeTruncateStr;
+
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, ScrollType, VerticalScroll,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use crossterm::event::{Event, KeyEvent};
+
+/// lets the user pick the remote branch a local branch tracks
+pub struct UpstreamPopup {
+	repo: RepoPathRef,
+	branch: String,
+	remote_branches: Vec<BranchInfo>,
+	visible: bool,
+	current_height: Cell<u16>,
+	queue: Queue,
+	selection: u16,
+	scroll: VerticalScroll,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for UpstreamPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(40, 40);
+			const MIN_SIZE: Size = Size::new(30, 15);
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				rect,
+			);
+			let area = ui::rect_inside(MIN_SIZE, rect.into(), area);
+			let area = area.intersection(rect);
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.title(strings::upstream_popup_title(
+						&self.key_config,
+						&self.branch,
+					))
+					.border_type(BorderType::Thick)
+					.borders(Borders::ALL),
+				area,
+			);
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+			self.draw_list(f, area)?;
+		}
+		Ok(())
+	}
+}
+
+impl Component for UpstreamPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				self.is_visible(),
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				self.valid_selection(),
+				self.is_visible(),
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if self.move_event(e)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			} else if key_match(e, self.key_config.keys.enter)
+				&& self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					"set upstream error:",
+					self.set_upstream()
+				);
+			}
+		}
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		Ok(())
+	}
+}
+
+impl UpstreamPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			branch: String::new(),
+			remote_branches: Vec::new(),
+			visible: false,
+			scroll: VerticalScroll::new(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+			queue: env.queue.clone(),
+			current_height: Cell::new(0),
+			selection: 0,
+		}
+	}
+
+	/// `upstream` is the reference of the currently tracked branch,
+	/// it is preselected if present
+	pub fn open(
+		&mut self,
+		branch: String,
+		upstream: Option<String>,
+	) -> Result<()> {
+		self.remote_branches =
+			get_branches_info(&self.repo.borrow(), false)?
+				.into_iter()
+				.filter(|b| !b.name.ends_with("/HEAD"))
+				.collect();
+
+		self.branch = branch;
+		self.selection = upstream
+			.and_then(|upstream| {
+				self.remote_branches
+					.iter()
+					.position(|b| b.reference == upstream)
+			})
+			.unwrap_or_default()
+			.try_into()?;
+
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn set_upstream(&mut self) -> Result<()> {
+		let reference = &self.remote_branches
+			[usize::from(self.selection)]
+		.reference;
+
+		let repo = self.repo.borrow().clone();
+		let (remote, merge) =
+			get_remote_branch_upstream(&repo, reference)?;
+		sync::set_branch_upstream(
+			&repo,
+			&self.branch,
+			&remote,
+			&merge,
+		)?;
+
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn move_event(&mut self, e: &KeyEvent) -> Result<EventState> {
+		if key_match(e, self.key_config.keys.exit_popup) {
+			self.hide();
+		} else if key_match(e, self.key_config.keys.move_down) {
+			return self
+				.move_selection(ScrollType::Up)
+				.map(Into::into);
+		} else if key_match(e, self.key_config.keys.move_up) {
+			return self
+				.move_selection(ScrollType::Down)
+				.map(Into::into);
+		} else if key_match(e, self.key_config.keys.page_down) {
+			return self
+				.move_selection(ScrollType::PageDown)
+				.map(Into::into);
+		} else if key_match(e, self.key_config.keys.page_up) {
+			return self
+				.move_selection(ScrollType::PageUp)
+				.map(Into::into);
+		} else if key_match(e, self.key_config.keys.home) {
+			return self
+				.move_selection(ScrollType::Home)
+				.map(Into::into);
+		} else if key_match(e, self.key_config.keys.end) {
+			return self
+				.move_selection(ScrollType::End)
+				.map(Into::into);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn get_text(
+		&self,
+		width_available: u16,
+		height: usize,
+	) -> Text<'_> {
+		const THREE_DOTS: &str = "...";
+		const THREE_DOTS_LENGTH: usize = THREE_DOTS.len(); // "..."
+
+		let name_length: usize = width_available as usize;
+
+		Text::from(
+			self.remote_branches
+				.iter()
+				.skip(self.scroll.get_top())
+				.take(height)
+				.enumerate()
+				.map(|(i, branch)| {
+					let selected = (self.selection as usize
+						- self.scroll.get_top())
+						== i;
+					let mut name = branch.name.clone();
+					if name.len() > name_length {
+						name = name
+							.unicode_truncate(
+								name_length.saturating_sub(
+									THREE_DOTS_LENGTH,
+								),
+							)
+							.0
+							.to_string();
+						name += THREE_DOTS;
+					}
+					Line::from(Span::styled(
+						format!("{name:name_length$}"),
+						self.theme.text(true, selected),
+					))
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+
+	fn draw_list(&self, f: &mut Frame, r: Rect) -> Result<()> {
+		let height_in_lines = r.height as usize;
+		self.current_height.set(height_in_lines.try_into()?);
+
+		self.scroll.update(
+			self.selection as usize,
+			self.remote_branches.len(),
+			height_in_lines,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.get_text(r.width, height_in_lines))
+				.alignment(Alignment::Left),
+			r,
+		);
+
+		let mut r = r;
+		r.width += 1;
+		r.height += 2;
+		r.y = r.y.saturating_sub(1);
+
+		self.scroll.draw(f, r, &self.theme);
+
+		Ok(())
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
+		let new_selection = match scroll {
+			ScrollType::Up => self.selection.saturating_add(1),
+			ScrollType::Down => self.selection.saturating_sub(1),
+			ScrollType::PageDown => self
+				.selection
+				.saturating_add(self.current_height.get()),
+			ScrollType::PageUp => self
+				.selection
+				.saturating_sub(self.current_height.get()),
+			ScrollType::Home => 0,
+			ScrollType::End => {
+				let num_branches: u16 =
+					self.remote_branches.len().try_into()?;
+				num_branches.saturating_sub(1)
+			}
+		};
+
+		let num_branches: u16 =
+			self.remote_branches.len().try_into()?;
+		self.selection =
+			new_selection.min(num_branches.saturating_sub(1));
+
+		Ok(true)
+	}
+
+	fn valid_selection(&self) -> bool {
+		usize::from(self.selection) < self.remote_branches.len()
+	}
+}
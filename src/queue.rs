@@ -2,15 +2,16 @@ use crate::{
 	components::FuzzyFinderTarget,
 	popups::{
 		AppOption, BlameFileOpen, FileRevOpen, FileTreeOpen,
-		InspectCommitOpen,
+		InspectCommitOpen, PushTarget,
 	},
 	tabs::StashingOptions,
 };
 use asyncgit::{
 	sync::{
-		diff::DiffLinePosition, CommitId, LogFilterSearchOptions,
+		diff::DiffLinePosition, BranchType, CommitId,
+		LogFilterSearchOptions,
 	},
-	PushType,
+	PushForce, PushType,
 };
 use bitflags::bitflags;
 use std::{
@@ -46,12 +47,12 @@ pub enum Action {
 	ResetLines(String, Vec<DiffLinePosition>),
 	StashDrop(Vec<CommitId>),
 	StashPop(CommitId),
-	DeleteLocalBranch(String),
-	DeleteRemoteBranch(String),
+	DeleteLocalBranch(Vec<String>),
+	DeleteRemoteBranch(Vec<String>),
 	DeleteTag(String),
 	DeleteRemoteTag(String, String),
 	DeleteRemote(String),
-	ForcePush(String, bool),
+	ForcePush(String, PushForce),
 	PullMerge { incoming: usize, rebase: bool },
 	AbortMerge,
 	AbortRebase,
@@ -102,6 +103,8 @@ pub enum InternalEvent {
 	///
 	PopupStashing(StashingOptions),
 	///
+	RenameStash(CommitId, String),
+	///
 	TabSwitchStatus,
 	///
 	TabSwitch(AppTabs),
@@ -114,6 +117,8 @@ pub enum InternalEvent {
 	///
 	CreateBranch,
 	///
+	StashBranch(CommitId),
+	///
 	RenameRemote(String),
 	///
 	UpdateRemoteUrl(String, String),
@@ -124,7 +129,17 @@ pub enum InternalEvent {
 	///
 	OpenExternalEditor(Option<String>),
 	///
-	Push(String, PushType, bool, bool),
+	Push(String, PushType, PushForce, bool),
+	///
+	OpenPushTo(String),
+	///
+	PushTo(String, PushTarget),
+	/// references of the two branches to compare
+	OpenCompareBranches(String, String),
+	/// branch to merge into HEAD
+	OpenMerge(String, BranchType),
+	/// branch, reference of its current upstream
+	OpenUpstream(String, Option<String>),
 	///
 	Pull(String),
 	///
@@ -157,6 +172,8 @@ pub enum InternalEvent {
 	OpenResetPopup(CommitId),
 	///
 	RewordCommit(CommitId),
+	/// edit the git note of a commit
+	EditNote(CommitId),
 	#[cfg_attr(feature = "disable-log-files-tabs", allow(dead_code))]
 	CommitSearch(LogFilterSearchOptions),
 }
@@ -2,7 +2,7 @@
 
 use std::borrow::Cow;
 
-use asyncgit::sync::CommitId;
+use asyncgit::sync::{CommitId, SignatureState};
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
 
@@ -17,8 +17,11 @@ pub mod order {
 
 pub static PUSH_POPUP_MSG: &str = "Push";
 pub static FORCE_PUSH_POPUP_MSG: &str = "Force Push";
+pub static FORCE_PUSH_WITH_LEASE_POPUP_MSG: &str =
+	"Force Push (lease)";
 pub static PULL_POPUP_MSG: &str = "Pull";
 pub static FETCH_POPUP_MSG: &str = "Fetch";
+pub static CLONE_POPUP_MSG: &str = "Clone";
 pub static PUSH_POPUP_PROGRESS_NONE: &str = "preparing...";
 pub static PUSH_POPUP_STATES_ADDING: &str = "adding objects (1/3)";
 pub static PUSH_POPUP_STATES_DELTAS: &str = "deltas (2/3)";
@@ -43,6 +46,9 @@ pub static POPUP_COMMIT_SHA_INVALID: &str = "Invalid commit sha";
 
 pub mod symbol {
 	pub const CHECKMARK: &str = "\u{2713}"; //✓
+	pub const CROSSMARK: &str = "\u{2717}"; //✗
+	pub const QUESTION_MARK: &str = "?";
+	pub const EXCLAMATION_MARK: &str = "!";
 	pub const SPACE: &str = "\u{02FD}"; //˽
 	pub const EMPTY_SPACE: &str = " ";
 	pub const ELLIPSIS: char = '\u{2026}'; // …
@@ -51,9 +57,67 @@ pub mod symbol {
 pub fn title_branches() -> String {
 	"Branches".to_string()
 }
+pub fn title_branches_merged(base: &str) -> String {
+	format!("Branches merged into '{base}'")
+}
+pub fn compare_branches_title(a: &str, b: &str) -> String {
+	format!("Compare '{a}'...'{b}'")
+}
+pub fn shallow_boundary() -> String {
+	"[shallow boundary]".to_string()
+}
+pub fn compare_branches_merge_base() -> String {
+	"merge base:".to_string()
+}
+pub fn compare_branches_no_merge_base() -> String {
+	"no merge base (unrelated histories)".to_string()
+}
+pub fn compare_branches_only_in(
+	branch: &str,
+	other: &str,
+	count: usize,
+) -> String {
+	format!("{count} commits in '{branch}' but not in '{other}':")
+}
 pub fn title_tags() -> String {
 	"Tags".to_string()
 }
+pub const fn signature_symbol(
+	state: Option<&SignatureState>,
+) -> &'static str {
+	match state {
+		Some(SignatureState::Good(_)) => symbol::CHECKMARK,
+		Some(SignatureState::Bad(_)) => symbol::CROSSMARK,
+		Some(SignatureState::UnknownKey(_)) => symbol::QUESTION_MARK,
+		Some(SignatureState::Unsigned) | None => symbol::EMPTY_SPACE,
+	}
+}
+pub fn signature_state(state: &SignatureState) -> String {
+	match state {
+		SignatureState::Unsigned => "not signed".to_string(),
+		SignatureState::Good(signer) => {
+			format!("good signature by {signer}")
+		}
+		SignatureState::Bad(signer) if signer.is_empty() => {
+			"bad signature".to_string()
+		}
+		SignatureState::Bad(signer) => {
+			format!("bad signature by {signer}")
+		}
+		SignatureState::UnknownKey(key) => {
+			format!("signed with unknown key {key}")
+		}
+	}
+}
+pub fn signature_verify_failed() -> String {
+	"signature verification failed".to_string()
+}
+pub fn verify_tags_failed(tags: &[&str]) -> String {
+	format!(
+		"verify tags error:\ncould not verify {}\n(see log for details)",
+		tags.join(", ")
+	)
+}
 pub fn title_status(_key_config: &SharedKeyConfig) -> String {
 	" Unstaged Changes ".to_string()
 }
@@ -146,6 +210,9 @@ pub fn commit_msg(_key_config: &SharedKeyConfig) -> String {
 pub fn commit_first_line_warning(count: usize) -> String {
 	format!("[subject length: {count}]")
 }
+pub fn commit_hook_running_title(hook: &str) -> String {
+	format!("Running {hook} hook..")
+}
 pub const fn branch_name_invalid() -> &'static str {
 	"[invalid name]"
 }
@@ -155,9 +222,26 @@ pub fn commit_editor_msg(_key_config: &SharedKeyConfig) -> String {
 # Lines starting with '#' will be ignored"
 		.to_string()
 }
+pub fn note_popup_title() -> String {
+	"Note".to_string()
+}
+pub fn note_popup_msg() -> String {
+	"type note (empty to remove)".to_string()
+}
+pub fn note_editor_msg() -> String {
+	r"
+# Edit the note of the commit, an empty note removes it
+# Lines starting with '#' will be ignored"
+		.to_string()
+}
 pub fn stash_popup_title(_key_config: &SharedKeyConfig) -> String {
 	"Stash".to_string()
 }
+pub fn stash_rename_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Rename Stash".to_string()
+}
 pub fn stash_popup_msg(_key_config: &SharedKeyConfig) -> String {
 	"type name (optional)".to_string()
 }
@@ -257,19 +341,33 @@ pub fn confirm_msg_resethunk(
 }
 pub fn confirm_title_delete_branch(
 	_key_config: &SharedKeyConfig,
+	multiple: bool,
 ) -> String {
-	"Delete Branch".to_string()
+	format!("Delete Branch{}", if multiple { "es" } else { "" })
 }
 pub fn confirm_msg_delete_branch(
 	_key_config: &SharedKeyConfig,
-	branch_ref: &str,
+	branch_refs: &[String],
 ) -> String {
-	format!("Confirm deleting branch: '{branch_ref}' ?")
+	match branch_refs {
+		[branch_ref] => {
+			format!("Confirm deleting branch: '{branch_ref}' ?")
+		}
+		_ => format!(
+			"Confirm deleting {} branches:\n\n{} ?",
+			branch_refs.len(),
+			branch_refs.join(", ")
+		),
+	}
 }
 pub fn confirm_title_delete_remote_branch(
 	_key_config: &SharedKeyConfig,
+	multiple: bool,
 ) -> String {
-	"Delete Remote Branch".to_string()
+	format!(
+		"Delete Remote Branch{}",
+		if multiple { "es" } else { "" }
+	)
 }
 pub fn confirm_title_delete_remote(
 	_key_config: &SharedKeyConfig,
@@ -284,9 +382,20 @@ pub fn confirm_msg_delete_remote(
 }
 pub fn confirm_msg_delete_remote_branch(
 	_key_config: &SharedKeyConfig,
-	branch_ref: &str,
+	branch_refs: &[String],
 ) -> String {
-	format!("Confirm deleting remote branch: '{branch_ref}' ?")
+	match branch_refs {
+		[branch_ref] => {
+			format!(
+				"Confirm deleting remote branch: '{branch_ref}' ?"
+			)
+		}
+		_ => format!(
+			"Confirm deleting {} remote branches:\n\n{} ?",
+			branch_refs.len(),
+			branch_refs.join(", ")
+		),
+	}
 }
 pub fn confirm_title_delete_tag(
 	_key_config: &SharedKeyConfig,
@@ -307,16 +416,28 @@ pub fn confirm_msg_delete_tag_remote(remote_name: &str) -> String {
 }
 pub fn confirm_title_force_push(
 	_key_config: &SharedKeyConfig,
+	lease: bool,
 ) -> String {
-	"Force Push".to_string()
+	if lease {
+		"Force Push (with lease)".to_string()
+	} else {
+		"Force Push".to_string()
+	}
 }
 pub fn confirm_msg_force_push(
 	_key_config: &SharedKeyConfig,
 	branch_ref: &str,
+	lease: bool,
 ) -> String {
-	format!(
-        "Confirm force push to branch '{branch_ref}' ?  This may rewrite history."
-    )
+	if lease {
+		format!(
+            "Confirm force push to branch '{branch_ref}' ?  This may rewrite history, but fails if the remote branch changed since the last fetch."
+        )
+	} else {
+		format!(
+            "Confirm force push to branch '{branch_ref}' without lease ?  This may rewrite history and discard commits you have not fetched yet."
+        )
+	}
 }
 pub fn log_title(_key_config: &SharedKeyConfig) -> String {
 	"Commit".to_string()
@@ -365,6 +486,36 @@ pub fn create_branch_popup_title(
 ) -> String {
 	"Branch".to_string()
 }
+pub fn push_to_popup_title(
+	_key_config: &SharedKeyConfig,
+	branch: &str,
+) -> String {
+	format!("Push '{branch}' To")
+}
+pub fn merge_popup_title(
+	_key_config: &SharedKeyConfig,
+	branch: &str,
+) -> String {
+	format!("Merge '{branch}'")
+}
+pub fn merge_conflicts_preview(count: usize) -> String {
+	if count == 0 {
+		"no conflicts".to_string()
+	} else {
+		format!("{count} conflicting file(s):")
+	}
+}
+pub fn upstream_popup_title(
+	_key_config: &SharedKeyConfig,
+	branch: &str,
+) -> String {
+	format!("Upstream of '{branch}'")
+}
+pub fn stash_branch_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Branch from Stash".to_string()
+}
 pub fn create_branch_popup_msg(
 	_key_config: &SharedKeyConfig,
 ) -> String {
@@ -477,6 +628,18 @@ pub mod commit {
 	pub fn details_message() -> String {
 		"Subject: ".to_string()
 	}
+	pub fn details_notes() -> String {
+		"Notes:".to_string()
+	}
+	pub fn details_signature() -> String {
+		"Signature: ".to_string()
+	}
+	pub fn details_signature_verifying() -> String {
+		"verifying..".to_string()
+	}
+	pub fn details_signature_failed() -> String {
+		"could not be verified".to_string()
+	}
 	pub fn details_info_title(
 		_key_config: &SharedKeyConfig,
 	) -> String {
@@ -587,6 +750,16 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn clone_repo(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clone [{}]",
+				key_config.get_hint(key_config.keys.clone_repo),
+			),
+			"clone a repository and open it",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1199,6 +1372,17 @@ pub mod commands {
 		)
 	}
 
+	pub fn cancel_hook(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cancel hook [{}]",
+				key_config.get_hint(key_config.keys.cancel_hook),
+			),
+			"kill the running hook and abort the commit",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+
 	pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1381,6 +1565,34 @@ pub mod commands {
 			CMD_GROUP_STASHING,
 		)
 	}
+	pub fn stashing_toggle_staged_only(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Toggle Staged Only [{}]",
+				key_config.get_hint(
+					key_config.keys.stashing_toggle_staged_only
+				),
+			),
+			"toggle stashing only staged changes",
+			CMD_GROUP_STASHING,
+		)
+	}
+	pub fn stashing_toggle_selected_only(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Toggle Selected Only [{}]",
+				key_config.get_hint(
+					key_config.keys.stashing_toggle_selected_only
+				),
+			),
+			"toggle stashing only the selected file or folder",
+			CMD_GROUP_STASHING,
+		)
+	}
 	pub fn stashing_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1405,6 +1617,18 @@ pub mod commands {
 			CMD_GROUP_STASHES,
 		)
 	}
+	pub fn stashlist_rename(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Rename [{}]",
+				key_config.get_hint(key_config.keys.stash_rename),
+			),
+			"change the message of the selected stash",
+			CMD_GROUP_STASHES,
+		)
+	}
 	pub fn stashlist_drop(
 		key_config: &SharedKeyConfig,
 		marked: usize,
@@ -1447,6 +1671,31 @@ pub mod commands {
 			CMD_GROUP_STASHES,
 		)
 	}
+	pub fn stashlist_branch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Branch [{}]",
+				key_config.get_hint(key_config.keys.stash_branch),
+			),
+			"create a branch at the stash base, apply and drop the stash",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn stash_next_part(
+		key_config: &SharedKeyConfig,
+		part: &str,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Show {part} [{}]",
+				key_config.get_hint(key_config.keys.stash_next_part),
+			),
+			"show the next part of the stash (tracked, index, untracked)",
+			CMD_GROUP_STASHES,
+		)
+	}
 	pub fn log_details_toggle(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1570,6 +1819,30 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn log_edit_note(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Note [{}]",
+				key_config.get_hint(key_config.keys.log_edit_note),
+			),
+			"add, edit or remove the git note of a commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn note_confirm_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Save [{}]",
+				key_config.get_hint(key_config.keys.commit),
+			),
+			"save note, an empty note gets removed",
+			CMD_GROUP_LOG,
+		)
+	}
 	#[cfg(not(feature = "disable-log-files-tabs"))]
 	pub fn log_find_commit(
 		key_config: &SharedKeyConfig,
@@ -1711,6 +1984,32 @@ pub mod commands {
 			CMD_GROUP_BRANCHES,
 		)
 	}
+	pub fn branch_set_upstream(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Set Upstream [{}]",
+				key_config
+					.get_hint(key_config.keys.branch_set_upstream),
+			),
+			"pick the remote branch to track",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_unset_upstream(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unset Upstream [{}]",
+				key_config
+					.get_hint(key_config.keys.branch_unset_upstream),
+			),
+			"stop tracking a remote branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn delete_branch_popup(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1749,6 +2048,18 @@ pub mod commands {
 		)
 	}
 
+	pub fn compare_branches(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Compare Marked [{}]",
+				key_config.get_hint(key_config.keys.compare_commits),
+			),
+			"compare the two marked branches",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn compare_with_head(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1788,6 +2099,63 @@ pub mod commands {
 			CMD_GROUP_BRANCHES,
 		)
 	}
+	pub fn branch_list_mark(
+		key_config: &SharedKeyConfig,
+		marked: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if marked { "Unmark" } else { "Mark" },
+				key_config.get_hint(key_config.keys.log_mark_commit),
+			),
+			"mark multiple branches to delete",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_sort(
+		key_config: &SharedKeyConfig,
+		sort: &str,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Sort: {sort} [{}]",
+				key_config.get_hint(key_config.keys.branch_sort),
+			),
+			"sort branches by name, last commit date or author",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_filter(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Filter [{}]",
+				key_config.get_hint(key_config.keys.branch_filter),
+			),
+			"only show branches matching a text",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_merged(
+		key_config: &SharedKeyConfig,
+		active: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if active {
+					"All Branches"
+				} else {
+					"Merged Into"
+				},
+				key_config.get_hint(key_config.keys.branch_merged),
+			),
+			"only show branches merged into the selected one",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn toggle_branch_popup(
 		key_config: &SharedKeyConfig,
 		local: bool,
@@ -1840,6 +2208,39 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn verify_tags(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Verify [{}]",
+				key_config.get_hint(key_config.keys.verify_tags),
+			),
+			"verify the signatures of annotated tags",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn tag_sort(
+		key_config: &SharedKeyConfig,
+		sort: &str,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Sort: {sort} [{}]",
+				key_config.get_hint(key_config.keys.tag_sort),
+			),
+			"sort tags by date, version or name",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn tag_filter(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Filter [{}]",
+				key_config.get_hint(key_config.keys.tag_filter),
+			),
+			"only show tags matching a text",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn select_tag(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1861,6 +2262,18 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn status_push_to(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push To [{}]",
+				key_config.get_hint(key_config.keys.push_to),
+			),
+			"push to a chosen remote and branch name",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn status_force_push(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1874,6 +2287,19 @@ pub mod commands {
 		)
 	}
 
+	pub fn confirm_force_push_without_lease(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Without Lease [{}]",
+				key_config.get_hint(key_config.keys.force_push),
+			),
+			"force push even if the remote branch changed since the last fetch",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn status_fetch(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -24,6 +24,6 @@ mod status;
 pub use files::FilesTab;
 #[cfg(not(feature = "disable-log-files-tabs"))]
 pub use revlog::Revlog;
-pub use stashing::{Stashing, StashingOptions};
+pub use stashing::{StashScope, Stashing, StashingOptions};
 pub use stashlist::StashList;
 pub use status::Status;
@@ -19,6 +19,7 @@ use asyncgit::{
 		self, filter_commit_by_search, CommitId, LogFilterSearch,
 		LogFilterSearchOptions, RepoPathRef,
 	},
+	verify_commits::AsyncVerifyCommitsJob,
 	AsyncBranchesJob, AsyncCommitFilterJob, AsyncGitNotification,
 	AsyncLog, AsyncTags, CommitFilesParams, FetchStatus,
 	ProgressPercent,
@@ -69,6 +70,7 @@ pub struct Revlog {
 	git_tags: AsyncTags,
 	git_local_branches: AsyncSingleJob<AsyncBranchesJob>,
 	git_remote_branches: AsyncSingleJob<AsyncBranchesJob>,
+	git_signatures: AsyncSingleJob<AsyncVerifyCommitsJob>,
 	queue: Queue,
 	visible: bool,
 	key_config: SharedKeyConfig,
@@ -86,7 +88,8 @@ impl Revlog {
 			list: CommitList::new(
 				env,
 				&strings::log_title(&env.key_config),
-			),
+			)
+			.with_signatures(),
 			git_log: AsyncLog::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -103,6 +106,9 @@ impl Revlog {
 			git_remote_branches: AsyncSingleJob::new(
 				env.sender_git.clone(),
 			),
+			git_signatures: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
 			visible: false,
 			key_config: env.key_config.clone(),
 			sender: env.sender_git.clone(),
@@ -117,6 +123,7 @@ impl Revlog {
 			|| self.git_tags.is_pending()
 			|| self.git_local_branches.is_pending()
 			|| self.git_remote_branches.is_pending()
+			|| self.git_signatures.is_pending()
 			|| self.commit_details.any_work_pending()
 	}
 
@@ -127,6 +134,13 @@ impl Revlog {
 	///
 	pub fn update(&mut self) -> Result<()> {
 		if self.is_visible() {
+			let shallow_boundary =
+				sync::shallow_boundary(&self.repo.borrow())?;
+			if self.list.set_shallow_boundary(shallow_boundary) {
+				// deepening keeps HEAD but extends the history
+				self.git_log.reset()?;
+			}
+
 			if self.git_log.fetch()? == FetchStatus::Started {
 				self.list.clear();
 			}
@@ -136,6 +150,8 @@ impl Revlog {
 
 			self.git_tags.request(Duration::from_secs(3), false)?;
 
+			self.verify_visible_commits();
+
 			if self.commit_details.is_visible() {
 				let commit = self.selected_commit();
 				let tags = self.selected_commit_tags(commit.as_ref());
@@ -162,6 +178,21 @@ impl Revlog {
 				AsyncGitNotification::CommitFilter => {
 					self.update_search_state();
 				}
+				AsyncGitNotification::VerifyCommits => {
+					if let Some(signatures) = self
+						.git_signatures
+						.take_last()
+						.and_then(|job| job.result())
+					{
+						self.list.set_signatures(signatures);
+						self.verify_visible_commits();
+					}
+
+					self.commit_details.update_git(ev);
+				}
+				AsyncGitNotification::Describe => {
+					self.commit_details.update_git(ev);
+				}
 				AsyncGitNotification::Tags => {
 					if let Some(tags) = self.git_tags.last()? {
 						self.list.set_tags(tags);
@@ -200,6 +231,21 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// one job at a time, the next one picks up what is still missing
+	fn verify_visible_commits(&self) {
+		if self.git_signatures.is_pending() {
+			return;
+		}
+
+		let commits = self.list.unverified_visible_commits();
+		if !commits.is_empty() {
+			self.git_signatures.spawn(AsyncVerifyCommitsJob::new(
+				self.repo.borrow().clone(),
+				commits,
+			));
+		}
+	}
+
 	fn selected_commit(&self) -> Option<CommitId> {
 		self.list.selected_entry().map(|e| e.id)
 	}
@@ -569,6 +615,18 @@ impl Component for Revlog {
 							Ok(EventState::Consumed)
 						},
 					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_edit_note,
+				) {
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue
+								.push(InternalEvent::EditNote(id));
+							Ok(EventState::Consumed)
+						},
+					);
 				} else if key_match(k, self.key_config.keys.log_find)
 					&& self.can_start_search()
 				{
@@ -600,6 +658,7 @@ impl Component for Revlog {
 									commit_id: marked[0],
 									compare_id: Some(marked[1]),
 									tags: None,
+									stash: None,
 								},
 							),
 						));
@@ -612,6 +671,7 @@ impl Component for Revlog {
 		Ok(EventState::NotConsumed)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -724,6 +784,11 @@ impl Component for Revlog {
 			self.selected_commit().is_some(),
 			(self.visible && !self.is_search_pending()) || force_all,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::log_edit_note(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
 		out.push(CommandInfo::new(
 			strings::commands::log_find_commit(&self.key_config),
 			self.can_start_search(),
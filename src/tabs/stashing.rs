@@ -24,10 +24,34 @@ use ratatui::{
 };
 use std::borrow::Cow;
 
-#[derive(Default, Clone, Copy, Debug)]
+/// which changes get stashed
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StashScope {
+	#[default]
+	All,
+	/// the staged changes only (ignores the other options)
+	Staged,
+	/// the selected file or folder only
+	Selected,
+}
+
+impl StashScope {
+	fn toggle(self, scope: Self) -> Self {
+		if self == scope {
+			Self::All
+		} else {
+			scope
+		}
+	}
+}
+
+#[derive(Default, Clone, Debug)]
 pub struct StashingOptions {
 	pub stash_untracked: bool,
 	pub keep_index: bool,
+	pub scope: StashScope,
+	/// paths to limit a [`StashScope::Selected`] stash to
+	pub paths: Vec<String>,
 }
 
 pub struct Stashing {
@@ -55,8 +79,8 @@ impl Stashing {
 			),
 			visible: false,
 			options: StashingOptions {
-				keep_index: false,
 				stash_untracked: true,
+				..StashingOptions::default()
 			},
 			theme: env.theme.clone(),
 			git_status: AsyncStatus::new(
@@ -71,9 +95,15 @@ impl Stashing {
 	///
 	pub fn update(&self) -> Result<()> {
 		if self.is_visible() {
+			let status_type =
+				if self.options.scope == StashScope::Staged {
+					StatusType::Stage
+				} else {
+					StatusType::Both
+				};
+
 			self.git_status
-				//TODO: support options
-				.fetch(&StatusParams::new(StatusType::Both, None))?;
+				.fetch(&StatusParams::new(status_type, None))?;
 		}
 
 		Ok(())
@@ -119,17 +149,58 @@ impl Stashing {
 				Span::raw(Cow::from(" stash untracked")),
 			]),
 			Line::from(vec![
-				bracket_open,
+				bracket_open.clone(),
 				if self.options.keep_index {
+					option_on.clone()
+				} else {
+					option_off.clone()
+				},
+				bracket_close.clone(),
+				Span::raw(Cow::from(" keep index")),
+			]),
+			Line::from(vec![
+				bracket_open.clone(),
+				if self.options.scope == StashScope::Staged {
+					option_on.clone()
+				} else {
+					option_off.clone()
+				},
+				bracket_close.clone(),
+				Span::raw(Cow::from(" staged only")),
+			]),
+			Line::from(vec![
+				bracket_open,
+				if self.options.scope == StashScope::Selected {
 					option_on
 				} else {
 					option_off
 				},
 				bracket_close,
-				Span::raw(Cow::from(" keep index")),
+				Span::raw(Cow::from(" selected only")),
 			]),
 		]
 	}
+
+	/// options for the stash popup with the paths to stash filled in
+	fn stash_options(&self) -> StashingOptions {
+		let mut options = self.options.clone();
+
+		if options.scope == StashScope::Selected {
+			options.paths = self
+				.index
+				.selection()
+				.map(|item| vec![item.info.full_path])
+				.unwrap_or_default();
+		}
+
+		options
+	}
+
+	fn can_stash(&self) -> bool {
+		!self.index.is_empty()
+			&& (self.options.scope != StashScope::Selected
+				|| self.index.selection().is_some())
+	}
 }
 
 impl DrawableComponent for Stashing {
@@ -148,7 +219,7 @@ impl DrawableComponent for Stashing {
 		let right_chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
-				[Constraint::Length(4), Constraint::Min(1)].as_ref(),
+				[Constraint::Length(6), Constraint::Min(1)].as_ref(),
 			)
 			.split(chunks[1]);
 
@@ -182,7 +253,7 @@ impl Component for Stashing {
 
 			out.push(CommandInfo::new(
 				strings::commands::stashing_save(&self.key_config),
-				self.visible && !self.index.is_empty(),
+				self.visible && self.can_stash(),
 				self.visible || force_all,
 			));
 			out.push(CommandInfo::new(
@@ -199,6 +270,20 @@ impl Component for Stashing {
 				self.visible,
 				self.visible || force_all,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stashing_toggle_staged_only(
+					&self.key_config,
+				),
+				self.visible,
+				self.visible || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stashing_toggle_selected_only(
+					&self.key_config,
+				),
+				self.visible,
+				self.visible || force_all,
+			));
 		}
 
 		visibility_blocking(self)
@@ -219,10 +304,10 @@ impl Component for Stashing {
 				return if key_match(
 					k,
 					self.key_config.keys.stashing_save,
-				) && !self.index.is_empty()
+				) && self.can_stash()
 				{
 					self.queue.push(InternalEvent::PopupStashing(
-						self.options,
+						self.stash_options(),
 					));
 
 					Ok(EventState::Consumed)
@@ -242,6 +327,25 @@ impl Component for Stashing {
 						!self.options.stash_untracked;
 					self.update()?;
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.stashing_toggle_staged_only,
+				) {
+					self.options.scope =
+						self.options.scope.toggle(StashScope::Staged);
+					self.update()?;
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config
+						.keys
+						.stashing_toggle_selected_only,
+				) {
+					self.options.scope = self
+						.options
+						.scope
+						.toggle(StashScope::Selected);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
@@ -84,14 +84,38 @@ impl StashList {
 
 	fn inspect(&self) {
 		if let Some(e) = self.list.selected_entry() {
-			self.queue.push(InternalEvent::OpenPopup(
-				StackablePopupOpen::InspectCommit(
-					InspectCommitOpen::new(e.id),
-				),
+			match sync::get_stash_parts(&self.repo.borrow(), e.id) {
+				Ok(parts) => {
+					self.queue.push(InternalEvent::OpenPopup(
+						StackablePopupOpen::InspectCommit(
+							InspectCommitOpen::new_stash(parts),
+						),
+					));
+				}
+				Err(e) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("stash inspect error:\n{e}"),
+					));
+				}
+			}
+		}
+	}
+
+	fn rename_stash(&self) {
+		if let Some(e) = self.list.selected_entry() {
+			self.queue.push(InternalEvent::RenameStash(
+				e.id,
+				e.msg.to_string(),
 			));
 		}
 	}
 
+	fn branch_stash(&self) {
+		if let Some(e) = self.list.selected_entry() {
+			self.queue.push(InternalEvent::StashBranch(e.id));
+		}
+	}
+
 	/// Called when a pending stash action has been confirmed
 	pub fn action_confirmed(
 		&mut self,
@@ -182,6 +206,16 @@ impl Component for StashList {
 				selection_valid,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stashlist_rename(&self.key_config),
+				selection_valid,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stashlist_branch(&self.key_config),
+				selection_valid,
+				true,
+			));
 		}
 
 		visibility_blocking(self)
@@ -214,6 +248,16 @@ impl Component for StashList {
 					self.key_config.keys.stash_open,
 				) {
 					self.inspect();
+				} else if key_match(
+					k,
+					self.key_config.keys.stash_branch,
+				) {
+					self.branch_stash();
+				} else if key_match(
+					k,
+					self.key_config.keys.stash_rename,
+				) {
+					self.rename_stash();
 				}
 			}
 		}
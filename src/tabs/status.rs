@@ -10,18 +10,19 @@ use crate::{
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
 	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
-	strings, try_or_popup,
+	report_hook, strings, try_or_popup,
 	ui::style::Theme,
 };
 use anyhow::Result;
 use asyncgit::{
 	cached,
 	sync::{
-		self, status::StatusType, RepoPath, RepoPathRef, RepoState,
+		self, status::StatusType, RebaseState, RepoPath, RepoPathRef,
+		RepoState,
 	},
 	sync::{BranchCompare, CommitId},
 	AsyncDiff, AsyncGitNotification, AsyncStatus, DiffParams,
-	DiffType, PushType, StatusItem, StatusParams,
+	DiffType, PushForce, PushType, StatusItem, StatusParams,
 };
 use crossterm::event::Event;
 use itertools::Itertools;
@@ -382,17 +383,41 @@ impl Status {
 
 	///
 	pub fn update(&mut self) -> Result<()> {
+		self.update_with(None)
+	}
+
+	/// like `update` but only `paths` (relative to the workdir) changed
+	pub fn update_changed_paths(
+		&mut self,
+		paths: &[String],
+	) -> Result<()> {
+		self.update_with(Some(paths))
+	}
+
+	fn update_with(
+		&mut self,
+		paths: Option<&[String]>,
+	) -> Result<()> {
 		self.git_branch_name.lookup().map(Some).unwrap_or(None);
 
 		if self.is_visible() {
 			let config =
 				self.options.borrow().status_show_untracked();
 
-			self.git_diff.refresh()?;
-			self.git_status_workdir.fetch(&StatusParams::new(
-				StatusType::WorkingDir,
-				config,
-			))?;
+			if paths
+				.is_none_or(|paths| self.diff.is_affected_by(paths))
+			{
+				self.git_diff.refresh()?;
+			}
+
+			let workdir_params =
+				StatusParams::new(StatusType::WorkingDir, config);
+			self.git_status_workdir.fetch(&match paths {
+				Some(paths) => {
+					workdir_params.with_changed_paths(paths.to_vec())
+				}
+				None => workdir_params,
+			})?;
 			self.git_status_stage.fetch(&StatusParams::new(
 				StatusType::Stage,
 				config,
@@ -567,13 +592,16 @@ impl Status {
 			if let Some(branch) = self.git_branch_name.last() {
 				if force {
 					self.queue.push(InternalEvent::ConfirmAction(
-						Action::ForcePush(branch, force),
+						Action::ForcePush(
+							branch,
+							PushForce::WithLease,
+						),
 					));
 				} else {
 					self.queue.push(InternalEvent::Push(
 						branch,
 						PushType::Branch,
-						force,
+						PushForce::None,
 						false,
 					));
 				}
@@ -609,6 +637,12 @@ impl Status {
 			});
 	}
 
+	fn push_to(&self) {
+		if let Some(branch) = self.git_branch_name.last() {
+			self.queue.push(InternalEvent::OpenPushTo(branch));
+		}
+	}
+
 	fn can_push(&self) -> bool {
 		let is_ahead = self
 			.git_branch_state
@@ -652,11 +686,18 @@ impl Status {
 	}
 
 	fn continue_rebase(&self) {
-		try_or_popup!(
-			self,
-			"continue rebase",
-			sync::continue_pending_rebase(&self.repo.borrow())
-		);
+		match sync::continue_pending_rebase(&self.repo.borrow()) {
+			Ok(RebaseState::Finished(hook)) => {
+				report_hook!(self, "post-rewrite", anyhow::Ok(hook));
+			}
+			Ok(RebaseState::Conflicted) => (),
+			Err(e) => {
+				log::error!("continue rebase {e}");
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("continue rebase\n{e}"),
+				));
+			}
+		}
 	}
 
 	fn commands_nav(
@@ -756,6 +797,11 @@ impl Component for Status {
 				true,
 				self.can_push() && !focus_on_diff,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::status_push_to(&self.key_config),
+				self.remotes.has_remote_for_push,
+				!focus_on_diff,
+			));
 
 			out.push(CommandInfo::new(
 				strings::commands::status_fetch(&self.key_config),
@@ -884,6 +930,12 @@ impl Component for Status {
 				{
 					self.push(false);
 					Ok(EventState::Consumed)
+				} else if key_match(k, self.key_config.keys.push_to)
+					&& !self.is_focus_on_diff()
+					&& self.remotes.has_remote_for_push
+				{
+					self.push_to();
+					Ok(EventState::Consumed)
 				} else if key_match(k, self.key_config.keys.fetch)
 					&& !self.is_focus_on_diff()
 					&& self.can_fetch()
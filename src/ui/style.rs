@@ -1,6 +1,6 @@
 use crate::ui::syntax_text::DEFAULT_SYNTAX_THEME;
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{sync::SignatureState, DiffLineType, StatusItemType};
 use ratatui::style::{Color, Modifier, Style};
 use ron::ser::{to_string_pretty, PrettyConfig};
 use serde::{Deserialize, Serialize};
@@ -393,6 +393,29 @@ impl Theme {
 		)
 	}
 
+	pub fn signature(
+		&self,
+		state: &SignatureState,
+		selected: bool,
+	) -> Style {
+		let style = match state {
+			SignatureState::Good(_) => {
+				Style::default().fg(self.diff_line_add)
+			}
+			SignatureState::Bad(_) => {
+				Style::default().fg(self.danger_fg)
+			}
+			SignatureState::UnknownKey(_) => {
+				Style::default().fg(self.commit_author)
+			}
+			SignatureState::Unsigned => {
+				Style::default().fg(self.disabled_fg)
+			}
+		};
+
+		self.apply_select(style, selected)
+	}
+
 	pub fn commit_hash_in_blame(
 		&self,
 		is_blamed_commit: bool,
@@ -3,10 +3,18 @@ use crossbeam_channel::{unbounded, Sender};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
 use scopetime::scope_time;
-use std::{path::Path, thread, time::Duration};
+use std::{
+	path::{Path, PathBuf},
+	thread,
+	time::Duration,
+};
+
+/// paths (relative to the workdir) of changed worktree files,
+/// `None` if anything else (like the git dir) changed as well
+pub type ChangedPaths = Option<Vec<String>>;
 
 pub struct RepoWatcher {
-	receiver: crossbeam_channel::Receiver<()>,
+	receiver: crossbeam_channel::Receiver<ChangedPaths>,
 }
 
 impl RepoWatcher {
@@ -18,6 +26,7 @@ impl RepoWatcher {
 
 		let (tx, rx) = std::sync::mpsc::channel();
 
+		let root = PathBuf::from(workdir);
 		let workdir = workdir.to_string();
 
 		thread::spawn(move || {
@@ -28,7 +37,7 @@ impl RepoWatcher {
 		let (out_tx, out_rx) = unbounded();
 
 		thread::spawn(move || {
-			if let Err(e) = Self::forwarder(&rx, &out_tx) {
+			if let Err(e) = Self::forwarder(&rx, &out_tx, &root) {
 				//maybe we need to restart the forwarder now?
 				log::error!("notify receive error: {e}");
 			}
@@ -38,14 +47,23 @@ impl RepoWatcher {
 	}
 
 	///
-	pub fn receiver(&self) -> crossbeam_channel::Receiver<()> {
+	pub fn receiver(
+		&self,
+	) -> crossbeam_channel::Receiver<ChangedPaths> {
 		self.receiver.clone()
 	}
 
 	fn forwarder(
 		receiver: &std::sync::mpsc::Receiver<DebounceEventResult>,
-		sender: &Sender<()>,
+		sender: &Sender<ChangedPaths>,
+		workdir: &Path,
 	) -> Result<()> {
+		// events might be reported with symlinks resolved
+		let roots = [
+			workdir.to_path_buf(),
+			workdir.canonicalize().unwrap_or_else(|_| workdir.into()),
+		];
+
 		loop {
 			let ev = receiver.recv()?;
 
@@ -57,11 +75,40 @@ impl RepoWatcher {
 				}
 
 				if !ev.is_empty() {
-					sender.send(())?;
+					sender.send(Self::changed_paths(
+						&roots,
+						ev.iter().map(|ev| ev.path.as_path()),
+					))?;
 				}
 			}
 		}
 	}
+
+	fn changed_paths<'a>(
+		roots: &[PathBuf],
+		paths: impl Iterator<Item = &'a Path>,
+	) -> ChangedPaths {
+		let mut res = Vec::new();
+
+		for path in paths {
+			let relative = roots
+				.iter()
+				.find_map(|root| path.strip_prefix(root).ok())?;
+
+			if relative.as_os_str().is_empty()
+				|| relative.starts_with(".git")
+			{
+				return None;
+			}
+
+			res.push(relative.to_str()?.replace('\\', "/"));
+		}
+
+		res.sort();
+		res.dedup();
+
+		Some(res)
+	}
 }
 
 fn create_watcher(
@@ -80,3 +127,50 @@ fn create_watcher(
 
 	std::mem::forget(bouncer);
 }
+
+#[cfg(test)]
+mod tests {
+	use super::RepoWatcher;
+	use std::path::{Path, PathBuf};
+
+	#[test]
+	fn test_changed_paths() {
+		let roots = [PathBuf::from("/repo")];
+
+		assert_eq!(
+			RepoWatcher::changed_paths(
+				&roots,
+				[
+					Path::new("/repo/src/b.rs"),
+					Path::new("/repo/a.txt"),
+					Path::new("/repo/src/b.rs"),
+				]
+				.into_iter(),
+			),
+			Some(vec![
+				String::from("a.txt"),
+				String::from("src/b.rs")
+			])
+		);
+
+		assert_eq!(
+			RepoWatcher::changed_paths(
+				&roots,
+				[
+					Path::new("/repo/a.txt"),
+					Path::new("/repo/.git/index")
+				]
+				.into_iter(),
+			),
+			None
+		);
+
+		assert_eq!(
+			RepoWatcher::changed_paths(
+				&roots,
+				[Path::new("/elsewhere/a.txt")].into_iter(),
+			),
+			None
+		);
+	}
+}